@@ -1,5 +1,7 @@
 //! Trait bound utilities.
 
+#[cfg(all(unix, feature = "async"))]
+use futures_io::{AsyncRead as FuturesAsyncRead, AsyncWrite as FuturesAsyncWrite};
 use std::io::prelude::*;
 #[cfg(feature = "tokio")]
 use tokio::io::{AsyncRead as TokioAsyncRead, AsyncWrite as TokioAsyncWrite};
@@ -54,3 +56,11 @@ bound_util! {
     /// [Tokio's `AsyncWrite`](TokioAsyncWrite) by reference.
     RefTokioAsyncWrite of TokioAsyncWrite with Write mtd as_tokio_async_write
 }
+
+#[cfg(all(unix, feature = "async"))]
+bound_util! {
+    /// [futures-io's `AsyncRead`](FuturesAsyncRead) by reference.
+    RefFuturesAsyncRead  of FuturesAsyncRead  with Read  mtd as_futures_async_read
+    /// [futures-io's `AsyncWrite`](FuturesAsyncWrite) by reference.
+    RefFuturesAsyncWrite of FuturesAsyncWrite with Write mtd as_futures_async_write
+}
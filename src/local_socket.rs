@@ -18,13 +18,13 @@
 //! [`Stream`](traits::Stream) [`RecvHalf`](traits::RecvHalf), [`SendHalf`](traits::SendHalf)
 //! traits that are located in the [`traits`] module.
 //!
-//! Note that this dispatch is currently zero-cost on all platforms, as there is only one
-//! underlying local socket implementation per platform, with Windows only using named pipe based
-//! local sockets and Unix only using Unix-domain socket based local sockets, but this
-//! may change in the future with the introduction of support for
-//! [the Windows implementation of Unix-domain sockets][udswnd]. Even if that were to be
-//! introduced, the overhead of dispatch would still be insignificant compared to the overhead of
-//! making the system calls that perform the actual communication.
+//! On each platform, there is a native implementation of local sockets – named pipes on Windows
+//! and Unix-domain sockets on Unix – as well as a [portable fallback](tcp) that uses TCP over the
+//! loopback interface and is only selected by the [`LoopbackTcp`] name type. More native
+//! implementations may be added in the future, such as one based on
+//! [the Windows implementation of Unix-domain sockets][udswnd]. In any case, the overhead of
+//! dispatch is insignificant compared to the overhead of making the system calls that perform the
+//! actual communication.
 //!
 //! [udswnd]: https://devblogs.microsoft.com/commandline/af_unix-comes-to-windows/
 //!
@@ -44,6 +44,10 @@
 //! need to do to ensure reliable communication is use the appropriate name type on the platforms
 //! that are important to you.
 //!
+//! The one exception to this is the [loopback TCP](tcp) implementation, which requires clients to
+//! read a port file and present an authentication token before the stream can be used. Past that
+//! handshake, the guarantee above applies to it as well.
+//!
 //! ## Raw handle and file descriptor access
 //! The enum dispatchers purposely omit implementations of `{As,Into,From}Raw{Handle,Fd}`,
 //! `As{Handle,Fd}`, `From<Owned{HandleFd}>` and `Into<Owned{Handle,Fd}>`. To access those trait
@@ -61,15 +65,19 @@
 //! // Consuming a stream to get its file descriptor
 //! let fd = match stream {
 //!     LocalSocketStream::UdSocket(s) => OwnedFd::from(s),
+//!     LocalSocketStream::Tcp(s) => OwnedFd::from(s),
+//!     // The enums are non-exhaustive, as implementations may be added in the future
+//!     _ => unimplemented!(),
 //! };
 //! # let _ = fd;
 //!
 //! # let stream = LocalSocketStream::UdSocket(fd2.into());
 //! // Accessing a stream's file descriptor without taking ownership
-//! let stream_impl = match &stream {
-//!     LocalSocketStream::UdSocket(s) => s,
+//! let fd = match &stream {
+//!     LocalSocketStream::UdSocket(s) => s.as_fd(),
+//!     LocalSocketStream::Tcp(s) => s.as_fd(),
+//!     _ => unimplemented!(),
 //! };
-//! let fd = stream_impl.as_fd();
 //! # let _ = fd;
 //!
 //! // Listener, RecvHalf, and SendHalf work analogously.
@@ -90,8 +98,10 @@ mod stream {
 mod listener {
     pub(super) mod r#enum;
     pub(super) mod options;
+    pub(super) mod resilient;
     pub(super) mod r#trait;
 }
+pub mod tcp;
 
 /// Traits representing the interface of local sockets.
 pub mod traits {
@@ -99,6 +109,12 @@ pub mod traits {
         listener::r#trait::{Listener, ListenerExt},
         stream::r#trait::*,
     };
+    /// Traits for the async-io variants of local socket objects.
+    #[cfg(all(unix, feature = "async"))]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(all(unix, feature = "async"))))]
+    pub mod async_io {
+        pub use super::super::async_io::{listener::r#trait::*, stream::r#trait::*};
+    }
     /// Traits for the Tokio variants of local socket objects.
     #[cfg(feature = "tokio")]
     #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
@@ -112,6 +128,7 @@ pub use {
         options::ListenerOptions,
         r#enum::*,
         r#trait::{Incoming, ListenerNonblockingMode},
+        resilient::*,
     },
     name::*,
     peer_creds::*,
@@ -128,6 +145,48 @@ pub mod prelude {
     };
 }
 
+/// Asynchronous local sockets which work with any async runtime via the async-io reactor.
+///
+/// The types in this module implement the [`AsyncRead`](futures_io::AsyncRead) and
+/// [`AsyncWrite`](futures_io::AsyncWrite) traits from `futures-io` and are driven by the reactor
+/// of the [`async-io`](https://docs.rs/async-io) crate, which runs on a thread of its own. This
+/// makes them usable with `smol`, `async-std` and any other executor, including
+/// [`async_io::block_on()`](::async_io::block_on).
+///
+/// Everything said in the [documentation for sync local sockets](crate::local_socket) applies to
+/// the async-io versions of the corresponding items as well. Please read it before using
+/// async-io-based local sockets.
+///
+/// This module is only available on Unix, as the async-io reactor cannot wait for named pipes on
+/// Windows.
+#[cfg(all(unix, feature = "async"))]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(all(unix, feature = "async"))))]
+pub mod async_io {
+    pub(super) mod listener {
+        pub(in super::super) mod r#enum;
+        pub(in super::super) mod r#trait;
+    }
+    pub(super) mod stream {
+        pub(in super::super) mod r#enum;
+        pub(in super::super) mod r#trait;
+    }
+    pub use {listener::r#enum::*, stream::r#enum::*};
+
+    /// Like the [sync local socket prelude](super::prelude), but for async-io local sockets.
+    pub mod prelude {
+        pub use super::{
+            super::{
+                name::{NameType as _, ToFsName as _, ToNsName as _},
+                traits::{
+                    async_io::{Listener as _, Stream as _},
+                    StreamCommon as _,
+                },
+            },
+            Listener as LocalSocketListener, Stream as LocalSocketStream,
+        };
+    }
+}
+
 /// Asynchronous local sockets which work with the Tokio runtime and event loop.
 ///
 /// The Tokio integration allows the local socket streams and listeners to be notified by the OS
@@ -140,7 +199,8 @@ pub mod prelude {
 ///
 /// Types from this module will *not* work with other async runtimes, such as `async-std` or `smol`,
 /// since the Tokio types' methods will panic whenever they're called outside of a Tokio runtime
-/// context. Open an issue if you'd like to see other runtimes supported as well.
+/// context. On Unix, the `async_io` module, enabled by the `async` feature, provides local sockets
+/// that work with other runtimes.
 #[cfg(feature = "tokio")]
 #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
 pub mod tokio {
@@ -152,7 +212,14 @@ pub mod tokio {
         pub(in super::super) mod r#enum;
         pub(in super::super) mod r#trait;
     }
-    pub use {listener::r#enum::*, stream::r#enum::*};
+    pub(crate) mod timeouts;
+    pub use {
+        listener::{
+            r#enum::*,
+            r#trait::{ConnectionPermit, Incoming, LimitedIncoming},
+        },
+        stream::r#enum::*,
+    };
 
     /// Like the [sync local socket prelude](super::prelude), but for Tokio local sockets.
     pub mod prelude {
@@ -160,7 +227,7 @@ pub mod tokio {
             super::{
                 name::{NameType as _, ToFsName as _, ToNsName as _},
                 traits::{
-                    tokio::{Listener as _, Stream as _},
+                    tokio::{Listener as _, ListenerExt as _, Stream as _},
                     StreamCommon as _,
                 },
             },
@@ -0,0 +1,65 @@
+use {
+    super::r#trait,
+    crate::{
+        local_socket::{async_io::Stream, tcp::async_io as tcp_impl, ListenerOptions, Name},
+        os::unix::uds_local_socket::async_io as uds_impl,
+    },
+    std::io,
+};
+
+impmod! {local_socket::dispatch_async_io as dispatch}
+
+mkenum!(
+/// async-io-based local socket server, listening for connections.
+///
+/// This struct is created by [`ListenerOptions`](crate::local_socket::ListenerOptions).
+///
+/// See the [module-level documentation of local sockets](crate::local_socket) for more details.
+///
+/// [Name reclamation](crate::local_socket::Listener#name-reclamation) is performed by default
+/// when using local socket implementations that necessitate it.
+///
+/// # Examples
+///
+/// ## Basic server
+/// ```no_run
+/// use {
+///     futures_lite::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader},
+///     interprocess::local_socket::{
+///         async_io::prelude::*, GenericNamespaced, ListenerOptions,
+///     },
+/// };
+/// # async_io::block_on(async {
+/// let listener = ListenerOptions::new()
+///     .name("example.sock".to_ns_name::<GenericNamespaced>()?)
+///     .create_async_io()?;
+/// loop {
+///     let conn = listener.accept().await?;
+///     let mut conn = BufReader::new(conn);
+///     let mut line = String::new();
+///     conn.read_line(&mut line).await?;
+///     conn.get_mut().write_all(b"Hello from server!\n").await?;
+/// }
+/// # #[allow(unreachable_code)] Ok::<(), Box<dyn std::error::Error>>(()) });
+/// ```
+Listener);
+impl r#trait::Listener for Listener {
+    type Stream = Stream;
+
+    #[inline]
+    fn from_options(options: ListenerOptions<'_>) -> io::Result<Self> {
+        dispatch::listen(options)
+    }
+    #[inline]
+    async fn accept(&self) -> io::Result<Stream> {
+        dispatch!(Self: x in self => x.accept().await.map(Stream::from))
+    }
+    #[inline]
+    fn do_not_reclaim_name_on_drop(&mut self) {
+        dispatch!(Self: x in self => x.do_not_reclaim_name_on_drop())
+    }
+    #[inline]
+    fn local_name(&self) -> io::Result<Name<'static>> {
+        dispatch!(Self: x in self => x.local_name())
+    }
+}
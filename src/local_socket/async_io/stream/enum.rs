@@ -0,0 +1,180 @@
+use {
+    super::r#trait,
+    crate::{
+        local_socket::{
+            tcp::async_io as tcp_impl, traits::StreamCommon, ConnectOptions, Name, PeerCreds,
+        },
+        os::unix::uds_local_socket::async_io as uds_impl,
+    },
+    futures_io::{AsyncRead, AsyncWrite},
+    std::{
+        io::{self, IoSlice, IoSliceMut},
+        pin::Pin,
+        task::{Context, Poll},
+    },
+};
+
+impmod! {local_socket::dispatch_async_io as dispatch}
+
+macro_rules! dispatch_read {
+    (@iw $ty:ident) => {
+        #[inline]
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            dispatch!($ty: x in self.get_mut() => Pin::new(x).poll_read(cx, buf))
+        }
+        #[inline]
+        fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &mut [IoSliceMut<'_>]) -> Poll<io::Result<usize>> {
+            dispatch!($ty: x in self.get_mut() => Pin::new(x).poll_read_vectored(cx, bufs))
+        }
+    };
+    ($ty:ident) => {
+        impl AsyncRead for &$ty {
+            dispatch_read!(@iw $ty);
+        }
+        impl AsyncRead for $ty {
+            dispatch_read!(@iw $ty);
+        }
+    };
+}
+macro_rules! dispatch_write {
+    (@iw $ty:ident) => {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            dispatch!($ty: x in self.get_mut() => Pin::new(x).poll_write(cx, buf))
+        }
+        #[inline]
+        fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>]) -> Poll<io::Result<usize>> {
+            dispatch!($ty: x in self.get_mut() => Pin::new(x).poll_write_vectored(cx, bufs))
+        }
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    };
+    ($ty:ident) => {
+        /// Flushing and closing are always successful no-ops.
+        impl AsyncWrite for &$ty {
+            dispatch_write!(@iw $ty);
+        }
+        /// Flushing and closing are always successful no-ops.
+        impl AsyncWrite for $ty {
+            dispatch_write!(@iw $ty);
+        }
+    };
+}
+
+mkenum!(
+/// async-io-based local socket byte stream, obtained either from
+/// [`Listener`](super::super::Listener) or by connecting to an existing local socket.
+///
+/// See the [module-level documentation of local sockets](crate::local_socket) for more details.
+///
+/// # Examples
+///
+/// ## Basic client
+/// ```no_run
+/// use {
+///     futures_lite::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader},
+///     interprocess::local_socket::{async_io::prelude::*, GenericNamespaced},
+/// };
+/// # async_io::block_on(async {
+/// let name = "example.sock".to_ns_name::<GenericNamespaced>()?;
+/// let conn = LocalSocketStream::connect(name).await?;
+/// let (recver, mut sender) = conn.split();
+/// sender.write_all(b"Hello from client!\n").await?;
+/// let mut line = String::new();
+/// BufReader::new(recver).read_line(&mut line).await?;
+/// print!("Server answered: {line}");
+/// # Ok::<(), Box<dyn std::error::Error>>(()) });
+/// ```
+Stream);
+
+impl r#trait::Stream for Stream {
+    type RecvHalf = RecvHalf;
+    type SendHalf = SendHalf;
+
+    #[inline]
+    async fn from_options(options: &ConnectOptions<'_>) -> io::Result<Self> {
+        dispatch::connect(options).await
+    }
+    fn split(self) -> (RecvHalf, SendHalf) {
+        match self {
+            Stream::UdSocket(s) => {
+                let (rh, sh) = s.split();
+                (RecvHalf::UdSocket(rh), SendHalf::UdSocket(sh))
+            }
+            Stream::Tcp(s) => {
+                let (rh, sh) = s.split();
+                (RecvHalf::Tcp(rh), SendHalf::Tcp(sh))
+            }
+        }
+    }
+    fn reunite(rh: RecvHalf, sh: SendHalf) -> ReuniteResult {
+        match (rh, sh) {
+            (RecvHalf::UdSocket(rh), SendHalf::UdSocket(sh)) => {
+                uds_impl::Stream::reunite(rh, sh).map(From::from).map_err(|e| e.convert_halves())
+            }
+            (RecvHalf::Tcp(rh), SendHalf::Tcp(sh)) => {
+                tcp_impl::Stream::reunite(rh, sh).map(From::from).map_err(|e| e.convert_halves())
+            }
+            (rh, sh) => Err(ReuniteError { rh, sh }),
+        }
+    }
+}
+impl StreamCommon for Stream {
+    #[inline]
+    fn take_error(&self) -> io::Result<Option<io::Error>> {
+        dispatch!(Self: x in self => x.take_error())
+    }
+    #[inline]
+    fn peer_creds(&self) -> io::Result<PeerCreds> { dispatch!(Self: x in self => x.peer_creds()) }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> {
+        dispatch!(Self: x in self => x.local_name())
+    }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> {
+        dispatch!(Self: x in self => x.peer_name())
+    }
+}
+multimacro! {
+    Stream,
+    dispatch_read,
+    dispatch_write,
+}
+
+mkenum!(
+/// Receive half of an async-io-based local socket stream, obtained by splitting a [`Stream`].
+///
+/// See the [module-level documentation of local sockets](crate::local_socket) for more details.
+"local_socket::async_io::" RecvHalf);
+impl r#trait::RecvHalf for RecvHalf {
+    type Stream = Stream;
+}
+multimacro! {
+    RecvHalf,
+    dispatch_read,
+}
+
+mkenum!(
+/// Send half of an async-io-based local socket stream, obtained by splitting a [`Stream`].
+///
+/// See the [module-level documentation of local sockets](crate::local_socket) for more details.
+"local_socket::async_io::" SendHalf);
+impl r#trait::SendHalf for SendHalf {
+    type Stream = Stream;
+}
+multimacro! {
+    SendHalf,
+    dispatch_write,
+}
+
+/// [`ReuniteError`](crate::error::ReuniteError) for [`Stream`].
+pub type ReuniteError = crate::error::ReuniteError<RecvHalf, SendHalf>;
+
+/// Result type for [`.reunite()`](trait::Stream::reunite) on [`Stream`].
+pub type ReuniteResult = r#trait::ReuniteResult<Stream>;
@@ -11,6 +11,7 @@ macro_rules! dispatch {
             $ty::NamedPipe(arm) => dispatch!(@arm $nm $e),
             #[cfg(unix)]
             $ty::UdSocket(arm) => dispatch!(@arm $nm $e),
+            $ty::Tcp(arm) => dispatch!(@arm $nm $e),
         }
     }};
 }
@@ -18,6 +19,7 @@ macro_rules! dispatch {
 macro_rules! mkenum {
     ($(#[$($attr:tt)+])* $pref:literal $nm:ident) => {
         $(#[$($attr)+])*
+        #[non_exhaustive]
         pub enum $nm {
             /// Makes use of Windows named pipes.
             ///
@@ -31,6 +33,10 @@ macro_rules! mkenum {
             #[cfg(unix)]
             #[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
             UdSocket(uds_impl::$nm),
+            /// Makes use of TCP over the loopback interface.
+            ///
+            /// Click the struct name in the parentheses to learn more.
+            Tcp(tcp_impl::$nm),
         }
         impl $crate::Sealed for $nm {}
         #[cfg(windows)]
@@ -47,6 +53,11 @@ macro_rules! mkenum {
                 Self::UdSocket(x)
             }
         }
+        impl From<tcp_impl::$nm> for $nm {
+            fn from(x: tcp_impl::$nm) -> Self {
+                Self::Tcp(x)
+            }
+        }
         impl std::fmt::Debug for $nm {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 let mut dt = f.debug_tuple(concat!($pref, stringify!($nm)));
@@ -4,7 +4,7 @@ use crate::os::unix::uds_local_socket as uds_impl;
 use crate::os::windows::named_pipe::local_socket as np_impl;
 use {
     super::{options::ListenerOptions, r#trait},
-    crate::local_socket::{ListenerNonblockingMode, Stream},
+    crate::local_socket::{tcp as tcp_impl, ListenerNonblockingMode, Name, Stream},
     std::{io, iter::FusedIterator},
 };
 
@@ -63,7 +63,7 @@ impl r#trait::Listener for Listener {
     }
     #[inline]
     fn accept(&self) -> io::Result<Stream> {
-        dispatch!(Self: x in self => x.accept()).map(Stream::from)
+        dispatch!(Self: x in self => x.accept().map(Stream::from))
     }
     #[inline]
     fn set_nonblocking(&self, nonblocking: ListenerNonblockingMode) -> io::Result<()> {
@@ -73,6 +73,10 @@ impl r#trait::Listener for Listener {
     fn do_not_reclaim_name_on_drop(&mut self) {
         dispatch!(Self: x in self => x.do_not_reclaim_name_on_drop())
     }
+    #[inline]
+    fn local_name(&self) -> io::Result<Name<'static>> {
+        dispatch!(Self: x in self => x.local_name())
+    }
 }
 impl Iterator for Listener {
     type Item = io::Result<Stream>;
@@ -80,3 +84,20 @@ impl Iterator for Listener {
     fn next(&mut self) -> Option<Self::Item> { Some(r#trait::Listener::accept(self)) }
 }
 impl FusedIterator for Listener {}
+#[cfg(unix)]
+impl crate::os::unix::local_socket::ListenerExt for Listener {
+    #[inline]
+    fn accept_deadline(&self, deadline: std::time::Instant) -> io::Result<Stream> {
+        dispatch!(Self: x in self => x.accept_deadline(deadline).map(Stream::from))
+    }
+}
+#[cfg(unix)]
+impl std::os::unix::io::AsFd for Listener {
+    #[inline]
+    fn as_fd(&self) -> std::os::unix::io::BorrowedFd<'_> {
+        match self {
+            Self::UdSocket(l) => l.as_fd(),
+            Self::Tcp(l) => l.as_fd(),
+        }
+    }
+}
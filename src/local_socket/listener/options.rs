@@ -1,3 +1,5 @@
+#[cfg(all(unix, feature = "async"))]
+use crate::local_socket::async_io::Listener as AsyncIoListener;
 #[cfg(feature = "tokio")]
 use crate::local_socket::tokio::Listener as TokioListener;
 #[cfg(windows)]
@@ -16,11 +18,17 @@ use {
 /// Server-side builder for [local socket listeners](traits::Listener), including [`Listener`].
 pub struct ListenerOptions<'n> {
     pub(crate) name: Name<'n>,
-    flags: u8,
+    flags: u16,
     #[cfg(unix)]
     max_spin_time: std::time::Duration,
     #[cfg(unix)]
     mode: libc::mode_t,
+    #[cfg(unix)]
+    dir_mode: libc::mode_t,
+    #[cfg(unix)]
+    owner: libc::uid_t,
+    #[cfg(unix)]
+    group: libc::gid_t,
     #[cfg(windows)]
     pub(crate) security_descriptor: Option<SecurityDescriptor>,
 }
@@ -32,13 +40,23 @@ const SHFT_RECLAIM_NAME: u8 = 2;
 const SHFT_TRY_OVERWRITE: u8 = 3;
 const SHFT_HAS_MODE: u8 = 4;
 const SHFT_HAS_MAX_SPIN_TIME: u8 = 5;
+#[cfg(unix)]
+const SHFT_AUTOBIND: u8 = 6;
+#[cfg(unix)]
+const SHFT_HAS_DIR_MODE: u8 = 7;
+#[cfg(unix)]
+const SHFT_VERIFY_PARENT_DIR: u8 = 8;
+#[cfg(unix)]
+const SHFT_HAS_OWNER: u8 = 9;
+#[cfg(unix)]
+const SHFT_HAS_GROUP: u8 = 10;
 
-const ALL_BITS: u8 = (1 << 6) - 1;
-const NONBLOCKING_BITS: u8 = (1 << SHFT_NONBLOCKING_ACCEPT) | (1 << SHFT_NONBLOCKING_STREAM);
-const fn set_bit(flags: u8, pos: u8, val: bool) -> u8 {
-    flags & (ALL_BITS ^ (1 << pos)) | ((val as u8) << pos)
+const ALL_BITS: u16 = (1 << 11) - 1;
+const NONBLOCKING_BITS: u16 = (1 << SHFT_NONBLOCKING_ACCEPT) | (1 << SHFT_NONBLOCKING_STREAM);
+const fn set_bit(flags: u16, pos: u8, val: bool) -> u16 {
+    flags & (ALL_BITS ^ (1 << pos)) | ((val as u16) << pos)
 }
-const fn has_bit(flags: u8, pos: u8) -> bool { flags & (1 << pos) != 0 }
+const fn has_bit(flags: u16, pos: u8) -> bool { flags & (1 << pos) != 0 }
 
 impl TryClone for ListenerOptions<'_> {
     fn try_clone(&self) -> io::Result<Self> {
@@ -49,6 +67,12 @@ impl TryClone for ListenerOptions<'_> {
             max_spin_time: self.max_spin_time,
             #[cfg(unix)]
             mode: self.mode,
+            #[cfg(unix)]
+            dir_mode: self.dir_mode,
+            #[cfg(unix)]
+            owner: self.owner,
+            #[cfg(unix)]
+            group: self.group,
             #[cfg(windows)]
             security_descriptor: self
                 .security_descriptor
@@ -71,6 +95,12 @@ impl ListenerOptions<'_> {
             max_spin_time: std::time::Duration::ZERO,
             #[cfg(unix)]
             mode: 0,
+            #[cfg(unix)]
+            dir_mode: 0,
+            #[cfg(unix)]
+            owner: 0,
+            #[cfg(unix)]
+            group: 0,
             #[cfg(windows)]
             security_descriptor: None,
         }
@@ -89,7 +119,7 @@ impl<'n> ListenerOptions<'n> {
     #[must_use = builder_must_use!()]
     #[inline(always)]
     pub fn nonblocking(mut self, nonblocking: ListenerNonblockingMode) -> Self {
-        self.flags = (self.flags & (ALL_BITS ^ NONBLOCKING_BITS)) | nonblocking as u8;
+        self.flags = (self.flags & (ALL_BITS ^ NONBLOCKING_BITS)) | nonblocking as u16;
         self
     }
     /// Sets whether [name reclamation](Listener#name-reclamation) is to happen or not.
@@ -167,6 +197,34 @@ impl<'n> ListenerOptions<'n> {
         self.flags |= 1 << SHFT_HAS_MODE;
         self.mode = mode;
     }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[inline(always)]
+    pub(crate) fn set_autobind(&mut self, autobind: bool) {
+        self.flags = set_bit(self.flags, SHFT_AUTOBIND, autobind);
+    }
+    #[cfg(unix)]
+    #[inline(always)]
+    pub(crate) fn set_dir_mode(&mut self, dir_mode: libc::mode_t) {
+        self.flags |= 1 << SHFT_HAS_DIR_MODE;
+        self.dir_mode = dir_mode;
+    }
+    #[cfg(unix)]
+    #[inline(always)]
+    pub(crate) fn set_verify_parent_dir(&mut self, verify_parent_dir: bool) {
+        self.flags = set_bit(self.flags, SHFT_VERIFY_PARENT_DIR, verify_parent_dir);
+    }
+    #[cfg(unix)]
+    #[inline(always)]
+    pub(crate) fn set_owner(&mut self, owner: libc::uid_t) {
+        self.flags |= 1 << SHFT_HAS_OWNER;
+        self.owner = owner;
+    }
+    #[cfg(unix)]
+    #[inline(always)]
+    pub(crate) fn set_group(&mut self, group: libc::gid_t) {
+        self.flags |= 1 << SHFT_HAS_GROUP;
+        self.group = group;
+    }
 }
 
 /// Option getters.
@@ -184,6 +242,24 @@ impl ListenerOptions<'_> {
         has_bit(self.flags, SHFT_HAS_MODE).then_some(self.mode)
     }
     #[cfg(unix)]
+    pub(crate) fn get_autobind(&self) -> bool { has_bit(self.flags, SHFT_AUTOBIND) }
+    #[cfg(unix)]
+    pub(crate) fn get_dir_mode(&self) -> Option<libc::mode_t> {
+        has_bit(self.flags, SHFT_HAS_DIR_MODE).then_some(self.dir_mode)
+    }
+    #[cfg(unix)]
+    pub(crate) fn get_verify_parent_dir(&self) -> bool {
+        has_bit(self.flags, SHFT_VERIFY_PARENT_DIR)
+    }
+    #[cfg(unix)]
+    pub(crate) fn get_owner(&self) -> Option<libc::uid_t> {
+        has_bit(self.flags, SHFT_HAS_OWNER).then_some(self.owner)
+    }
+    #[cfg(unix)]
+    pub(crate) fn get_group(&self) -> Option<libc::gid_t> {
+        has_bit(self.flags, SHFT_HAS_GROUP).then_some(self.group)
+    }
+    #[cfg(unix)]
     pub(crate) fn get_max_spin_time(&self) -> Option<std::time::Duration> {
         has_bit(self.flags, SHFT_HAS_MAX_SPIN_TIME).then_some(self.max_spin_time)
     }
@@ -222,6 +298,23 @@ impl ListenerOptions<'_> {
     pub fn create_tokio_as<L: traits::tokio::Listener>(self) -> io::Result<L> {
         L::from_options(self)
     }
+    /// Creates an async-io [`Listener`](AsyncIoListener), binding it to the specified local
+    /// socket name.
+    ///
+    /// On platforms where there are multiple available implementations, this dispatches to the
+    /// appropriate implementation based on where the name points to.
+    #[inline]
+    #[cfg(all(unix, feature = "async"))]
+    pub fn create_async_io(self) -> io::Result<AsyncIoListener> {
+        self.create_async_io_as::<AsyncIoListener>()
+    }
+    /// Creates the given [type of listener](traits::async_io::Listener), binding it to the
+    /// specified local socket name.
+    #[inline]
+    #[cfg(all(unix, feature = "async"))]
+    pub fn create_async_io_as<L: traits::async_io::Listener>(self) -> io::Result<L> {
+        L::from_options(self)
+    }
 }
 
 impl Default for ListenerOptions<'_> {
@@ -247,7 +340,15 @@ impl Debug for ListenerOptions<'_> {
         #[cfg(unix)]
         {
             // FIXME not octal
-            dbs.field("mode", &self.get_mode());
+            dbs.field("mode", &self.get_mode())
+                .field("dir_mode", &self.get_dir_mode())
+                .field("verify_parent_dir", &self.get_verify_parent_dir())
+                .field("owner", &self.get_owner())
+                .field("group", &self.get_group());
+        }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            dbs.field("autobind", &self.get_autobind());
         }
         #[cfg(windows)]
         {
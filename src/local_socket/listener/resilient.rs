@@ -0,0 +1,289 @@
+#[cfg(feature = "tokio")]
+use crate::local_socket::traits::tokio as traits_tokio;
+use {
+    crate::local_socket::traits,
+    std::{
+        fmt::{self, Debug, Formatter},
+        io,
+        time::Duration,
+    },
+};
+
+/// Classification of errors returned by [`accept()`](traits::Listener::accept), as done by
+/// [`ResilientAccept`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AcceptErrorClass {
+    /// The error only concerns the client that was being accepted, such as the client having
+    /// disconnected before it could be accepted (`ECONNABORTED`) or the call having been
+    /// interrupted by a signal (`EINTR`). Accepting can be retried immediately.
+    Transient,
+    /// The process or the system has run out of file descriptors (`EMFILE`, `ENFILE`) or memory
+    /// (`ENOBUFS`, `ENOMEM`). Accepting can be retried after resources have been freed up.
+    ResourceExhaustion,
+    /// Any other error. This includes [`WouldBlock`](io::ErrorKind::WouldBlock) and
+    /// [`TimedOut`](io::ErrorKind::TimedOut), as well as errors that indicate that the listener is
+    /// unusable.
+    Fatal,
+}
+impl AcceptErrorClass {
+    /// Classifies the given error.
+    pub fn of(e: &io::Error) -> Self {
+        #[cfg(unix)]
+        if let Some(code) = e.raw_os_error() {
+            match code {
+                libc::EPROTO => return Self::Transient,
+                libc::EMFILE | libc::ENFILE | libc::ENOBUFS => return Self::ResourceExhaustion,
+                _ => {}
+            }
+        }
+        match e.kind() {
+            io::ErrorKind::ConnectionAborted | io::ErrorKind::Interrupted => Self::Transient,
+            io::ErrorKind::OutOfMemory => Self::ResourceExhaustion,
+            _ => Self::Fatal,
+        }
+    }
+}
+
+/// Counters of the errors that a [`ResilientAccept`] has encountered, by
+/// [class](AcceptErrorClass).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AcceptStats {
+    /// Number of [transient](AcceptErrorClass::Transient) errors that were retried.
+    pub transient: u64,
+    /// Number of [resource exhaustion](AcceptErrorClass::ResourceExhaustion) errors that were
+    /// backed off from.
+    pub resource_exhaustion: u64,
+    /// Number of [fatal](AcceptErrorClass::Fatal) errors that were returned.
+    pub fatal: u64,
+    /// Number of clients that were accepted and immediately disconnected to keep them from
+    /// waiting while the process is out of file descriptors.
+    pub dropped: u64,
+}
+
+type ErrorCallback = Box<dyn FnMut(AcceptErrorClass, &io::Error) + Send>;
+
+/// How long [`ResilientAccept::accept_tokio()`] waits for the client that it is to disconnect.
+#[cfg(feature = "tokio")]
+const SPARE_ACCEPT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Accept loop that survives transient errors and resource exhaustion.
+///
+/// A naïve server loop that iterates through [`incoming()`](traits::ListenerExt::incoming) and
+/// ignores errors spins at 100% CPU once the process runs out of file descriptors, since
+/// `accept()` then fails immediately while the client that is waiting to be accepted stays in the
+/// queue. `ResilientAccept` wraps `accept()` calls, [classifying](AcceptErrorClass) the errors
+/// they return:
+/// - transient errors are retried immediately;
+/// - on resource exhaustion, the waiting client is accepted and immediately disconnected using a
+///   file descriptor reserved in advance (Unix only), which lets the client know that it won't
+///   be served instead of leaving it waiting, and further attempts are backed off from
+///   exponentially;
+/// - fatal errors are returned.
+///
+/// Each error is reported to an [optional callback](Self::on_error) and counted in
+/// [`stats()`](Self::stats).
+///
+/// ```no_run
+/// use interprocess::local_socket::{
+///     prelude::*, GenericNamespaced, ListenerOptions, ResilientAccept,
+/// };
+/// let listener = ListenerOptions::new()
+///     .name("example.sock".to_ns_name::<GenericNamespaced>()?)
+///     .create_sync()?;
+/// let mut resilient =
+///     ResilientAccept::new().on_error(|class, e| eprintln!("accept failed ({class:?}): {e}"));
+/// loop {
+///     let conn = resilient.accept(&listener)?;
+///     // Handle the connection...
+/// #   drop(conn);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Implementation notes
+/// The spare file descriptor is an open handle to `/dev/null`. Releasing it and accepting the
+/// waiting client is only done for `EMFILE` and `ENFILE`. If the listener is in blocking mode and
+/// the client disconnects in the meantime, the `accept()` call that is meant to dequeue it blocks
+/// until the next client arrives, which is then disconnected.
+/// `accept_tokio()` does not have this problem, as it gives up on dequeuing the client after
+/// 100 milliseconds.
+pub struct ResilientAccept {
+    #[cfg(unix)]
+    spare: Option<std::fs::File>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    backoff: Duration,
+    on_error: Option<ErrorCallback>,
+    stats: AcceptStats,
+}
+
+/// Creation and configuration.
+impl ResilientAccept {
+    /// Creates a resilient accept loop with the default backoff parameters, reserving a spare file
+    /// descriptor on Unix.
+    ///
+    /// Failure to reserve the spare file descriptor is not an error – it will be attempted again
+    /// after every resource exhaustion event.
+    pub fn new() -> Self {
+        const INITIAL_BACKOFF: Duration = Duration::from_millis(10);
+        Self {
+            #[cfg(unix)]
+            spare: open_spare(),
+            initial_backoff: INITIAL_BACKOFF,
+            max_backoff: Duration::from_secs(1),
+            backoff: INITIAL_BACKOFF,
+            on_error: None,
+            stats: AcceptStats::default(),
+        }
+    }
+    /// Sets the amount of time to wait after the first resource exhaustion error, which is doubled
+    /// after every consecutive one up to `max`. A successful accept resets it.
+    ///
+    /// The defaults are 10 milliseconds and 1 second.
+    #[must_use = builder_must_use!()]
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self.backoff = initial;
+        self
+    }
+    /// Sets a callback to be called with every error encountered, along with its class, before
+    /// it is retried, backed off from or returned.
+    #[must_use = builder_must_use!()]
+    pub fn on_error(
+        mut self,
+        f: impl FnMut(AcceptErrorClass, &io::Error) + Send + 'static,
+    ) -> Self {
+        self.on_error = Some(Box::new(f));
+        self
+    }
+    /// Returns the counters of the errors encountered so far.
+    #[inline]
+    pub fn stats(&self) -> AcceptStats { self.stats }
+}
+
+/// Accepting.
+impl ResilientAccept {
+    /// Accepts a connection from the given listener, retrying on transient errors and backing off
+    /// on resource exhaustion, until it succeeds or a fatal error occurs.
+    pub fn accept<L: traits::Listener>(&mut self, listener: &L) -> io::Result<L::Stream> {
+        loop {
+            let e = match listener.accept() {
+                Ok(stream) => return Ok(self.succeeded(stream)),
+                Err(e) => e,
+            };
+            match self.record(&e) {
+                AcceptErrorClass::Transient => continue,
+                AcceptErrorClass::ResourceExhaustion => {
+                    if self.release_spare(&e) {
+                        // The client is disconnected before the spare is reopened
+                        let dropped = listener.accept().is_ok();
+                        self.restore_spare(dropped);
+                    }
+                    std::thread::sleep(self.next_backoff());
+                }
+                _ => return Err(e),
+            }
+        }
+    }
+    /// Like [`accept()`](Self::accept), but for Tokio listeners.
+    ///
+    /// # Panics
+    /// If resource exhaustion is encountered outside of a Tokio runtime with the time driver
+    /// enabled, as backing off is done via [`tokio::time::sleep()`].
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
+    pub async fn accept_tokio<L: traits_tokio::Listener>(
+        &mut self,
+        listener: &L,
+    ) -> io::Result<L::Stream> {
+        loop {
+            let e = match listener.accept().await {
+                Ok(stream) => return Ok(self.succeeded(stream)),
+                Err(e) => e,
+            };
+            match self.record(&e) {
+                AcceptErrorClass::Transient => continue,
+                AcceptErrorClass::ResourceExhaustion => {
+                    if self.release_spare(&e) {
+                        // The client may have disconnected in the meantime
+                        let accept =
+                            tokio::time::timeout(SPARE_ACCEPT_TIMEOUT, listener.accept());
+                        let dropped = matches!(accept.await, Ok(Ok(..)));
+                        self.restore_spare(dropped);
+                    }
+                    tokio::time::sleep(self.next_backoff()).await;
+                }
+                _ => return Err(e),
+            }
+        }
+    }
+
+    fn succeeded<S>(&mut self, stream: S) -> S {
+        self.backoff = self.initial_backoff;
+        stream
+    }
+    fn record(&mut self, e: &io::Error) -> AcceptErrorClass {
+        let class = AcceptErrorClass::of(e);
+        let counter = match class {
+            AcceptErrorClass::Transient => &mut self.stats.transient,
+            AcceptErrorClass::ResourceExhaustion => &mut self.stats.resource_exhaustion,
+            AcceptErrorClass::Fatal => &mut self.stats.fatal,
+        };
+        *counter = counter.saturating_add(1);
+        if let Some(f) = &mut self.on_error {
+            f(class, e);
+        }
+        class
+    }
+    fn next_backoff(&mut self) -> Duration {
+        let backoff = self.backoff;
+        self.backoff = backoff.saturating_mul(2).min(self.max_backoff);
+        backoff
+    }
+    /// Closes the spare file descriptor if the error is caused by a lack of file descriptors,
+    /// returning whether that happened.
+    #[cfg(unix)]
+    fn release_spare(&mut self, e: &io::Error) -> bool {
+        matches!(e.raw_os_error(), Some(libc::EMFILE | libc::ENFILE))
+            && self.spare.take().is_some()
+    }
+    #[cfg(not(unix))]
+    fn release_spare(&mut self, _: &io::Error) -> bool { false }
+    /// Counts the dropped client, if any, and reserves the spare file descriptor again.
+    fn restore_spare(&mut self, dropped: bool) {
+        if dropped {
+            self.stats.dropped = self.stats.dropped.saturating_add(1);
+        }
+        #[cfg(unix)]
+        {
+            self.spare = open_spare();
+        }
+    }
+}
+
+impl Default for ResilientAccept {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+
+impl Debug for ResilientAccept {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut dbs = f.debug_struct("ResilientAccept");
+        #[cfg(unix)]
+        {
+            dbs.field("spare", &self.spare);
+        }
+        dbs.field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("backoff", &self.backoff)
+            .field("on_error", &self.on_error.is_some())
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+#[cfg(unix)]
+fn open_spare() -> Option<std::fs::File> { std::fs::File::open("/dev/null").ok() }
@@ -2,7 +2,7 @@
 
 use {
     crate::{
-        local_socket::{stream::r#trait::Stream, ListenerOptions},
+        local_socket::{stream::r#trait::Stream, ListenerOptions, Name},
         Sealed,
     },
     std::{fmt::Debug, io, iter::FusedIterator},
@@ -53,6 +53,20 @@ pub trait Listener:
     /// Disables [name reclamation](super::enum::Listener#name-reclamation) on the listener.
     fn do_not_reclaim_name_on_drop(&mut self);
 
+    /// Returns the name the listener is bound to.
+    ///
+    /// ## Platform-specific behavior
+    /// ### Unix
+    /// The name is retrieved with `getsockname`, which means that names produced by
+    /// [`GenericNamespaced`](crate::local_socket::GenericNamespaced) on platforms without an
+    /// abstract namespace are returned as the filesystem paths they were resolved to. On Linux,
+    /// this is how the name assigned by
+    /// [autobind](crate::os::unix::local_socket::ListenerOptionsExt::autobind) is obtained.
+    ///
+    /// ### Windows
+    /// The path of the named pipe is returned.
+    fn local_name(&self) -> io::Result<Name<'static>>;
+
     /// Creates a socket server using the specified options.
     ///
     /// This method typically shouldn't be called directly – use the creation methods on
@@ -1,9 +1,13 @@
+mod error;
+mod generator;
 mod inner;
+mod text;
 pub(super) mod to_name;
 pub(super) mod r#type;
 
 pub(crate) use self::inner::*;
-pub use {r#type::*, to_name::*};
+use crate::Sealed;
+pub use {error::*, generator::*, r#type::*, text::*, to_name::*};
 
 /// Name for a local socket.
 ///
@@ -24,8 +28,54 @@ pub use {r#type::*, to_name::*};
 ///
 /// Instances of this type cannot be constructed from unsupported values. They can, however, be
 /// constructed from invalid ones.
+///
+/// # Textual representation
+/// Names can be converted to and from strings of the form `scheme:name` via
+/// [`Display`](std::fmt::Display) and [`FromStr`](std::str::FromStr), which is useful for storing
+/// them in configuration files. The following schemes are available:
+///
+/// | Scheme        | Platforms      | Name type                                       |
+/// |---------------|----------------|-------------------------------------------------|
+/// | `unix:`       | Unix           | `FilesystemUdSocket`                            |
+/// | `abstract:`   | Linux, Android | `AbstractNsUdSocket`                            |
+/// | `pipe:`       | Windows        | `NamedPipe` (paths)                             |
+/// | `ns:`         | All            | [`GenericNamespaced`](super::GenericNamespaced) |
+/// | `tcp:`        | All            | [`LoopbackTcp`](super::LoopbackTcp) (paths)     |
+/// | `specialdir:` | Unix           | `SpecialDirUdSocket`                            |
+///
+/// Parsing a scheme that is not available on the current platform fails with
+/// [`NameParseError::UnsupportedScheme`].
+///
+/// Since `ns:` maps to a different kind of name on each platform, displaying such a name yields
+/// the scheme of the name type it was mapped to (`abstract:` on Linux, `pipe:` on Windows, and
+/// `specialdir:` on other Unices). Likewise, namespaced [`LoopbackTcp`](super::LoopbackTcp)
+/// names are displayed with the `tcp:` scheme and the path of their port file. Parsing the result
+/// produces an equal name.
+///
+/// The characters `%` and control characters (including the nul character) are written as
+/// percent escapes of the form `%XX`, where `XX` is the hexadecimal value of a byte of their
+/// UTF-8 encoding on Unix and of a UTF-16 code unit on Windows. Bytes that are not valid UTF-8 on
+/// Unix are escaped in the same way, as are unpaired surrogates on Windows, which use the form
+/// `%uXXXX`. Any code unit may be escaped when parsing.
+///
+/// ```
+/// use interprocess::local_socket::{prelude::*, GenericNamespaced, Name};
+/// let name = "example.sock".to_ns_name::<GenericNamespaced>()?;
+/// let parsed = name.to_string().parse::<Name<'static>>()?;
+/// assert_eq!(parsed, name);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Serde
+/// With the `serde` feature enabled, names implement `Serialize`, and `Name<'static>` implements
+/// `Deserialize`. A name is represented as an enum whose variant is the scheme and whose content
+/// is the escaped name, so that in JSON, `unix:/run/app.sock` becomes
+/// `{"unix":"/run/app.sock"}`. This representation is the same on every platform; deserializing
+/// a name whose scheme is not available on the current platform fails, as does deserializing a
+/// name that its name type rejects.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Name<'s>(pub(crate) NameInner<'s>);
+impl Sealed for Name<'_> {}
 impl Name<'_> {
     /// Returns `true` if the name points to a dedicated local socket namespace, `false` otherwise.
     #[inline]
@@ -0,0 +1,86 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+/// Error returned when a string cannot be [mapped](super::NameType) to a local socket name, or
+/// when a name cannot be used by the underlying OS.
+///
+/// This type is convertible into [`io::Error`], and I/O errors produced by local socket creation
+/// that are caused by the name carry it as their [inner error](io::Error::get_ref).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum NameError {
+    /// The name is longer than the underlying OS allows.
+    TooLong {
+        /// The maximum length, in bytes on Unix and UTF-16 code units on Windows.
+        max: usize,
+        /// The length of the name, in the same units as `max`.
+        actual: usize,
+    },
+    /// The name contains a nul character, which the name type does not allow, at the given
+    /// position (in bytes on Unix and UTF-16 code units on Windows).
+    InteriorNul(usize),
+    /// The name type is not supported on the current platform or in the runtime circumstances of
+    /// the program.
+    Unsupported,
+    /// The name does not start with a prefix required by the name type, such as `\\.\pipe\` for
+    /// named pipes.
+    InvalidPrefix,
+    /// The name is required to be a single non-empty path component other than `.` and `..`, but
+    /// is not.
+    InvalidComponent,
+    /// An I/O error occurred while the name was being mapped.
+    Io(io::Error),
+}
+impl NameError {
+    /// Returns the [kind](io::ErrorKind) of the [`io::Error`] that this error converts into.
+    pub fn kind(&self) -> io::ErrorKind {
+        match self {
+            Self::TooLong { .. } | Self::InteriorNul(..) | Self::InvalidComponent => {
+                io::ErrorKind::InvalidInput
+            }
+            Self::Unsupported | Self::InvalidPrefix => io::ErrorKind::Unsupported,
+            Self::Io(e) => e.kind(),
+        }
+    }
+}
+impl Display for NameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLong { max, actual } => {
+                write!(f, "local socket name is too long ({actual}, maximum is {max})")
+            }
+            Self::InteriorNul(pos) => {
+                write!(f, "local socket name contains a nul character at position {pos}")
+            }
+            Self::Unsupported => f.write_str("local socket name type is not supported"),
+            Self::InvalidPrefix => f.write_str("local socket name does not have a valid prefix"),
+            Self::InvalidComponent => {
+                f.write_str("local socket name is not a single non-empty path component")
+            }
+            Self::Io(e) => write!(f, "could not map local socket name: {e}"),
+        }
+    }
+}
+impl Error for NameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+impl From<io::Error> for NameError {
+    #[inline]
+    fn from(e: io::Error) -> Self { Self::Io(e) }
+}
+impl From<NameError> for io::Error {
+    fn from(e: NameError) -> Self {
+        match e {
+            NameError::Io(e) => e,
+            e => io::Error::new(e.kind(), e),
+        }
+    }
+}
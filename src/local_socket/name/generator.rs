@@ -0,0 +1,187 @@
+#[cfg(feature = "tokio")]
+use crate::local_socket::tokio::Listener as TokioListener;
+use {
+    super::{r#type::NamespacedNameType, Name, NameError},
+    crate::{
+        local_socket::{traits, Listener, ListenerOptions},
+        TryClone,
+    },
+    std::{
+        borrow::Cow,
+        collections::hash_map::RandomState,
+        ffi::OsStr,
+        fmt::Write as _,
+        hash::{BuildHasher, Hasher},
+        io,
+        marker::PhantomData,
+        sync::atomic::{AtomicU64, Ordering::Relaxed},
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Generator of unique [local socket names](Name) for ephemeral listeners.
+///
+/// Names are made by appending 16 random hexadecimal digits to a prefix, which is
+/// `interprocess-` by default, and mapping the result via the
+/// [namespaced name type](NamespacedNameType) `NT`. The random part is derived from a
+/// randomly-keyed hash of the process ID, the current time and a process-wide counter, which makes
+/// collisions, both within one process and with names generated elsewhere, highly unlikely.
+/// To bind to a filesystem path in a private directory instead of a namespace, use
+/// `RuntimeDirUdSocket` as the name type.
+///
+/// The generator is an infinite [iterator](Iterator) of names. To deal with the possibility of a
+/// collision, listeners can be created via [`create_sync()`](Self::create_sync) and its
+/// counterparts, which retry with a new name if the one they tried is already in use. The name
+/// the listener ended up being bound to can be retrieved via
+/// [`local_name()`](traits::Listener::local_name).
+///
+/// ```no_run
+/// use interprocess::local_socket::{prelude::*, GenericNamespaced, ListenerOptions, NameGenerator};
+/// let listener = NameGenerator::<GenericNamespaced>::with_prefix("example-")?
+///     .create_sync(ListenerOptions::new())?;
+/// println!("Listening on {}", listener.local_name()?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct NameGenerator<NT> {
+    prefix: Cow<'static, str>,
+    attempts: u32,
+    _nt: PhantomData<fn() -> NT>,
+}
+
+const DEFAULT_ATTEMPTS: u32 = 16;
+
+/// Creation and configuration.
+impl<NT: NamespacedNameType<OsStr>> NameGenerator<NT> {
+    /// Creates a generator with the default prefix, `interprocess-`.
+    ///
+    /// # Errors
+    /// [`NameError::Unsupported`] if the name type
+    /// [is not supported](super::NameType::is_supported).
+    #[inline]
+    pub fn new() -> Result<Self, NameError> { Self::with_prefix("interprocess-") }
+    /// Creates a generator with the given prefix.
+    ///
+    /// # Errors
+    /// [`NameError::Unsupported`] if the name type
+    /// [is not supported](super::NameType::is_supported), or the error the name type returns when
+    /// mapping a name with the given prefix, such as [`NameError::TooLong`] if the prefix does not
+    /// leave room for the random part within the length limit of the name type.
+    pub fn with_prefix(prefix: impl Into<Cow<'static, str>>) -> Result<Self, NameError> {
+        if !NT::is_supported() {
+            return Err(NameError::Unsupported);
+        }
+        let slf = Self { prefix: prefix.into(), attempts: DEFAULT_ATTEMPTS, _nt: PhantomData };
+        slf.generate()?;
+        Ok(slf)
+    }
+    /// Sets the number of names [listener creation](Self::create_sync) tries before giving up.
+    ///
+    /// The default value is 16. Zero is treated as one.
+    #[must_use = builder_must_use!()]
+    #[inline]
+    pub fn attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    /// Generates a new name.
+    pub fn generate(&self) -> Result<Name<'static>, NameError> {
+        let mut name = String::with_capacity(self.prefix.len().saturating_add(16));
+        name.push_str(&self.prefix);
+        let _ = write!(name, "{:016x}", random_suffix());
+        NT::map(Cow::Owned(name.into())).map(Name::into_owned)
+    }
+}
+
+/// Listener creation.
+impl<NT: NamespacedNameType<OsStr>> NameGenerator<NT> {
+    /// Creates a [`Listener`] from the given options, binding it to a generated name and
+    /// retrying with a new one if that name is already in use. On Windows, where a taken named
+    /// pipe name is indistinguishable from an access denial, permission errors are retried too.
+    ///
+    /// The name set in the options is ignored, and
+    /// [`try_overwrite`](ListenerOptions::try_overwrite) is disabled, as overwriting a
+    /// colliding listener would defeat the purpose.
+    #[inline]
+    pub fn create_sync(&self, options: ListenerOptions<'_>) -> io::Result<Listener> {
+        self.create_sync_as::<Listener>(options)
+    }
+    /// Creates the given [type of listener](traits::Listener) in the same manner as
+    /// [`create_sync()`](Self::create_sync).
+    pub fn create_sync_as<L: traits::Listener>(
+        &self,
+        options: ListenerOptions<'_>,
+    ) -> io::Result<L> {
+        self.retry(options, ListenerOptions::create_sync_as::<L>)
+    }
+    /// Creates a Tokio [`Listener`](TokioListener) in the same manner as
+    /// [`create_sync()`](Self::create_sync).
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
+    #[inline]
+    pub fn create_tokio(&self, options: ListenerOptions<'_>) -> io::Result<TokioListener> {
+        self.create_tokio_as::<TokioListener>(options)
+    }
+    /// Creates the given [type of Tokio listener](traits::tokio::Listener) in the same manner as
+    /// [`create_sync()`](Self::create_sync).
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
+    pub fn create_tokio_as<L: traits::tokio::Listener>(
+        &self,
+        options: ListenerOptions<'_>,
+    ) -> io::Result<L> {
+        self.retry(options, ListenerOptions::create_tokio_as::<L>)
+    }
+
+    fn retry<'n, L>(
+        &self,
+        options: ListenerOptions<'n>,
+        mut create: impl FnMut(ListenerOptions<'n>) -> io::Result<L>,
+    ) -> io::Result<L> {
+        let options = options.try_overwrite(false);
+        let mut attempts = self.attempts;
+        loop {
+            let options = options.try_clone()?.name(self.generate()?);
+            attempts = attempts.saturating_sub(1);
+            match create(options) {
+                Err(e) if attempts > 0 && is_collision(&e) => continue,
+                otherwise => return otherwise,
+            }
+        }
+    }
+}
+
+impl<NT> Iterator for NameGenerator<NT>
+where
+    NT: NamespacedNameType<OsStr>,
+{
+    type Item = Result<Name<'static>, NameError>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> { Some(self.generate()) }
+}
+
+/// Whether the error indicates that the name is taken. Other errors would most likely recur with a
+/// different name, and are returned right away.
+///
+/// On Windows, creating the first instance of a named pipe that already exists fails with
+/// `ERROR_ACCESS_DENIED`, so permission errors count as collisions there. A permission error with
+/// a different cause is thus only returned once all attempts are used up.
+fn is_collision(e: &io::Error) -> bool {
+    match e.kind() {
+        io::ErrorKind::AddrInUse | io::ErrorKind::AlreadyExists => true,
+        io::ErrorKind::PermissionDenied => cfg!(windows),
+        _ => false,
+    }
+}
+
+fn random_suffix() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    hasher.write_u64(COUNTER.fetch_add(1, Relaxed));
+    if let Ok(time) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(time.as_nanos());
+    }
+    hasher.finish()
+}
@@ -1,6 +1,4 @@
-use std::borrow::Cow;
-#[cfg(unix)]
-use std::ffi::OsStr;
+use std::{borrow::Cow, ffi::OsStr};
 #[cfg(windows)]
 use widestring::U16CStr;
 
@@ -15,6 +13,7 @@ pub(crate) enum NameInner<'s> {
     UdSocketPseudoNs(Cow<'s, OsStr>),
     #[cfg(any(target_os = "linux", target_os = "android"))]
     UdSocketNs(Cow<'s, [u8]>),
+    Tcp(Cow<'s, OsStr>),
 }
 
 impl Default for NameInner<'_> {
@@ -42,6 +41,7 @@ macro_rules! map_cow {
             NameInner::UdSocketPseudoNs($nm) => NameInner::UdSocketPseudoNs($e),
             #[cfg(any(target_os = "linux", target_os = "android"))]
             NameInner::UdSocketNs($nm) => NameInner::UdSocketNs($e),
+            NameInner::Tcp($nm) => NameInner::Tcp($e),
         }
     };
 }
@@ -58,6 +58,7 @@ impl NameInner<'_> {
             Self::UdSocketPseudoNs(..) => false,
             #[cfg(any(target_os = "linux", target_os = "android"))]
             Self::UdSocketNs(..) => true,
+            Self::Tcp(..) => false,
         }
     }
     #[inline]
@@ -71,9 +72,13 @@ impl NameInner<'_> {
             Self::UdSocketPseudoNs(..) => false,
             #[cfg(any(target_os = "linux", target_os = "android"))]
             Self::UdSocketNs(..) => false,
+            Self::Tcp(..) => true,
         }
     }
 
+    #[inline]
+    pub const fn is_tcp(&self) -> bool { matches!(self, Self::Tcp(..)) }
+
     #[inline]
     pub fn borrow(&self) -> NameInner<'_> { map_cow!(cow in self => Cow::Borrowed(cow)) }
 
@@ -0,0 +1,306 @@
+//! Textual representation of local socket names.
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::os::unix::local_socket::AbstractNsUdSocket;
+use {
+    super::{
+        r#type::{GenericNamespaced, LoopbackTcp, NamespacedNameType as _, PathNameType},
+        Name, NameError, NameInner,
+    },
+    std::{
+        borrow::Cow,
+        error::Error,
+        ffi::{OsStr, OsString},
+        fmt::{self, Display, Formatter, Write as _},
+        io,
+        str::{self, FromStr},
+    },
+};
+#[cfg(unix)]
+#[allow(deprecated)]
+use {
+    crate::os::unix::local_socket::{FilesystemUdSocket, SpecialDirUdSocket},
+    std::os::unix::prelude::*,
+};
+#[cfg(windows)]
+use {crate::os::windows::local_socket::NamedPipe, std::os::windows::prelude::*};
+
+/// Code unit of the platform's native string encoding.
+#[cfg(unix)]
+type Unit = u8;
+#[cfg(windows)]
+type Unit = u16;
+
+/// Every scheme, along with whether it is supported on the current platform.
+const SCHEMES: &[(&str, bool)] = &[
+    ("unix", cfg!(unix)),
+    ("abstract", cfg!(any(target_os = "linux", target_os = "android"))),
+    ("ns", true),
+    ("pipe", cfg!(windows)),
+    ("tcp", true),
+    ("specialdir", cfg!(unix)),
+];
+
+/// Error returned by the [`FromStr`] implementation of [`Name`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum NameParseError {
+    /// The string does not start with a `scheme:` prefix.
+    MissingScheme,
+    /// The scheme is not known to Interprocess.
+    UnknownScheme(String),
+    /// The scheme is known, but the type of name it denotes is not available on the current
+    /// platform.
+    UnsupportedScheme(&'static str),
+    /// A percent escape at the given byte offset into the string is malformed.
+    InvalidEscape(usize),
+    /// The name itself was rejected by the [name type](super::NameType) the scheme maps to.
+    InvalidName(NameError),
+}
+impl Display for NameParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingScheme => f.write_str("local socket name has no scheme"),
+            Self::UnknownScheme(s) => write!(f, "unknown local socket name scheme {s:?}"),
+            Self::UnsupportedScheme(s) => {
+                write!(f, "local socket name scheme {s:?} is not supported on this platform")
+            }
+            Self::InvalidEscape(pos) => write!(f, "invalid percent escape at offset {pos}"),
+            Self::InvalidName(e) => write!(f, "invalid local socket name: {e}"),
+        }
+    }
+}
+impl Error for NameParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidName(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+impl From<NameParseError> for io::Error {
+    fn from(e: NameParseError) -> Self {
+        match e {
+            NameParseError::InvalidName(e) => e.into(),
+            NameParseError::UnsupportedScheme(..) => {
+                io::Error::new(io::ErrorKind::Unsupported, e)
+            }
+            e => io::Error::new(io::ErrorKind::InvalidInput, e),
+        }
+    }
+}
+
+impl FromStr for Name<'static> {
+    type Err = NameParseError;
+    fn from_str(s: &str) -> Result<Self, NameParseError> {
+        let (scheme, body) = s.split_once(':').ok_or(NameParseError::MissingScheme)?;
+        // Cannot overflow, as the scheme and the colon are part of the string
+        #[allow(clippy::arithmetic_side_effects)]
+        from_parts(scheme, body, scheme.len() + 1)
+    }
+}
+
+/// Displays the name as `scheme:name`, escaping characters as described in the
+/// [type-level documentation](Name#textual-representation).
+impl Display for Name<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (scheme, units) = to_parts(self);
+        f.write_str(scheme)?;
+        f.write_char(':')?;
+        write_escaped(f, &units)
+    }
+}
+
+/// Creates a name from a scheme and an escaped name. `offset` is the position of `body` in the
+/// string it came from, used for error reporting.
+fn from_parts(scheme: &str, body: &str, offset: usize) -> Result<Name<'static>, NameParseError> {
+    let scheme = match SCHEMES.iter().find(|(known, _)| *known == scheme) {
+        Some((known, true)) => *known,
+        Some((known, false)) => return Err(NameParseError::UnsupportedScheme(known)),
+        None => return Err(NameParseError::UnknownScheme(scheme.to_owned())),
+    };
+    let body = Cow::<OsStr>::Owned(unescape(body, offset)?);
+    match scheme {
+        #[cfg(unix)]
+        "unix" => FilesystemUdSocket::map(body),
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        "abstract" => AbstractNsUdSocket::map(body),
+        #[cfg(windows)]
+        "pipe" => NamedPipe::map(body),
+        "tcp" => <LoopbackTcp as PathNameType<OsStr>>::map(body),
+        #[cfg(unix)]
+        #[allow(deprecated)]
+        "specialdir" => SpecialDirUdSocket::map(body),
+        _ => GenericNamespaced::map(body),
+    }
+    .map(Name::into_owned)
+    .map_err(NameParseError::InvalidName)
+}
+/// Splits a name into its scheme and unescaped contents.
+fn to_parts<'a>(name: &'a Name<'_>) -> (&'static str, Cow<'a, [Unit]>) {
+    match &name.0 {
+        #[cfg(windows)]
+        NameInner::NamedPipe(path) => ("pipe", Cow::Borrowed(path.as_slice())),
+        #[cfg(unix)]
+        NameInner::UdSocketPath(path) => ("unix", Cow::Borrowed(path.as_bytes())),
+        #[cfg(unix)]
+        NameInner::UdSocketPseudoNs(name) => ("specialdir", Cow::Borrowed(name.as_bytes())),
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        NameInner::UdSocketNs(name) => ("abstract", Cow::Borrowed(name)),
+        #[cfg(unix)]
+        NameInner::Tcp(path) => ("tcp", Cow::Borrowed(path.as_bytes())),
+        #[cfg(windows)]
+        NameInner::Tcp(path) => ("tcp", Cow::Owned(path.encode_wide().collect())),
+    }
+}
+
+#[cfg(unix)]
+fn write_escaped(f: &mut Formatter<'_>, mut bytes: &[u8]) -> fmt::Result {
+    loop {
+        let e = match str::from_utf8(bytes) {
+            Ok(s) => return write_escaped_str(f, s),
+            Err(e) => e,
+        };
+        let (valid, rest) = bytes.split_at(e.valid_up_to());
+        write_escaped_str(f, str::from_utf8(valid).unwrap_or_default())?;
+        let (invalid, rest) = rest.split_at(e.error_len().unwrap_or(rest.len()));
+        for &b in invalid {
+            write!(f, "%{b:02X}")?;
+        }
+        bytes = rest;
+    }
+}
+#[cfg(windows)]
+fn write_escaped(f: &mut Formatter<'_>, units: &[u16]) -> fmt::Result {
+    let mut buf = [0; 2];
+    for c in char::decode_utf16(units.iter().copied()) {
+        match c {
+            Ok(c) if !needs_escape(c) => f.write_char(c)?,
+            Ok(c) => {
+                c.encode_utf16(&mut buf).iter().try_for_each(|&u| write_escaped_unit(f, u))?
+            }
+            Err(e) => write_escaped_unit(f, e.unpaired_surrogate())?,
+        }
+    }
+    Ok(())
+}
+#[cfg(windows)]
+fn write_escaped_unit(f: &mut Formatter<'_>, u: u16) -> fmt::Result {
+    if u <= 0xFF {
+        write!(f, "%{u:02X}")
+    } else {
+        write!(f, "%u{u:04X}")
+    }
+}
+
+#[cfg(unix)]
+fn write_escaped_str(f: &mut Formatter<'_>, s: &str) -> fmt::Result {
+    let mut buf = [0; 4];
+    for c in s.chars() {
+        if needs_escape(c) {
+            for b in c.encode_utf8(&mut buf).bytes() {
+                write!(f, "%{b:02X}")?;
+            }
+        } else {
+            f.write_char(c)?;
+        }
+    }
+    Ok(())
+}
+
+fn needs_escape(c: char) -> bool { c == '%' || c.is_control() }
+
+/// Decodes percent escapes. `offset` is the position of `s` in the whole string, used for error
+/// reporting.
+fn unescape(s: &str, offset: usize) -> Result<OsString, NameParseError> {
+    let mut units = Vec::<Unit>::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(pct) = rest.find('%') {
+        let (plain, escape) = rest.split_at(pct);
+        push_str(&mut units, plain);
+        let pos = offset.saturating_add(s.len().saturating_sub(escape.len()));
+        let (unit, after) = parse_escape(escape).ok_or(NameParseError::InvalidEscape(pos))?;
+        units.push(unit);
+        rest = after;
+    }
+    push_str(&mut units, rest);
+    Ok(from_units(units))
+}
+/// Parses an escape at the beginning of the string, which starts with `%`, returning the escaped
+/// code unit and the rest of the string.
+fn parse_escape(escape: &str) -> Option<(Unit, &str)> {
+    let escape = escape.strip_prefix('%')?;
+    #[cfg(windows)]
+    if let Some(escape) = escape.strip_prefix('u') {
+        let hex = escape.get(..4)?;
+        return Some((parse_hex(hex)?, escape.get(4..)?));
+    }
+    let hex = escape.get(..2)?;
+    Some((parse_hex(hex)?.try_into().ok()?, escape.get(2..)?))
+}
+fn parse_hex(hex: &str) -> Option<u16> {
+    // from_str_radix accepts a leading sign, which is not a hex digit
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u16::from_str_radix(hex, 16).ok()
+}
+
+#[cfg(unix)]
+fn push_str(units: &mut Vec<u8>, s: &str) { units.extend_from_slice(s.as_bytes()) }
+#[cfg(windows)]
+fn push_str(units: &mut Vec<u16>, s: &str) { units.extend(s.encode_utf16()) }
+
+#[cfg(unix)]
+fn from_units(units: Vec<u8>) -> OsString { OsString::from_vec(units) }
+#[cfg(windows)]
+fn from_units(units: Vec<u16>) -> OsString { OsString::from_wide(&units) }
+
+/// Serializes the name as a single-entry map from the scheme to the escaped name, as described
+/// in the [type-level documentation](Name#serde).
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "serde")))]
+impl serde::Serialize for Name<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Escaped<'a>(&'a [Unit]);
+        impl Display for Escaped<'_> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { write_escaped(f, self.0) }
+        }
+        impl serde::Serialize for Escaped<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+        let (scheme, units) = to_parts(self);
+        let index = SCHEMES.iter().position(|(known, _)| *known == scheme).unwrap_or_default();
+        #[allow(clippy::cast_possible_truncation)]
+        serializer.serialize_newtype_variant("Name", index as u32, scheme, &Escaped(&units))
+    }
+}
+
+/// Deserializes and validates a name in the format produced by its `Serialize` implementation.
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Name<'static> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Name", rename_all = "lowercase")]
+        enum Repr {
+            Unix(String),
+            Abstract(String),
+            Ns(String),
+            Pipe(String),
+            Tcp(String),
+            SpecialDir(String),
+        }
+        let (scheme, body) = match Repr::deserialize(deserializer)? {
+            Repr::Unix(body) => ("unix", body),
+            Repr::Abstract(body) => ("abstract", body),
+            Repr::Ns(body) => ("ns", body),
+            Repr::Pipe(body) => ("pipe", body),
+            Repr::Tcp(body) => ("tcp", body),
+            Repr::SpecialDir(body) => ("specialdir", body),
+        };
+        from_parts(scheme, &body, 0).map_err(serde::de::Error::custom)
+    }
+}
@@ -1,12 +1,11 @@
 use {
     super::{
         r#type::{NamespacedNameType, PathNameType},
-        Name,
+        Name, NameError,
     },
     std::{
         borrow::Cow,
         ffi::{CStr, CString, OsStr, OsString},
-        io,
         path::{Path, PathBuf},
         str,
     },
@@ -20,7 +19,7 @@ macro_rules! trivial_string_impl {
     ) => {$(
         impl<'s> $cvttrait<'s, $str> for $tgt {
             #[inline]
-            fn $mtd<T: $nttrait<$str>>(self) -> io::Result<Name<'s>> {
+            fn $mtd<T: $nttrait<$str>>(self) -> Result<Name<'s>, NameError> {
                 $via::$ctor(self).$mtd::<T>()
             }
         }
@@ -31,30 +30,27 @@ macro_rules! trivial_string_impl {
 pub trait ToFsName<'s, S: ToOwned + ?Sized> {
     /// Performs the conversion to a filesystem path-type name.
     ///
-    /// Fails if the resulting name isn't supported by the platform.
-    fn to_fs_name<NT: PathNameType<S>>(self) -> io::Result<Name<'s>>;
+    /// Fails with a [`NameError`] if the resulting name isn't supported by the platform.
+    fn to_fs_name<NT: PathNameType<S>>(self) -> Result<Name<'s>, NameError>;
 }
 
 /// Conversion to a namespaced local socket name.
 pub trait ToNsName<'s, S: ToOwned + ?Sized> {
     /// Performs the conversion to a namespaced name.
     ///
-    /// Fails if the resulting name isn't supported by the platform.
-    fn to_ns_name<NT: NamespacedNameType<S>>(self) -> io::Result<Name<'s>>;
+    /// Fails with a [`NameError`] if the resulting name isn't supported by the platform.
+    fn to_ns_name<NT: NamespacedNameType<S>>(self) -> Result<Name<'s>, NameError>;
 }
 
-#[allow(dead_code)]
-fn err(s: &'static str) -> io::Error { io::Error::new(io::ErrorKind::Unsupported, s) }
-
 impl<'s> ToFsName<'s, OsStr> for &'s Path {
     #[inline]
-    fn to_fs_name<FT: PathNameType<OsStr>>(self) -> io::Result<Name<'s>> {
+    fn to_fs_name<FT: PathNameType<OsStr>>(self) -> Result<Name<'s>, NameError> {
         FT::map(Cow::Borrowed(self.as_os_str()))
     }
 }
 impl<'s> ToFsName<'s, OsStr> for PathBuf {
     #[inline]
-    fn to_fs_name<FT: PathNameType<OsStr>>(self) -> io::Result<Name<'s>> {
+    fn to_fs_name<FT: PathNameType<OsStr>>(self) -> Result<Name<'s>, NameError> {
         FT::map(Cow::Owned(self.into_os_string()))
     }
 }
@@ -67,13 +63,13 @@ trivial_string_impl! { ToFsName<OsStr>::to_fs_name<PathNameType> for
 
 impl<'s> ToNsName<'s, OsStr> for &'s OsStr {
     #[inline]
-    fn to_ns_name<NT: NamespacedNameType<OsStr>>(self) -> io::Result<Name<'s>> {
+    fn to_ns_name<NT: NamespacedNameType<OsStr>>(self) -> Result<Name<'s>, NameError> {
         NT::map(Cow::Borrowed(self))
     }
 }
 impl<'s> ToNsName<'s, OsStr> for OsString {
     #[inline]
-    fn to_ns_name<NT: NamespacedNameType<OsStr>>(self) -> io::Result<Name<'s>> {
+    fn to_ns_name<NT: NamespacedNameType<OsStr>>(self) -> Result<Name<'s>, NameError> {
         NT::map(Cow::Owned(self))
     }
 }
@@ -84,26 +80,26 @@ trivial_string_impl! { ToNsName<OsStr>::to_ns_name<NamespacedNameType> for
 
 impl<'s> ToFsName<'s, CStr> for &'s CStr {
     #[inline]
-    fn to_fs_name<FT: PathNameType<CStr>>(self) -> io::Result<Name<'s>> {
+    fn to_fs_name<FT: PathNameType<CStr>>(self) -> Result<Name<'s>, NameError> {
         FT::map(Cow::Borrowed(self))
     }
 }
 impl<'s> ToFsName<'s, CStr> for CString {
     #[inline]
-    fn to_fs_name<FT: PathNameType<CStr>>(self) -> io::Result<Name<'s>> {
+    fn to_fs_name<FT: PathNameType<CStr>>(self) -> Result<Name<'s>, NameError> {
         FT::map(Cow::Owned(self))
     }
 }
 
 impl<'s> ToNsName<'s, CStr> for &'s CStr {
     #[inline]
-    fn to_ns_name<NT: NamespacedNameType<CStr>>(self) -> io::Result<Name<'s>> {
+    fn to_ns_name<NT: NamespacedNameType<CStr>>(self) -> Result<Name<'s>, NameError> {
         NT::map(Cow::Borrowed(self))
     }
 }
 impl<'s> ToNsName<'s, CStr> for CString {
     #[inline]
-    fn to_ns_name<NT: NamespacedNameType<CStr>>(self) -> io::Result<Name<'s>> {
+    fn to_ns_name<NT: NamespacedNameType<CStr>>(self) -> Result<Name<'s>, NameError> {
         NT::map(Cow::Owned(self))
     }
 }
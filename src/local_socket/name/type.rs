@@ -10,9 +10,9 @@
 #[cfg(unix)]
 use std::ffi::CStr;
 use {
-    super::Name,
-    crate::Sealed,
-    std::{borrow::Cow, ffi::OsStr, io},
+    super::{Name, NameError},
+    crate::{local_socket::tcp, Sealed},
+    std::{borrow::Cow, ffi::OsStr},
 };
 
 impmod! {local_socket::name_type as n_impl}
@@ -45,21 +45,21 @@ pub trait NameType: Copy + std::fmt::Debug + Eq + Send + Sync + Unpin + Sealed {
 ///
 /// See [`ToFsName::to_fs_name()`](super::ToFsName::to_fs_name).
 pub trait PathNameType<S: ToOwned + ?Sized>: NameType {
-    /// Maps the given path to a local socket name, failing if the resulting name is unsupported by
-    /// the underlying OS.
+    /// Maps the given path to a local socket name, failing with a [`NameError`] if the resulting
+    /// name is unsupported by the underlying OS.
     ///
     /// The idiomatic way to use this is [`ToFsName::to_fs_name()`](super::ToFsName::to_fs_name).
-    fn map(path: Cow<'_, S>) -> io::Result<Name<'_>>;
+    fn map(path: Cow<'_, S>) -> Result<Name<'_>, NameError>;
 }
 /// [Mappings](NameType) from strings to [local socket names](Name).
 ///
 /// See [`ToNsName::to_ns_name()`](super::ToNsName::to_ns_name).
 pub trait NamespacedNameType<S: ToOwned + ?Sized>: NameType {
-    /// Maps the given string to a local socket name, failing if the resulting name is unsupported
-    /// by the underlying OS.
+    /// Maps the given string to a local socket name, failing with a [`NameError`] if the resulting
+    /// name is unsupported by the underlying OS.
     ///
     /// The idiomatic way to use this is [`ToNsName::to_ns_name()`](super::ToNsName::to_ns_name).
-    fn map(name: Cow<'_, S>) -> io::Result<Name<'_>>;
+    fn map(name: Cow<'_, S>) -> Result<Name<'_>, NameError>;
 }
 
 tag_enum!(
@@ -82,13 +82,17 @@ impl NameType for GenericFilePath {
 }
 impl PathNameType<OsStr> for GenericFilePath {
     #[inline]
-    fn map(path: Cow<'_, OsStr>) -> io::Result<Name<'_>> { n_impl::map_generic_path_osstr(path) }
+    fn map(path: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
+        n_impl::map_generic_path_osstr(path)
+    }
 }
 #[cfg(unix)]
 #[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
 impl PathNameType<CStr> for GenericFilePath {
     #[inline]
-    fn map(path: Cow<'_, CStr>) -> io::Result<Name<'_>> { n_impl::map_generic_path_cstr(path) }
+    fn map(path: Cow<'_, CStr>) -> Result<Name<'_>, NameError> {
+        n_impl::map_generic_path_cstr(path)
+    }
 }
 
 tag_enum!(
@@ -104,18 +108,20 @@ tag_enum!(
 ///
 /// ### Linux
 /// Resolves to the abstract namespace with no string transformations and thus has a maximum length
-/// of 107 bytes. (This is the behavior of `AbstractNsUdSocket.`)
+/// of 107 bytes. (This is the behavior of `AbstractNsUdSocket`, except that nul characters are
+/// rejected with [`NameError::InteriorNul`], as they are on all other platforms.)
 ///
 /// ### Other Unices
 /// Resolves to filesystem paths by prepending `/tmp/` (but see `SpecialDirUdSocket` and its
-/// deprecation warning).
+/// deprecation warning). `RuntimeDirUdSocket` provides a better-behaved alternative, but is not
+/// used here for compatibility reasons.
 GenericNamespaced);
 impl NameType for GenericNamespaced {
     fn is_supported() -> bool { true }
 }
 impl NamespacedNameType<OsStr> for GenericNamespaced {
     #[inline]
-    fn map(name: Cow<'_, OsStr>) -> io::Result<Name<'_>> {
+    fn map(name: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
         n_impl::map_generic_namespaced_osstr(name)
     }
 }
@@ -123,7 +129,64 @@ impl NamespacedNameType<OsStr> for GenericNamespaced {
 #[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
 impl NamespacedNameType<CStr> for GenericNamespaced {
     #[inline]
-    fn map(name: Cow<'_, CStr>) -> io::Result<Name<'_>> {
+    fn map(name: Cow<'_, CStr>) -> Result<Name<'_>, NameError> {
         n_impl::map_generic_namespaced_cstr(name)
     }
 }
+
+tag_enum!(
+/// Mapping from paths and strings to local socket names that use
+/// [TCP over the loopback interface](crate::local_socket::tcp), for environments where the
+/// native local socket implementation is unavailable, such as sandboxes that block `AF_UNIX`.
+///
+/// Such names refer to a *port file* rather than to a socket. When a listener is created, it binds
+/// a TCP socket to an ephemeral port on `127.0.0.1` and writes the port, along with a randomly
+/// generated authentication token, to the port file. Clients read the port file, connect to the
+/// port and send the token before anything else, and the listener drops connections that fail to
+/// do so. Access to the local socket is thus governed by who can read the port file.
+///
+/// Paths are used as the path of the port file verbatim, and may not contain nul characters
+/// ([`NameError::InteriorNul`]).
+///
+/// Namespaced strings are mapped to a port file named `<name>.port` inside of a per-user
+/// directory, and may not be empty, be `.` or `..`, or contain path separators
+/// ([`NameError::InvalidComponent`]) or nul characters ([`NameError::InteriorNul`]).
+///
+/// ## Platform-specific behavior
+/// ### Unix
+/// The directory is the one chosen by
+/// [`RuntimeDirUdSocket`](crate::os::unix::local_socket::RuntimeDirUdSocket).
+///
+/// ### Windows
+/// The directory is `interprocess` inside of the [temporary directory](std::env::temp_dir). It is
+/// created with a DACL that grants access to the current user only. If it already exists, it must
+/// be owned by the current user, and its DACL is replaced with such a DACL; otherwise, mapping the
+/// name fails with [`PermissionDenied`](std::io::ErrorKind::PermissionDenied).
+LoopbackTcp);
+impl NameType for LoopbackTcp {
+    fn is_supported() -> bool { true }
+}
+impl PathNameType<OsStr> for LoopbackTcp {
+    #[inline]
+    fn map(path: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> { tcp::map_path(path) }
+}
+impl NamespacedNameType<OsStr> for LoopbackTcp {
+    #[inline]
+    fn map(name: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> { tcp::map_namespaced(name) }
+}
+#[cfg(unix)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl PathNameType<CStr> for LoopbackTcp {
+    #[inline]
+    fn map(path: Cow<'_, CStr>) -> Result<Name<'_>, NameError> {
+        tcp::map_path(n_impl::c2os(path))
+    }
+}
+#[cfg(unix)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl NamespacedNameType<CStr> for LoopbackTcp {
+    #[inline]
+    fn map(name: Cow<'_, CStr>) -> Result<Name<'_>, NameError> {
+        tcp::map_namespaced(n_impl::c2os(name))
+    }
+}
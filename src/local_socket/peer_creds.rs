@@ -36,6 +36,24 @@ impl Debug for PeerCreds {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { Debug::fmt(&self.0, f) }
 }
+/// Serializes the credentials as a struct with a field for each getter available on the current
+/// platform, using `None` for the credentials that the platform does not provide.
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "serde")))]
+impl serde::Serialize for PeerCreds {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct as _;
+        let mut st = serializer.serialize_struct("PeerCreds", if cfg!(unix) { 4 } else { 1 })?;
+        st.serialize_field("pid", &self.pid())?;
+        #[cfg(unix)]
+        {
+            st.serialize_field("euid", &self.euid())?;
+            st.serialize_field("egid", &self.egid())?;
+            st.serialize_field("groups", &self.groups())?;
+        }
+        st.end()
+    }
+}
 impl From<Inner> for PeerCreds {
     #[inline(always)]
     fn from(v: Inner) -> Self { Self(v) }
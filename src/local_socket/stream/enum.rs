@@ -5,7 +5,7 @@ use crate::os::windows::named_pipe::local_socket as np_impl;
 use {
     super::r#trait,
     crate::{
-        local_socket::{ConnectOptions, PeerCreds},
+        local_socket::{tcp as tcp_impl, ConnectOptions, Name, PeerCreds},
         TryClone,
     },
     std::{
@@ -62,6 +62,20 @@ macro_rules! dispatch_write {
         }
     };
 }
+macro_rules! dispatch_as_fd {
+    ($ty:ident) => {
+        #[cfg(unix)]
+        impl std::os::unix::io::AsFd for $ty {
+            #[inline]
+            fn as_fd(&self) -> std::os::unix::io::BorrowedFd<'_> {
+                match self {
+                    Self::UdSocket(x) => x.as_fd(),
+                    Self::Tcp(x) => x.as_fd(),
+                }
+            }
+        }
+    };
+}
 
 mkenum!(
 /// Local socket byte stream, obtained either from [`Listener`](super::super::Listener) or by
@@ -112,6 +126,10 @@ impl r#trait::Stream for Stream {
                 let (rh, sh) = s.split();
                 (RecvHalf::UdSocket(rh), SendHalf::UdSocket(sh))
             }
+            Stream::Tcp(s) => {
+                let (rh, sh) = s.split();
+                (RecvHalf::Tcp(rh), SendHalf::Tcp(sh))
+            }
         }
     }
     fn reunite(rh: RecvHalf, sh: SendHalf) -> ReuniteResult {
@@ -124,7 +142,9 @@ impl r#trait::Stream for Stream {
             (RecvHalf::UdSocket(rh), SendHalf::UdSocket(sh)) => {
                 uds_impl::Stream::reunite(rh, sh).map(From::from).map_err(|e| e.convert_halves())
             }
-            #[allow(unreachable_patterns)]
+            (RecvHalf::Tcp(rh), SendHalf::Tcp(sh)) => {
+                tcp_impl::Stream::reunite(rh, sh).map(From::from).map_err(|e| e.convert_halves())
+            }
             (rh, sh) => Err(ReuniteError { rh, sh }),
         }
     }
@@ -136,16 +156,25 @@ impl r#trait::StreamCommon for Stream {
     }
     #[inline]
     fn peer_creds(&self) -> io::Result<PeerCreds> { dispatch!(Self: x in self => x.peer_creds()) }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> {
+        dispatch!(Self: x in self => x.local_name())
+    }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> {
+        dispatch!(Self: x in self => x.peer_name())
+    }
 }
 impl TryClone for Stream {
     fn try_clone(&self) -> io::Result<Self> {
-        dispatch!(Self: x in self => x.try_clone()).map(From::from)
+        dispatch!(Self: x in self => x.try_clone().map(Self::from))
     }
 }
 multimacro! {
     Stream,
     dispatch_read,
     dispatch_write,
+    dispatch_as_fd,
 }
 
 mkenum!(
@@ -163,6 +192,7 @@ impl r#trait::RecvHalf for RecvHalf {
     }
 }
 dispatch_read!(RecvHalf);
+dispatch_as_fd!(RecvHalf);
 
 mkenum!(
 /// Send half of a local socket stream, obtained by splitting a [`Stream`].
@@ -179,6 +209,7 @@ impl r#trait::SendHalf for SendHalf {
     }
 }
 dispatch_write!(SendHalf);
+dispatch_as_fd!(SendHalf);
 
 /// [`ReuniteError`](crate::error::ReuniteError) for [`Stream`].
 pub type ReuniteError = crate::error::ReuniteError<RecvHalf, SendHalf>;
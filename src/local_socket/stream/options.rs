@@ -1,15 +1,18 @@
+#[cfg(all(unix, feature = "async"))]
+use crate::local_socket::async_io::Stream as AsyncIoStream;
 #[cfg(feature = "tokio")]
 use crate::local_socket::tokio::Stream as TokioStream;
-#[cfg(feature = "tokio")]
+#[cfg(any(feature = "tokio", all(unix, feature = "async")))]
 use std::future::Future;
 use {
     crate::{
-        local_socket::{traits, Name, Stream},
+        local_socket::{traits, Name, PeerCreds, Stream},
         ConnectWaitMode, Sealed, TryClone,
     },
     std::{
         fmt::{self, Debug, Formatter},
         io,
+        sync::Arc,
         time::Duration,
     },
 };
@@ -19,7 +22,13 @@ pub struct ConnectOptions<'n> {
     pub(crate) name: Name<'n>,
     flags: u8,
     timeout: Duration,
+    server_check: Option<Arc<ServerCheck>>,
+    #[cfg(unix)]
+    server_euid: Option<libc::uid_t>,
+    #[cfg(unix)]
+    server_egid: Option<libc::gid_t>,
 }
+type ServerCheck = dyn Fn(&PeerCreds) -> bool + Send + Sync;
 impl Sealed for ConnectOptions<'_> {}
 
 const SHFT_NONBLOCKING_STREAM: u8 = 0;
@@ -37,7 +46,16 @@ const fn has_bit(flags: u8, pos: u8) -> bool { flags & (1 << pos) != 0 }
 impl TryClone for ConnectOptions<'_> {
     #[inline]
     fn try_clone(&self) -> io::Result<Self> {
-        Ok(Self { name: self.name.clone(), flags: self.flags, timeout: self.timeout })
+        Ok(Self {
+            name: self.name.clone(),
+            flags: self.flags,
+            timeout: self.timeout,
+            server_check: self.server_check.clone(),
+            #[cfg(unix)]
+            server_euid: self.server_euid,
+            #[cfg(unix)]
+            server_egid: self.server_egid,
+        })
     }
 }
 
@@ -45,7 +63,18 @@ impl TryClone for ConnectOptions<'_> {
 impl ConnectOptions<'_> {
     /// Returns a default set of client options.
     #[inline]
-    pub fn new() -> Self { Self { name: Name::invalid(), flags: 0, timeout: Duration::ZERO } }
+    pub fn new() -> Self {
+        Self {
+            name: Name::invalid(),
+            flags: 0,
+            timeout: Duration::ZERO,
+            server_check: None,
+            #[cfg(unix)]
+            server_euid: None,
+            #[cfg(unix)]
+            server_egid: None,
+        }
+    }
 }
 
 /// Option setters.
@@ -113,6 +142,39 @@ impl<'n> ConnectOptions<'n> {
         self.flags = set_bit(self.flags, SHFT_NONBLOCKING_STREAM, nonblocking);
         self
     }
+    /// Sets a predicate over the [credentials](PeerCreds) of the server that has to hold for the
+    /// connection to be accepted.
+    ///
+    /// The check is performed by `connect_*` immediately after the connection is established and
+    /// before the stream is returned, which is to say before any data can be sent to the server.
+    /// If the predicate returns `false`, or if the credentials cannot be retrieved, the connection
+    /// is dropped and an error of kind [`PermissionDenied`](io::ErrorKind::PermissionDenied) is
+    /// returned. This guards against other users impersonating the server by binding to its
+    /// name first, which is possible with abstract namespace names and names in shared
+    /// directories.
+    ///
+    /// Setting this again replaces the previous predicate. On Unix, checks on the user and group
+    /// ID of the server can also be set up via `ConnectOptionsExt`, in which case all of them have
+    /// to pass.
+    ///
+    /// ## Platform-specific behavior
+    /// With the [deferred wait mode](ConnectWaitMode::Deferred), the credentials are retrieved
+    /// before the connection is known to have been accepted, which fails on some platforms.
+    #[must_use = builder_must_use!()]
+    #[inline]
+    pub fn expect_server(
+        mut self,
+        check: impl Fn(&PeerCreds) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.server_check = Some(Arc::new(check));
+        self
+    }
+    #[cfg(unix)]
+    #[inline(always)]
+    pub(crate) fn set_server_euid(&mut self, euid: libc::uid_t) { self.server_euid = Some(euid); }
+    #[cfg(unix)]
+    #[inline(always)]
+    pub(crate) fn set_server_egid(&mut self, egid: libc::gid_t) { self.server_egid = Some(egid); }
 }
 
 /// Option getters.
@@ -129,6 +191,37 @@ impl ConnectOptions<'_> {
     pub(crate) fn get_nonblocking_stream(&self) -> bool {
         has_bit(self.flags, SHFT_NONBLOCKING_STREAM)
     }
+    fn has_server_checks(&self) -> bool {
+        #[cfg(unix)]
+        if self.server_euid.is_some() || self.server_egid.is_some() {
+            return true;
+        }
+        self.server_check.is_some()
+    }
+}
+
+/// Verifies the identity of the server on the other end of a freshly created stream according to
+/// the [`expect_server`](ConnectOptions::expect_server) policy.
+impl ConnectOptions<'_> {
+    fn verify_server<S: traits::StreamCommon>(&self, stream: S) -> io::Result<S> {
+        if !self.has_server_checks() {
+            return Ok(stream);
+        }
+        let denied = |msg| io::Error::new(io::ErrorKind::PermissionDenied, msg);
+        let creds = stream.peer_creds().map_err(|e| {
+            denied(format!("could not retrieve server credentials for verification: {e}"))
+        })?;
+        let mut ok = self.server_check.as_ref().map_or(true, |check| check(&creds));
+        #[cfg(unix)]
+        {
+            ok &= self.server_euid.map_or(true, |euid| creds.euid() == Some(euid));
+            ok &= self.server_egid.map_or(true, |egid| creds.egid() == Some(egid));
+        }
+        if !ok {
+            return Err(denied("server identity verification failed".to_owned()));
+        }
+        Ok(stream)
+    }
 }
 
 /// Stream constructors.
@@ -142,7 +235,9 @@ impl ConnectOptions<'_> {
     /// Creates the given [type of stream](traits::Stream) by connecting to the specified local
     /// socket name.
     #[inline]
-    pub fn connect_sync_as<S: traits::Stream>(&self) -> io::Result<S> { S::from_options(self) }
+    pub fn connect_sync_as<S: traits::Stream>(&self) -> io::Result<S> {
+        self.verify_server(S::from_options(self)?)
+    }
     /// Creates a Tokio [`Stream`](TokioStream) by connecting to the specified local socket name.
     ///
     /// On platforms where there are multiple available implementations, this dispatches to the
@@ -163,7 +258,28 @@ impl ConnectOptions<'_> {
     pub fn connect_tokio_as<S: traits::tokio::Stream>(
         &self,
     ) -> impl Future<Output = io::Result<S>> + Send + Sync + '_ {
-        S::from_options(self)
+        async move { self.verify_server(S::from_options(self).await?) }
+    }
+    /// Creates an async-io [`Stream`](AsyncIoStream) by connecting to the specified local socket
+    /// name.
+    ///
+    /// On platforms where there are multiple available implementations, this dispatches to the
+    /// appropriate implementation based on where the name points to.
+    #[inline]
+    #[cfg(all(unix, feature = "async"))]
+    pub fn connect_async_io(
+        &self,
+    ) -> impl Future<Output = io::Result<AsyncIoStream>> + Send + '_ {
+        self.connect_async_io_as::<AsyncIoStream>()
+    }
+    /// Creates the given [type of async-io stream](traits::async_io::Stream) by connecting to the
+    /// specified local socket name.
+    #[inline]
+    #[cfg(all(unix, feature = "async"))]
+    pub fn connect_async_io_as<S: traits::async_io::Stream>(
+        &self,
+    ) -> impl Future<Output = io::Result<S>> + Send + '_ {
+        async move { self.verify_server(S::from_options(self).await?) }
     }
 }
 
@@ -174,10 +290,15 @@ impl Default for ConnectOptions<'_> {
 
 impl Debug for ConnectOptions<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ConnectOptions")
-            .field("name", &self.name)
+        let mut dbs = f.debug_struct("ConnectOptions");
+        dbs.field("name", &self.name)
             .field("wait_mode", &self.get_wait_mode())
             .field("nonblocking_stream", &self.get_nonblocking_stream())
-            .finish()
+            .field("expect_server", &self.server_check.is_some());
+        #[cfg(unix)]
+        {
+            dbs.field("server_euid", &self.server_euid).field("server_egid", &self.server_egid);
+        }
+        dbs.finish()
     }
 }
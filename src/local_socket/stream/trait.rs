@@ -93,6 +93,29 @@ pub trait StreamCommon: Debug + Send + Sync + Sized + Sealed + 'static {
     /// credentials of the client), `listen` (for those of the server). On OpenBSD and NetBSD,
     /// server credentials are instead those which were in effect at the time of `bind`.
     fn peer_creds(&self) -> io::Result<PeerCreds>;
+    /// Returns the name the local side of the connection is bound to, or `None` if it is unnamed.
+    ///
+    /// ## Platform-specific behavior
+    /// ### Unix
+    /// The name is retrieved with `getsockname`. Client sockets are typically unnamed, while
+    /// streams produced by a listener have the name of the listener. Paths that were too long
+    /// for `sun_path` and were bound via a `/proc/self/fd/` alias are returned in their alias form.
+    ///
+    /// ### Windows
+    /// The path of the named pipe is returned on both sides of the connection.
+    fn local_name(&self) -> io::Result<Option<Name<'static>>>;
+    /// Returns the name the remote side of the connection is bound to, or `None` if it is
+    /// unnamed.
+    ///
+    /// ## Platform-specific behavior
+    /// ### Unix
+    /// The name is retrieved with `getpeername`. On the client side, this is the name of the
+    /// listener that was connected to; on the server side, it is typically `None`. The same
+    /// caveat about long paths as with [`local_name`](Self::local_name) applies.
+    ///
+    /// ### Windows
+    /// The path of the named pipe is returned on both sides of the connection.
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>>;
 }
 
 /// Receive halves of [`Stream`]s, obtained through [`.split()`](Stream::split).
@@ -0,0 +1,228 @@
+//! Local sockets implemented using TCP over the loopback interface.
+//!
+//! This implementation is selected by names of the [`LoopbackTcp`](super::LoopbackTcp) type on
+//! all platforms, and exists as a fallback for environments in which the native local socket
+//! implementation cannot be used, such as sandboxes that block `AF_UNIX`. It is slower than the
+//! native implementations and lacks some of their features, so it should not be used when those
+//! are available.
+//!
+//! # Protocol
+//! The listener binds to an ephemeral port on `127.0.0.1` and writes a *port file* to the path the
+//! name points to. The port file consists of a single line with the port number in decimal and a
+//! randomly generated 128-bit token in hexadecimal, separated by a space. The port file is created
+//! atomically, with the [name reclamation](super::Listener#name-reclamation) and
+//! [overwriting](super::ListenerOptions::try_overwrite) behavior of Unix domain socket files, and
+//! is only deleted by name reclamation if it still belongs to the listener.
+//!
+//! Clients read the port file, connect to the port and send the 16 bytes of the token before
+//! anything else. No response is sent by the listener, which drops connections that do not start
+//! with the token or fail to deliver it within one second. Past that point, the bytes written to
+//! the stream are the exact bytes that come out the other end, as with the native
+//! implementations.
+//!
+//! Listeners receive the tokens of many clients at once and only return clients that have sent a
+//! valid one, so a client that connects and stays silent does not hold up others. In nonblocking
+//! mode, [accepting](super::traits::Listener::accept) fails with
+//! [`WouldBlock`](io::ErrorKind::WouldBlock) while no client has completed the handshake yet.
+//!
+//! This means that the security of the local socket depends on the permissions of the port file,
+//! which is created with mode `0600` on Unix unless a different mode is
+//! [specified](crate::os::unix::local_socket::ListenerOptionsExt::mode).
+//!
+//! # Limitations
+//! - [Peer credentials](super::traits::StreamCommon::peer_creds) are not available, and thus
+//!   [server verification](super::ConnectOptions::expect_server) always fails.
+//! - Streams have no [local](super::traits::StreamCommon::local_name) or
+//!   [peer](super::traits::StreamCommon::peer_name) names.
+//! - The [deferred wait mode](crate::ConnectWaitMode::Deferred) behaves like the unbounded one,
+//!   since the token has to be sent before the stream is returned.
+//! - Of the platform-specific listener options, only the ones that concern the permissions of the
+//!   socket file are supported, and are applied to the port file instead: the
+//!   [mode](crate::os::unix::local_socket::ListenerOptionsExt::mode),
+//!   [owner](crate::os::unix::local_socket::ListenerOptionsExt::owner) and
+//!   [group](crate::os::unix::local_socket::ListenerOptionsExt::group), as well as
+//!   [verification](crate::os::unix::local_socket::ListenerOptionsExt::verify_parent_dir) of the
+//!   directory it is created in. The others are ignored.
+//! - Except on Linux, Android, BSD and Apple platforms, the file descriptor or socket of a listener
+//!   does not become readable when a client that was already accepted completes the handshake, so
+//!   waiting for it to become readable before accepting can leave such clients waiting.
+//! - The Tokio implementation spawns a task on the runtime for every client that connects, with up
+//!   to 64 of them in the middle of the handshake at once, and requires the time driver of the
+//!   runtime to be enabled (`Builder::enable_time()`) in order to accept clients.
+
+mod handshake;
+mod listener;
+mod port_file;
+mod readiness;
+mod stream;
+
+pub use {listener::*, stream::*};
+
+/// Async local sockets for async-io implemented using loopback TCP.
+#[cfg(all(unix, feature = "async"))]
+pub mod async_io {
+    mod listener;
+    mod stream;
+    pub use {listener::*, stream::*};
+}
+
+/// Async local sockets for Tokio implemented using loopback TCP.
+#[cfg(feature = "tokio")]
+pub mod tokio {
+    mod listener;
+    mod stream;
+    pub use {listener::*, stream::*};
+}
+
+#[cfg(unix)]
+use std::os::unix::prelude::*;
+#[cfg(windows)]
+use std::os::windows::prelude::*;
+use {
+    crate::local_socket::{Name, NameError, NameInner},
+    std::{
+        borrow::Cow,
+        ffi::OsStr,
+        fmt::{self, Debug, Formatter},
+        io,
+        net::{Ipv4Addr, SocketAddr},
+        path::{Component, Path},
+        time::Duration,
+    },
+};
+
+/// How long the listener waits for a freshly connected client to send the token.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(1);
+const TOKEN_LEN: usize = 16;
+#[cfg(any(feature = "tokio", all(unix, feature = "async")))]
+const CONN_TIMEOUT_MSG: &str = "timed out while connecting to local socket server";
+
+/// Random token that clients have to present to the listener.
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Token([u8; TOKEN_LEN]);
+impl Token {
+    /// Generates a token with the random number generator of the OS.
+    fn generate() -> io::Result<Self> {
+        let mut token = [0; TOKEN_LEN];
+        fill_random(&mut token)?;
+        Ok(Self(token))
+    }
+    /// Compares the token to the given bytes in constant time.
+    fn matches(&self, other: &[u8; TOKEN_LEN]) -> bool {
+        self.0.iter().zip(other).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+    fn parse(hex: &str) -> Option<Self> {
+        let mut token = [0; TOKEN_LEN];
+        if hex.len() != TOKEN_LEN * 2 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        for (byte, digits) in token.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+            *byte = u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()?;
+        }
+        Some(Self(token))
+    }
+}
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+/// Does not reveal the token.
+impl Debug for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str("Token(..)") }
+}
+
+/// Fills the buffer with cryptographically secure random bytes.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn fill_random(mut buf: &mut [u8]) -> io::Result<()> {
+    while !buf.is_empty() {
+        // SAFETY: the buffer is valid for writes of its length
+        let ret = unsafe { libc::getrandom(buf.as_mut_ptr().cast(), buf.len(), 0) };
+        match usize::try_from(ret) {
+            Ok(filled) => buf = buf.get_mut(filled..).unwrap_or_default(),
+            Err(..) => {
+                let e = io::Error::last_os_error();
+                if e.kind() != io::ErrorKind::Interrupted {
+                    return Err(e);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+/// Fills the buffer with cryptographically secure random bytes.
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+fn fill_random(buf: &mut [u8]) -> io::Result<()> {
+    use std::io::Read as _;
+    std::fs::File::open("/dev/urandom")?.read_exact(buf)
+}
+/// Fills the buffer with cryptographically secure random bytes.
+#[cfg(windows)]
+fn fill_random(buf: &mut [u8]) -> io::Result<()> {
+    use windows_sys::Win32::Security::Cryptography::{
+        BCryptGenRandom, BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+    };
+    let len = u32::try_from(buf.len()).map_err(io::Error::other)?;
+    // SAFETY: the buffer is valid for writes of its length
+    let status = unsafe {
+        BCryptGenRandom(
+            std::ptr::null_mut(),
+            buf.as_mut_ptr(),
+            len,
+            BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+        )
+    };
+    if status < 0 {
+        return Err(io::Error::other(format!("BCryptGenRandom failed with status {status:#x}")));
+    }
+    Ok(())
+}
+
+fn loopback(port: u16) -> SocketAddr { SocketAddr::from((Ipv4Addr::LOCALHOST, port)) }
+
+/// Extracts the path to the port file from a name, failing if it is not a loopback TCP name.
+fn port_file_path<'a>(name: &'a Name<'_>) -> io::Result<&'a Path> {
+    match &name.0 {
+        NameInner::Tcp(path) => Ok(Path::new(path)),
+        _ => Err(NameError::Unsupported.into()),
+    }
+}
+fn tcp_name(path: &Path) -> Name<'static> {
+    Name(NameInner::Tcp(Cow::Owned(path.as_os_str().to_owned())))
+}
+
+fn peer_creds_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "peer credentials are not available for loopback TCP local sockets",
+    )
+}
+fn bad_token() -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, "client presented an invalid token")
+}
+
+fn check_no_nul(s: &OsStr) -> Result<(), NameError> {
+    #[cfg(unix)]
+    let pos = s.as_bytes().iter().position(|&b| b == 0);
+    #[cfg(windows)]
+    let pos = s.encode_wide().position(|u| u == 0);
+    pos.map_or(Ok(()), |pos| Err(NameError::InteriorNul(pos)))
+}
+
+pub(crate) fn map_path(path: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
+    check_no_nul(&path)?;
+    Ok(Name(NameInner::Tcp(path)))
+}
+pub(crate) fn map_namespaced(name: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
+    check_no_nul(&name)?;
+    let mut components = Path::new(&name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(c)), None) if c == &*name => {}
+        _ => return Err(NameError::InvalidComponent),
+    }
+    let mut path = port_file::dir()?;
+    let mut file = name.into_owned();
+    file.push(".port");
+    path.push(file);
+    Ok(Name(NameInner::Tcp(Cow::Owned(path.into_os_string()))))
+}
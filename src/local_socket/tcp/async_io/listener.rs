@@ -0,0 +1,163 @@
+use {
+    super::{
+        super::{handshake::Handshakes, port_file::PortFile, tcp_name, Listener as SyncListener},
+        Stream,
+    },
+    crate::{
+        local_socket::{
+            prelude::*, traits::async_io as traits, ListenerNonblockingMode, ListenerOptions,
+            Name,
+        },
+        Sealed,
+    },
+    ::async_io::{Async, Timer},
+    std::{
+        future::{poll_fn, Future as _},
+        io,
+        net::{TcpListener, TcpStream},
+        os::unix::prelude::*,
+        pin::Pin,
+        sync::{Arc, Mutex, MutexGuard},
+        task::{Context, Poll, Wake, Waker},
+    },
+};
+
+/// Wrapper around [`TcpListener`] registered with the async-io reactor that implements
+/// [`Listener`](traits::Listener).
+///
+/// Clients are authenticated without blocking the listener: while waiting for a client to send the
+/// token, others keep being accepted, and the first one to complete the handshake is returned.
+/// Clients that haven't completed it yet are kept by the listener, which makes accepting
+/// cancel-safe.
+#[derive(Debug)]
+pub struct Listener {
+    listener: Async<TcpListener>,
+    port_file: PortFile,
+    pending: Mutex<Pending>,
+    wakers: Arc<Wakers>,
+}
+#[derive(Debug)]
+struct Pending {
+    handshakes: Handshakes<Async<TcpStream>>,
+    timer: Timer,
+}
+
+/// The tasks that are waiting in [`accept()`](traits::Listener::accept).
+///
+/// The listener and each stream only keep the waker of whoever polled them last, so they are
+/// given a waker that wakes all of those tasks instead of that of any individual one.
+#[derive(Debug, Default)]
+struct Wakers(Mutex<Vec<Waker>>);
+impl Wakers {
+    fn register(&self, waker: &Waker) {
+        let mut wakers = lock(&self.0);
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+}
+impl Wake for Wakers {
+    fn wake(self: Arc<Self>) { self.wake_by_ref() }
+    fn wake_by_ref(self: &Arc<Self>) {
+        let wakers = std::mem::take(&mut *lock(&self.0));
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl Sealed for Listener {}
+impl traits::Listener for Listener {
+    type Stream = Stream;
+
+    fn from_options(options: ListenerOptions<'_>) -> io::Result<Self> {
+        options
+            .nonblocking(ListenerNonblockingMode::Both)
+            .create_sync_as::<SyncListener>()
+            .and_then(Self::from_nonblocking_sync)
+    }
+    async fn accept(&self) -> io::Result<Stream> { poll_fn(|cx| self.poll_accept(cx)).await }
+
+    fn do_not_reclaim_name_on_drop(&mut self) { self.port_file.forget() }
+    fn local_name(&self) -> io::Result<Name<'static>> { Ok(tcp_name(self.port_file.path())) }
+}
+impl Listener {
+    fn from_nonblocking_sync(mut sync: SyncListener) -> io::Result<Self> {
+        let port_file = sync.port_file.take();
+        Ok(Self {
+            listener: Async::new(sync.listener)?,
+            port_file,
+            pending: Mutex::new(Pending { handshakes: Handshakes::new(), timer: Timer::never() }),
+            wakers: Arc::default(),
+        })
+    }
+    fn poll_accept(&self, cx: &mut Context<'_>) -> Poll<io::Result<Stream>> {
+        self.wakers.register(cx.waker());
+        let waker = Waker::from(Arc::clone(&self.wakers));
+        let cx = &mut Context::from_waker(&waker);
+        let mut pending = lock(&self.pending);
+        let Pending { handshakes, timer } = &mut *pending;
+        let token = self.port_file.token();
+        loop {
+            match self.listener.get_ref().accept() {
+                Ok((stream, _)) => {
+                    // Clients that fail the handshake are dropped, and the next one is waited for
+                    if let Some(stream) = handshakes.start(Async::new(stream)?, token) {
+                        return Poll::Ready(finish(stream));
+                    }
+                    continue;
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+            if let Some(stream) = handshakes.advance(token) {
+                return Poll::Ready(finish(stream));
+            }
+            // Errors are picked up by the next attempt to accept or read
+            let mut ready = self.listener.poll_readable(cx).is_ready();
+            for stream in handshakes.streams() {
+                ready |= stream.poll_readable(cx).is_ready();
+            }
+            if let Some(deadline) = handshakes.next_deadline() {
+                timer.set_at(deadline);
+                ready |= Pin::new(&mut *timer).poll(cx).is_ready();
+            }
+            if !ready {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    /// Returns the port on the loopback interface that the listener is bound to.
+    pub fn port(&self) -> io::Result<u16> { Ok(self.listener.get_ref().local_addr()?.port()) }
+}
+
+/// Prepares a client that has completed the handshake for use.
+fn finish(stream: Async<TcpStream>) -> io::Result<Stream> {
+    stream.get_ref().set_nodelay(true)?;
+    Ok(Stream::from(stream))
+}
+
+fn lock<T>(m: &Mutex<T>) -> MutexGuard<'_, T> { m.lock().unwrap_or_else(|e| e.into_inner()) }
+
+/// Access to the underlying implementation.
+impl Listener {
+    /// Borrows the [`Async`]-wrapped [`TcpListener`] contained within, granting access to
+    /// operations defined on it.
+    ///
+    /// Clients accepted directly from it have not been authenticated.
+    #[inline(always)]
+    pub fn inner(&self) -> &Async<TcpListener> { &self.listener }
+}
+
+/// Sets the sync `Listener` to `ListenerNonblockingMode::Both`.
+impl TryFrom<SyncListener> for Listener {
+    type Error = io::Error;
+    fn try_from(sync: SyncListener) -> io::Result<Self> {
+        sync.set_nonblocking(ListenerNonblockingMode::Both)?;
+        Self::from_nonblocking_sync(sync)
+    }
+}
+
+impl AsFd for Listener {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> { self.listener.as_fd() }
+}
@@ -0,0 +1,177 @@
+use {
+    super::super::{
+        loopback, peer_creds_unsupported, port_file, port_file_path, CONN_TIMEOUT_MSG,
+    },
+    crate::{
+        error::ReuniteError,
+        local_socket::{
+            traits::{async_io as traits, StreamCommon},
+            ConnectOptions, Name, PeerCreds,
+        },
+        ConnectWaitMode, Sealed,
+    },
+    ::async_io::{Async, Timer},
+    std::{
+        future::{poll_fn, Future},
+        io::{self, Write as _},
+        net::TcpStream,
+        os::unix::prelude::*,
+        pin::{pin, Pin},
+        sync::Arc,
+        task::Poll,
+        time::Duration,
+    },
+};
+
+/// Wrapper around [`TcpStream`] registered with the async-io reactor that implements
+/// [`Stream`](traits::Stream).
+#[derive(Debug)]
+pub struct Stream(pub(super) Async<TcpStream>);
+impl Sealed for Stream {}
+
+impl traits::Stream for Stream {
+    type RecvHalf = RecvHalf;
+    type SendHalf = SendHalf;
+
+    async fn from_options(opts: &ConnectOptions<'_>) -> io::Result<Self> {
+        let (port, token) = port_file::read(port_file_path(&opts.name)?)?;
+        let connect = Async::<TcpStream>::connect(loopback(port));
+        let stream = match opts.get_wait_mode() {
+            ConnectWaitMode::Timeout(timeout) => {
+                with_timeout(connect, timeout, || {
+                    io::Error::new(io::ErrorKind::TimedOut, CONN_TIMEOUT_MSG)
+                })
+                .await
+            }
+            ConnectWaitMode::Deferred | ConnectWaitMode::Unbounded => connect.await,
+        }?;
+        stream.get_ref().set_nodelay(true)?;
+        let mut rest = &token.0[..];
+        while !rest.is_empty() {
+            let written = stream.write_with(|s| (&*s).write(rest)).await?;
+            if written == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            rest = rest.get(written..).unwrap_or_default();
+        }
+        Ok(Self(stream))
+    }
+    #[inline]
+    fn split(self) -> (RecvHalf, SendHalf) {
+        let arc = Arc::new(self);
+        (RecvHalf(Arc::clone(&arc)), SendHalf(arc))
+    }
+    #[inline]
+    #[allow(clippy::unwrap_in_result)]
+    fn reunite(rh: RecvHalf, sh: SendHalf) -> Result<Self, ReuniteError<RecvHalf, SendHalf>> {
+        if !Arc::ptr_eq(&rh.0, &sh.0) {
+            return Err(ReuniteError { rh, sh });
+        }
+        drop(rh);
+        let inner = Arc::into_inner(sh.0).expect("stream half inexplicably copied");
+        Ok(inner)
+    }
+}
+impl StreamCommon for Stream {
+    #[inline]
+    fn take_error(&self) -> io::Result<Option<io::Error>> { self.0.get_ref().take_error() }
+    #[inline]
+    fn peer_creds(&self) -> io::Result<PeerCreds> { Err(peer_creds_unsupported()) }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> { Ok(None) }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> { Ok(None) }
+}
+
+/// Runs the given future to completion or until the timeout expires, whichever comes first.
+pub(super) async fn with_timeout<T>(
+    fut: impl Future<Output = io::Result<T>>,
+    timeout: Duration,
+    timed_out: impl Fn() -> io::Error,
+) -> io::Result<T> {
+    let mut fut = pin!(fut);
+    let mut timer = Timer::after(timeout);
+    poll_fn(|cx| {
+        if let Poll::Ready(rslt) = fut.as_mut().poll(cx) {
+            return Poll::Ready(rslt);
+        }
+        Pin::new(&mut timer).poll(cx).map(|_| Err(timed_out()))
+    })
+    .await
+}
+
+/// Access to the underlying implementation.
+impl Stream {
+    /// Borrows the [`Async`]-wrapped [`TcpStream`] contained within, granting access to
+    /// operations defined on it.
+    #[inline(always)]
+    pub fn inner(&self) -> &Async<TcpStream> { &self.0 }
+}
+
+/// The stream is assumed to have already gone through the handshake.
+impl From<Async<TcpStream>> for Stream {
+    #[inline]
+    fn from(s: Async<TcpStream>) -> Self { Self(s) }
+}
+impl AsFd for Stream {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> { self.0.as_fd() }
+}
+impl TryFrom<Stream> for OwnedFd {
+    type Error = io::Error;
+    #[inline]
+    fn try_from(slf: Stream) -> io::Result<Self> { Ok(slf.0.into_inner()?.into()) }
+}
+
+multimacro! {
+    Stream,
+    forward_rbv(Async<TcpStream>, &),
+    forward_futures_ref_rw,
+    derive_futures_mut_rw,
+}
+
+macro_rules! arc_accessors {
+    ($ty:ty) => {
+        /// [`Arc`] accessors.
+        impl $ty {
+            /// Borrows the [`Stream`] within the `Arc`.
+            #[inline]
+            pub fn as_stream(&self) -> &Stream { &self.0 }
+            /// Extracts the underlying `Arc<Stream>`.
+            #[inline]
+            pub fn into_arc(self) -> Arc<Stream> { self.0 }
+        }
+    };
+}
+
+/// [`Stream`]'s receive half, implemented using [`Arc`].
+#[derive(Clone, Debug)]
+pub struct RecvHalf(Arc<Stream>);
+impl Sealed for RecvHalf {}
+multimacro! {
+    RecvHalf,
+    forward_rbv(Stream, *),
+    arc_accessors,
+    forward_futures_ref_read,
+    forward_as_handle(unix),
+    derive_futures_mut_read,
+}
+impl traits::RecvHalf for RecvHalf {
+    type Stream = Stream;
+}
+
+/// [`Stream`]'s send half, implemented using [`Arc`].
+#[derive(Clone, Debug)]
+pub struct SendHalf(Arc<Stream>);
+impl Sealed for SendHalf {}
+multimacro! {
+    SendHalf,
+    forward_rbv(Stream, *),
+    arc_accessors,
+    forward_futures_ref_write,
+    forward_as_handle(unix),
+    derive_futures_mut_write,
+}
+impl traits::SendHalf for SendHalf {
+    type Stream = Stream;
+}
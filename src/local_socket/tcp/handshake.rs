@@ -0,0 +1,122 @@
+//! Reception of tokens from freshly accepted clients without blocking the listener.
+
+use {
+    super::{Token, HANDSHAKE_TIMEOUT, TOKEN_LEN},
+    std::{
+        collections::VecDeque,
+        fmt::{self, Debug, Formatter},
+        io::{self, Read as _},
+        net::TcpStream,
+        time::Instant,
+    },
+};
+
+/// How many clients can be in the middle of the handshake at once. When another one connects, the
+/// one that has been waiting the longest is dropped, except by the Tokio listener, which stops
+/// accepting until one of them is done.
+pub(super) const MAX_PENDING: usize = 64;
+
+/// Access to the [`TcpStream`] within the stream type that a set of handshakes works with.
+pub(super) trait AsTcpStream {
+    fn as_tcp_stream(&self) -> &TcpStream;
+}
+impl AsTcpStream for TcpStream {
+    #[inline(always)]
+    fn as_tcp_stream(&self) -> &TcpStream { self }
+}
+#[cfg(all(unix, feature = "async"))]
+impl AsTcpStream for async_io::Async<TcpStream> {
+    #[inline(always)]
+    fn as_tcp_stream(&self) -> &TcpStream { self.get_ref() }
+}
+
+enum Status {
+    Done,
+    Pending,
+    Failed,
+}
+
+/// Progress of a single client through the handshake.
+struct Handshake<S> {
+    stream: S,
+    token: [u8; TOKEN_LEN],
+    filled: usize,
+    deadline: Instant,
+}
+impl<S: AsTcpStream> Handshake<S> {
+    fn new(stream: S) -> Self {
+        let now = Instant::now();
+        let deadline = now.checked_add(HANDSHAKE_TIMEOUT).unwrap_or(now);
+        Self { stream, token: [0; TOKEN_LEN], filled: 0, deadline }
+    }
+    /// Reads as much of the token as has arrived, without blocking.
+    fn advance(&mut self, expected: &Token, now: Instant) -> Status {
+        while let Some(rest) = self.token.get_mut(self.filled..).filter(|rest| !rest.is_empty()) {
+            match self.stream.as_tcp_stream().read(rest) {
+                Ok(0) => return Status::Failed,
+                Ok(read) => self.filled = self.filled.saturating_add(read),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock && now < self.deadline => {
+                    return Status::Pending
+                }
+                Err(..) => return Status::Failed,
+            }
+        }
+        if expected.matches(&self.token) {
+            Status::Done
+        } else {
+            Status::Failed
+        }
+    }
+}
+
+/// Clients that have been accepted but have not sent the whole token yet. The streams must be in
+/// nonblocking mode.
+///
+/// Clients that send an invalid token, disconnect or fail to send the token within the handshake
+/// timeout are dropped.
+pub(super) struct Handshakes<S>(VecDeque<Handshake<S>>);
+impl<S: AsTcpStream> Handshakes<S> {
+    pub fn new() -> Self { Self(VecDeque::new()) }
+
+    /// Begins the handshake with a freshly accepted client, returning it right away if it has
+    /// already sent the token.
+    pub fn start(&mut self, stream: S, token: &Token) -> Option<S> {
+        let mut handshake = Handshake::new(stream);
+        match handshake.advance(token, Instant::now()) {
+            Status::Done => return Some(handshake.stream),
+            Status::Pending => {}
+            Status::Failed => return None,
+        }
+        if self.0.len() >= MAX_PENDING {
+            self.0.pop_front();
+        }
+        self.0.push_back(handshake);
+        None
+    }
+    /// Makes progress on every pending handshake without blocking, returning the first client that
+    /// has completed it.
+    pub fn advance(&mut self, token: &Token) -> Option<S> {
+        let now = Instant::now();
+        let mut i = 0;
+        while let Some(handshake) = self.0.get_mut(i) {
+            match handshake.advance(token, now) {
+                Status::Done => return self.0.remove(i).map(|h| h.stream),
+                Status::Pending => i = i.saturating_add(1),
+                Status::Failed => drop(self.0.remove(i)),
+            }
+        }
+        None
+    }
+
+    /// Returns the time at which the earliest pending handshake times out.
+    pub fn next_deadline(&self) -> Option<Instant> { self.0.iter().map(|h| h.deadline).min() }
+    /// Iterates over the streams of the clients that are in the middle of the handshake.
+    pub fn streams(&self) -> impl Iterator<Item = &S> { self.0.iter().map(|h| &h.stream) }
+}
+/// Does not reveal the partially received tokens.
+impl<S> Debug for Handshakes<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handshakes").field("pending", &self.0.len()).finish()
+    }
+}
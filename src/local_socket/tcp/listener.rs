@@ -0,0 +1,185 @@
+use {
+    super::{
+        handshake::Handshakes, loopback, port_file::PortFile, port_file_path,
+        readiness::Readiness, tcp_name, Stream, Token,
+    },
+    crate::local_socket::{traits, ListenerNonblockingMode, ListenerOptions, Name},
+    std::{
+        io,
+        iter::FusedIterator,
+        net::{TcpListener, TcpStream},
+        sync::{
+            atomic::{
+                AtomicBool,
+                Ordering::{Acquire, Release},
+            },
+            Mutex, MutexGuard, TryLockError,
+        },
+        time::Instant,
+    },
+};
+
+/// Wrapper around [`TcpListener`] that implements [`Listener`](traits::Listener).
+///
+/// Clients are authenticated without blocking the listener: while waiting for a client to send the
+/// token, others keep being accepted, and the first one to complete the handshake is returned.
+/// Clients that haven't completed it yet are kept by the listener across calls to
+/// [`accept()`](traits::Listener::accept), including ones that return
+/// [`WouldBlock`](io::ErrorKind::WouldBlock) in nonblocking mode.
+///
+/// On Linux, Android, BSD and Apple platforms, the file descriptor of the listener is that of an
+/// epoll or kqueue instance that becomes readable whenever accepting can make progress, including
+/// when a client completes the handshake, so that the listener can be waited on with other objects.
+/// Its nonblocking flag is the nonblocking mode of accepting. Elsewhere, the file descriptor or
+/// socket is that of the [`TcpListener`], which does not become readable when a client that was
+/// already accepted completes the handshake.
+#[derive(Debug)]
+pub struct Listener {
+    pub(super) listener: TcpListener,
+    pub(super) port_file: PortFile,
+    handshakes: Mutex<Handshakes<TcpStream>>,
+    readiness: Readiness,
+    nonblocking_streams: AtomicBool,
+}
+impl crate::Sealed for Listener {}
+impl traits::Listener for Listener {
+    type Stream = Stream;
+
+    fn from_options(opts: ListenerOptions<'_>) -> io::Result<Self> {
+        let path = port_file_path(&opts.name)?;
+        let listener = TcpListener::bind(loopback(0))?;
+        let port_file =
+            PortFile::create(path, listener.local_addr()?.port(), Token::generate()?, &opts)?;
+        // Clients in the middle of the handshake are waited on along with the listener
+        listener.set_nonblocking(true)?;
+        let readiness = Readiness::new(&listener, opts.get_nonblocking_accept())?;
+        Ok(Self {
+            listener,
+            port_file,
+            readiness,
+            handshakes: Mutex::new(Handshakes::new()),
+            nonblocking_streams: AtomicBool::new(opts.get_nonblocking_stream()),
+        })
+    }
+    fn accept(&self) -> io::Result<Stream> {
+        let nonblocking = self.readiness.is_nonblocking()?;
+        // With no deadline, None is never returned
+        self.accept_until(nonblocking, None)?.ok_or_else(|| io::ErrorKind::TimedOut.into())
+    }
+    #[inline]
+    fn set_nonblocking(&self, nonblocking: ListenerNonblockingMode) -> io::Result<()> {
+        self.readiness.set_nonblocking(nonblocking.accept_nonblocking())?;
+        self.nonblocking_streams.store(nonblocking.stream_nonblocking(), Release);
+        Ok(())
+    }
+    fn do_not_reclaim_name_on_drop(&mut self) { self.port_file.forget() }
+    fn local_name(&self) -> io::Result<Name<'static>> { Ok(tcp_name(self.port_file.path())) }
+}
+impl Iterator for Listener {
+    type Item = io::Result<Stream>;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> { Some(traits::Listener::accept(self)) }
+}
+impl FusedIterator for Listener {}
+
+impl Listener {
+    /// Accepts clients and advances their handshakes until one of them completes it, returning
+    /// `None` if the deadline passes first.
+    fn accept_until(
+        &self,
+        nonblocking: bool,
+        deadline: Option<Instant>,
+    ) -> io::Result<Option<Stream>> {
+        let mut handshakes = self.lock_handshakes(nonblocking)?;
+        let token = self.port_file.token();
+        loop {
+            loop {
+                let stream = match self.listener.accept() {
+                    Ok((stream, _)) => stream,
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(e) => return Err(e),
+                };
+                // Accepted sockets inherit nonblocking mode from the listener on some platforms,
+                // but not on others
+                if stream.set_nonblocking(true).is_err() || self.readiness.add(&stream).is_err() {
+                    continue;
+                }
+                if let Some(stream) = handshakes.start(stream, token) {
+                    return self.finish(stream).map(Some);
+                }
+            }
+            if let Some(stream) = handshakes.advance(token) {
+                return self.finish(stream).map(Some);
+            }
+            if nonblocking {
+                self.readiness.discard_stale();
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            let now = Instant::now();
+            if deadline.is_some_and(|deadline| now >= deadline) {
+                return Ok(None);
+            }
+            let wake = deadline.into_iter().chain(handshakes.next_deadline()).min();
+            let timeout = wake.map(|wake| wake.saturating_duration_since(now));
+            self.readiness.wait(&self.listener, handshakes.streams(), timeout)?;
+        }
+    }
+    /// Only one thread accepts at a time. In nonblocking mode, the listener is considered to have
+    /// no clients while another thread is accepting, as that thread will take the next one.
+    fn lock_handshakes(
+        &self,
+        nonblocking: bool,
+    ) -> io::Result<MutexGuard<'_, Handshakes<TcpStream>>> {
+        if !nonblocking {
+            return Ok(self.handshakes.lock().unwrap_or_else(|e| e.into_inner()));
+        }
+        match self.handshakes.try_lock() {
+            Ok(guard) => Ok(guard),
+            Err(TryLockError::Poisoned(e)) => Ok(e.into_inner()),
+            Err(TryLockError::WouldBlock) => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+    /// Prepares a client that has completed the handshake for use.
+    fn finish(&self, stream: TcpStream) -> io::Result<Stream> {
+        self.readiness.remove(&stream);
+        stream.set_nodelay(true)?;
+        stream.set_nonblocking(self.nonblocking_streams.load(Acquire))?;
+        Ok(Stream(stream))
+    }
+
+    /// Returns the port on the loopback interface that the listener is bound to.
+    pub fn port(&self) -> io::Result<u16> { Ok(self.listener.local_addr()?.port()) }
+}
+
+#[cfg(unix)]
+impl crate::os::unix::local_socket::ListenerExt for Listener {
+    /// ## System calls
+    /// - `epoll_wait` (Linux, Android), `kevent` (BSD, Apple platforms), `poll`/`ppoll` (others)
+    /// - `accept`
+    /// - `recv`
+    fn accept_deadline(&self, deadline: Instant) -> io::Result<Stream> {
+        use crate::os::unix::local_socket::accept_timed_out;
+        self.accept_until(false, Some(deadline))?.ok_or_else(accept_timed_out)
+    }
+}
+
+/// Access to the underlying implementation.
+impl Listener {
+    /// Borrows the [`TcpListener`] contained within, granting access to operations defined on it.
+    ///
+    /// The listener is always in nonblocking mode, and clients accepted directly from it have not
+    /// been authenticated.
+    #[inline(always)]
+    pub fn inner(&self) -> &TcpListener { &self.listener }
+}
+
+#[cfg(unix)]
+impl std::os::unix::io::AsFd for Listener {
+    #[inline]
+    fn as_fd(&self) -> std::os::unix::io::BorrowedFd<'_> { self.readiness.as_fd(&self.listener) }
+}
+#[cfg(windows)]
+impl std::os::windows::io::AsSocket for Listener {
+    #[inline]
+    fn as_socket(&self) -> std::os::windows::io::BorrowedSocket<'_> { self.listener.as_socket() }
+}
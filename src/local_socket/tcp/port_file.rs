@@ -0,0 +1,120 @@
+//! Creation, reading and reclamation of port files.
+
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt as _, PermissionsExt as _};
+use {
+    super::Token,
+    crate::local_socket::ListenerOptions,
+    std::{
+        fs::{self, OpenOptions},
+        io::{self, Write as _},
+        path::{Path, PathBuf},
+    },
+};
+
+/// Port file belonging to a listener, deleted on drop unless told otherwise.
+#[derive(Debug)]
+pub(super) struct PortFile {
+    path: PathBuf,
+    token: Token,
+    reclaim: bool,
+}
+impl PortFile {
+    /// Atomically creates a port file at the given path, replacing an existing one if overwriting
+    /// is enabled in the options and failing with [`AddrInUse`](io::ErrorKind::AddrInUse)
+    /// otherwise.
+    ///
+    /// On Unix, the mode, owner and group set in the options are applied to the port file before
+    /// it appears at the path, and the directory it is created in is verified if requested.
+    pub fn create(
+        path: &Path,
+        port: u16,
+        token: Token,
+        opts: &ListenerOptions<'_>,
+    ) -> io::Result<Self> {
+        #[cfg(unix)]
+        if opts.get_verify_parent_dir() {
+            crate::os::unix::uds_local_socket::verify_parent_dir_of(path)?;
+        }
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(format!(".{}.tmp", Token::generate()?));
+        let tmp = PathBuf::from(tmp);
+
+        let mut open_opts = OpenOptions::new();
+        open_opts.write(true).create_new(true);
+        #[cfg(unix)]
+        open_opts.mode(0o600);
+        let mut file = open_opts.open(&tmp)?;
+        let rslt = (|| {
+            file.write_all(format!("{port} {token}\n").as_bytes())?;
+            #[cfg(unix)]
+            if opts.get_owner().is_some() || opts.get_group().is_some() {
+                std::os::unix::fs::fchown(&file, opts.get_owner(), opts.get_group())?;
+            }
+            #[cfg(unix)]
+            if let Some(mode) = opts.get_mode() {
+                // Applied exactly, regardless of the umask
+                file.set_permissions(fs::Permissions::from_mode(mode))?;
+            }
+            drop(file);
+            if opts.get_try_overwrite() {
+                return fs::rename(&tmp, path);
+            }
+            // Unlike renaming, linking does not replace the destination
+            match fs::hard_link(&tmp, path) {
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    Err(io::ErrorKind::AddrInUse.into())
+                }
+                otherwise => otherwise,
+            }
+        })();
+        // Already gone if it was renamed
+        let _ = fs::remove_file(&tmp);
+        rslt?;
+        Ok(Self { path: path.to_owned(), token, reclaim: opts.get_reclaim_name() })
+    }
+
+    pub fn path(&self) -> &Path { &self.path }
+    pub fn token(&self) -> &Token { &self.token }
+    pub fn forget(&mut self) { self.reclaim = false }
+    /// Moves the responsibility for reclamation to the returned value.
+    pub fn take(&mut self) -> Self {
+        let reclaim = std::mem::replace(&mut self.reclaim, false);
+        Self { path: self.path.clone(), token: self.token, reclaim }
+    }
+}
+impl Drop for PortFile {
+    fn drop(&mut self) {
+        if !self.reclaim {
+            return;
+        }
+        // Another listener may have overwritten the port file in the meantime
+        if read(&self.path).is_ok_and(|(_, token)| token == self.token) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Reads the port and the token from a port file.
+pub(super) fn read(path: &Path) -> io::Result<(u16, Token)> {
+    let contents = fs::read_to_string(path)?;
+    let parse = || {
+        let (port, token) = contents.trim_end().split_once(' ')?;
+        Some((port.parse().ok()?, Token::parse(token)?))
+    };
+    parse().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed port file"))
+}
+
+/// Returns the directory that namespaced names are mapped into.
+#[cfg(unix)]
+pub(super) fn dir() -> io::Result<PathBuf> {
+    crate::os::unix::local_socket::runtime_dir::resolve().map(|dir| dir.into_path())
+}
+/// Returns the directory that namespaced names are mapped into, creating it if necessary. Only the
+/// current user is given access to it, and it is rejected if it is owned by someone else.
+#[cfg(windows)]
+pub(super) fn dir() -> io::Result<PathBuf> {
+    let dir = std::env::temp_dir().join("interprocess");
+    crate::os::windows::create_private_dir(&dir)?;
+    Ok(dir)
+}
@@ -0,0 +1,328 @@
+//! Waiting for the listener to have clients to accept or handshakes to advance.
+//!
+//! Where the OS has a pollable multiplexing facility (epoll or kqueue), the listening socket and
+//! the clients in the middle of the handshake are registered with an instance of it, whose file
+//! descriptor is exposed as that of the listener. It becomes readable whenever
+//! [`accept()`](crate::local_socket::traits::Listener::accept) can make progress, including when a
+//! client that was already accepted delivers its token, and its nonblocking flag is the
+//! nonblocking mode of accepting. Elsewhere, the listener and the clients are polled directly, and
+//! the nonblocking mode is kept in memory.
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(super) use epoll::Readiness;
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "watchos",
+    target_os = "visionos",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub(super) use kqueue::Readiness;
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "watchos",
+    target_os = "visionos",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+pub(super) use poll::Readiness;
+use std::{
+    io,
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+/// Rounded up, so as to not wake up right before the deadline.
+#[cfg(any(windows, target_os = "linux", target_os = "android"))]
+fn timeout_ms(timeout: Option<Duration>) -> i32 {
+    timeout.map_or(-1, |t| {
+        let ms = t.as_millis().saturating_add(u128::from(t.subsec_nanos() % 1_000_000 != 0));
+        i32::try_from(ms).unwrap_or(i32::MAX)
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod epoll {
+    use {
+        super::*,
+        crate::{os::unix::c_wrappers, OrErrno as _},
+        std::os::unix::prelude::*,
+    };
+
+    #[derive(Debug)]
+    pub(in super::super) struct Readiness(OwnedFd);
+    impl Readiness {
+        pub fn new(listener: &TcpListener, nonblocking: bool) -> io::Result<Self> {
+            // SAFETY: no pointers involved
+            let fd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+            let fd = (fd != -1).true_val_or_errno(fd)?;
+            // SAFETY: just created
+            let slf = Self(unsafe { OwnedFd::from_raw_fd(fd) });
+            slf.ctl(libc::EPOLL_CTL_ADD, listener.as_fd())?;
+            slf.set_nonblocking(nonblocking)?;
+            Ok(slf)
+        }
+        fn ctl(&self, op: libc::c_int, fd: BorrowedFd<'_>) -> io::Result<()> {
+            #[allow(clippy::cast_sign_loss)]
+            let mut event = libc::epoll_event { events: libc::EPOLLIN as u32, u64: 0 };
+            // SAFETY: the event is valid for reads
+            unsafe { libc::epoll_ctl(self.0.as_raw_fd(), op, fd.as_raw_fd(), &mut event) != -1 }
+                .true_val_or_errno(())
+        }
+
+        pub fn is_nonblocking(&self) -> io::Result<bool> {
+            c_wrappers::get_nonblocking(self.0.as_fd())
+        }
+        pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+            c_wrappers::set_nonblocking(self.0.as_fd(), nonblocking)
+        }
+
+        pub fn add(&self, stream: &TcpStream) -> io::Result<()> {
+            self.ctl(libc::EPOLL_CTL_ADD, stream.as_fd())
+        }
+        pub fn remove(&self, stream: &TcpStream) {
+            // Can only fail if the stream is not registered
+            let _ = self.ctl(libc::EPOLL_CTL_DEL, stream.as_fd());
+        }
+
+        pub fn wait<'a>(
+            &self,
+            _listener: &TcpListener,
+            _streams: impl Iterator<Item = &'a TcpStream>,
+            timeout: Option<Duration>,
+        ) -> io::Result<()> {
+            let mut events = [libc::epoll_event { events: 0, u64: 0 }; 8];
+            // SAFETY: the buffer is valid for writes of its length
+            let n = unsafe {
+                libc::epoll_wait(self.0.as_raw_fd(), events.as_mut_ptr(), 8, timeout_ms(timeout))
+            };
+            match (n != -1).true_val_or_errno(()) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => Ok(()),
+                els => els,
+            }
+        }
+        /// Only needed by kqueue.
+        #[inline(always)]
+        pub fn discard_stale(&self) {}
+
+        /// The file descriptor to expose as that of the listener.
+        pub fn as_fd<'a>(&'a self, _listener: &'a TcpListener) -> BorrowedFd<'a> {
+            self.0.as_fd()
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "watchos",
+    target_os = "visionos",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+mod kqueue {
+    use {
+        super::*,
+        crate::{os::unix::c_wrappers, OrErrno as _},
+        std::{os::unix::prelude::*, ptr},
+    };
+
+    #[derive(Debug)]
+    pub(in super::super) struct Readiness(OwnedFd);
+    impl Readiness {
+        pub fn new(listener: &TcpListener, nonblocking: bool) -> io::Result<Self> {
+            // SAFETY: no pointers involved
+            let fd = unsafe { libc::kqueue() };
+            let fd = (fd != -1).true_val_or_errno(fd)?;
+            // SAFETY: just created
+            let slf = Self(unsafe { OwnedFd::from_raw_fd(fd) });
+            // SAFETY: as above
+            unsafe { libc::fcntl(slf.0.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) != -1 }
+                .true_val_or_errno(())?;
+            slf.ctl(false, listener.as_fd())?;
+            slf.set_nonblocking(nonblocking)?;
+            Ok(slf)
+        }
+        fn ctl(&self, delete: bool, fd: BorrowedFd<'_>) -> io::Result<()> {
+            // SAFETY: all fields are integers or pointers, for which zero is valid
+            let mut change = unsafe { std::mem::zeroed::<libc::kevent>() };
+            #[allow(clippy::cast_sign_loss)]
+            change.ident = fd.as_raw_fd() as _;
+            change.filter = libc::EVFILT_READ;
+            change.flags = if delete { libc::EV_DELETE } else { libc::EV_ADD };
+            // SAFETY: the change is valid for reads, and no events are received
+            let ret = unsafe {
+                libc::kevent(self.0.as_raw_fd(), &change, 1, ptr::null_mut(), 0, ptr::null())
+            };
+            (ret != -1).true_val_or_errno(())
+        }
+
+        pub fn is_nonblocking(&self) -> io::Result<bool> {
+            c_wrappers::get_nonblocking(self.0.as_fd())
+        }
+        pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+            c_wrappers::set_nonblocking(self.0.as_fd(), nonblocking)
+        }
+
+        pub fn add(&self, stream: &TcpStream) -> io::Result<()> {
+            self.ctl(false, stream.as_fd())
+        }
+        pub fn remove(&self, stream: &TcpStream) {
+            // Can only fail if the stream is not registered
+            let _ = self.ctl(true, stream.as_fd());
+        }
+
+        pub fn wait<'a>(
+            &self,
+            _listener: &TcpListener,
+            _streams: impl Iterator<Item = &'a TcpStream>,
+            timeout: Option<Duration>,
+        ) -> io::Result<()> {
+            self.collect(timeout)
+        }
+        /// Events stay queued after the data that triggered them is gone until they are
+        /// collected, which would make the kqueue look readable to whoever polls it.
+        pub fn discard_stale(&self) { let _ = self.collect(Some(Duration::ZERO)); }
+        /// Collects events, which puts the ones that are still active back in the queue.
+        fn collect(&self, timeout: Option<Duration>) -> io::Result<()> {
+            let timeout = timeout.map(|t| libc::timespec {
+                tv_sec: t.as_secs().try_into().unwrap_or(libc::time_t::MAX),
+                tv_nsec: t.subsec_nanos().try_into().unwrap_or_default(),
+            });
+            let timeout = timeout.as_ref().map_or(ptr::null(), |t| t as *const _);
+            // SAFETY: as above
+            let mut events = [unsafe { std::mem::zeroed::<libc::kevent>() }; 8];
+            // SAFETY: the buffer is valid for writes of its length
+            let ret = unsafe {
+                libc::kevent(self.0.as_raw_fd(), ptr::null(), 0, events.as_mut_ptr(), 8, timeout)
+            };
+            match (ret != -1).true_val_or_errno(()) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => Ok(()),
+                els => els,
+            }
+        }
+
+        /// The file descriptor to expose as that of the listener.
+        pub fn as_fd<'a>(&'a self, _listener: &'a TcpListener) -> BorrowedFd<'a> {
+            self.0.as_fd()
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "watchos",
+    target_os = "visionos",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+mod poll {
+    use {
+        super::*,
+        std::sync::atomic::{
+            AtomicBool,
+            Ordering::{Acquire, Release},
+        },
+    };
+
+    #[derive(Debug)]
+    pub(in super::super) struct Readiness(AtomicBool);
+    impl Readiness {
+        #[allow(clippy::unnecessary_wraps)]
+        pub fn new(_listener: &TcpListener, nonblocking: bool) -> io::Result<Self> {
+            Ok(Self(AtomicBool::new(nonblocking)))
+        }
+
+        #[allow(clippy::unnecessary_wraps)]
+        pub fn is_nonblocking(&self) -> io::Result<bool> { Ok(self.0.load(Acquire)) }
+        #[allow(clippy::unnecessary_wraps)]
+        pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+            self.0.store(nonblocking, Release);
+            Ok(())
+        }
+
+        #[allow(clippy::unnecessary_wraps)]
+        #[inline(always)]
+        pub fn add(&self, _stream: &TcpStream) -> io::Result<()> { Ok(()) }
+        #[inline(always)]
+        pub fn remove(&self, _stream: &TcpStream) {}
+
+        /// Waits until the listener or one of the streams becomes readable or the timeout
+        /// expires.
+        #[cfg(unix)]
+        pub fn wait<'a>(
+            &self,
+            listener: &TcpListener,
+            streams: impl Iterator<Item = &'a TcpStream>,
+            timeout: Option<Duration>,
+        ) -> io::Result<()> {
+            use std::os::unix::prelude::*;
+            let pollfd = |fd: RawFd| libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+            let mut fds = std::iter::once(listener.as_raw_fd())
+                .chain(streams.map(AsRawFd::as_raw_fd))
+                .map(pollfd)
+                .collect::<Vec<_>>();
+            crate::os::unix::c_wrappers::poll_fds(&mut fds, timeout)
+        }
+        /// Waits until the listener or one of the streams becomes readable or the timeout
+        /// expires.
+        #[cfg(windows)]
+        pub fn wait<'a>(
+            &self,
+            listener: &TcpListener,
+            streams: impl Iterator<Item = &'a TcpStream>,
+            timeout: Option<Duration>,
+        ) -> io::Result<()> {
+            use {
+                std::os::windows::prelude::*,
+                windows_sys::Win32::Networking::WinSock::{
+                    WSAPoll, POLLRDNORM, SOCKET, WSAPOLLFD,
+                },
+            };
+            #[allow(clippy::cast_possible_truncation)] // sockets are pointer-sized
+            let pollfd =
+                |s: RawSocket| WSAPOLLFD { fd: s as SOCKET, events: POLLRDNORM, revents: 0 };
+            let mut fds = std::iter::once(listener.as_raw_socket())
+                .chain(streams.map(AsRawSocket::as_raw_socket))
+                .map(pollfd)
+                .collect::<Vec<_>>();
+            let nfds = u32::try_from(fds.len()).unwrap_or(u32::MAX);
+            // SAFETY: the array is valid for the given length
+            if unsafe { WSAPoll(fds.as_mut_ptr(), nfds, timeout_ms(timeout)) } < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        }
+        #[inline(always)]
+        pub fn discard_stale(&self) {}
+
+        /// The file descriptor to expose as that of the listener, which does not reflect the
+        /// progress of handshakes.
+        #[cfg(unix)]
+        pub fn as_fd<'a>(
+            &'a self,
+            listener: &'a TcpListener,
+        ) -> std::os::unix::io::BorrowedFd<'a> {
+            std::os::unix::io::AsFd::as_fd(listener)
+        }
+    }
+}
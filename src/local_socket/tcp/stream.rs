@@ -0,0 +1,194 @@
+use {
+    super::{loopback, peer_creds_unsupported, port_file, port_file_path},
+    crate::{
+        error::ReuniteError,
+        local_socket::{
+            prelude::*,
+            traits::{self, ReuniteResult},
+            ConnectOptions, Name, PeerCreds,
+        },
+        ConnectWaitMode, Sealed, TryClone,
+    },
+    std::{
+        io::{self, prelude::*},
+        net::TcpStream,
+        sync::Arc,
+        time::Duration,
+    },
+};
+
+/// Wrapper around [`TcpStream`] that implements [`Stream`](traits::Stream).
+#[derive(Debug)]
+pub struct Stream(pub(super) TcpStream);
+impl Sealed for Stream {}
+impl traits::Stream for Stream {
+    type RecvHalf = RecvHalf;
+    type SendHalf = SendHalf;
+
+    fn from_options(opts: &ConnectOptions<'_>) -> io::Result<Self> {
+        let (port, token) = port_file::read(port_file_path(&opts.name)?)?;
+        let stream = match opts.get_wait_mode() {
+            ConnectWaitMode::Timeout(timeout) => {
+                TcpStream::connect_timeout(&loopback(port), timeout)
+            }
+            ConnectWaitMode::Deferred | ConnectWaitMode::Unbounded => {
+                TcpStream::connect(loopback(port))
+            }
+        }?;
+        stream.set_nodelay(true)?;
+        (&stream).write_all(&token.0)?;
+        if opts.get_nonblocking_stream() {
+            stream.set_nonblocking(true)?;
+        }
+        Ok(Self(stream))
+    }
+
+    #[inline]
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    #[inline]
+    fn set_recv_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_read_timeout(timeout)
+    }
+    #[inline]
+    fn set_send_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_write_timeout(timeout)
+    }
+
+    #[inline]
+    fn split(self) -> (RecvHalf, SendHalf) {
+        let arc = Arc::new(self);
+        (RecvHalf(Arc::clone(&arc)), SendHalf(arc))
+    }
+    #[inline]
+    #[allow(clippy::unwrap_in_result)]
+    fn reunite(rh: RecvHalf, sh: SendHalf) -> ReuniteResult<Self> {
+        if !Arc::ptr_eq(&rh.0, &sh.0) {
+            return Err(ReuniteError { rh, sh });
+        }
+        drop(rh);
+        let inner = Arc::into_inner(sh.0).expect("stream half inexplicably copied");
+        Ok(inner)
+    }
+}
+impl traits::StreamCommon for Stream {
+    #[inline]
+    fn take_error(&self) -> io::Result<Option<io::Error>> { self.0.take_error() }
+    #[inline]
+    fn peer_creds(&self) -> io::Result<PeerCreds> { Err(peer_creds_unsupported()) }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> { Ok(None) }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> { Ok(None) }
+}
+
+/// Access to the underlying implementation.
+impl Stream {
+    /// Borrows the [`TcpStream`] contained within, granting access to operations defined on it.
+    #[inline(always)]
+    pub fn inner(&self) -> &TcpStream { &self.0 }
+    /// Mutably borrows the [`TcpStream`] contained within, granting access to operations defined
+    /// on it.
+    ///
+    /// This may allow for non-portable concurrent I/O. Please use [`inner`](Self::inner) instead
+    /// if you can.
+    #[inline(always)]
+    pub fn inner_mut(&mut self) -> &mut TcpStream { &mut self.0 }
+}
+
+/// The stream is assumed to have already gone through the handshake.
+impl From<TcpStream> for Stream {
+    #[inline]
+    fn from(s: TcpStream) -> Self { Self(s) }
+}
+impl From<Stream> for TcpStream {
+    #[inline]
+    fn from(s: Stream) -> Self { s.0 }
+}
+
+impl TryClone for Stream {
+    #[inline]
+    fn try_clone(&self) -> io::Result<Self> { self.0.try_clone().map(Self) }
+}
+
+multimacro! {
+    Stream,
+    forward_rbv(TcpStream, &),
+    forward_sync_ref_read,
+    forward_sync_ref_write,
+    derive_sync_mut_read,
+    derive_sync_mut_write,
+    forward_asinto_handle(unix),
+}
+#[cfg(windows)]
+impl std::os::windows::io::AsSocket for Stream {
+    #[inline]
+    fn as_socket(&self) -> std::os::windows::io::BorrowedSocket<'_> { self.0.as_socket() }
+}
+#[cfg(windows)]
+impl From<Stream> for std::os::windows::io::OwnedSocket {
+    #[inline]
+    fn from(s: Stream) -> Self { s.0.into() }
+}
+
+macro_rules! arc_accessors {
+    ($ty:ty) => {
+        /// [`Arc`] accessors.
+        impl $ty {
+            /// Borrows the [`Stream`] within the `Arc`.
+            #[inline]
+            pub fn as_stream(&self) -> &Stream { &self.0 }
+            /// Extracts the underlying `Arc<Stream>`.
+            #[inline]
+            pub fn into_arc(self) -> Arc<Stream> { self.0 }
+            /// Borrows the underlying `Arc<Stream>`, granting access to extra information about
+            /// the `Arc`.
+            #[inline]
+            pub fn as_arc(&self) -> &Arc<Stream> { &self.0 }
+        }
+    };
+}
+
+/// [`Stream`]'s receive half, implemented using [`Arc`].
+#[derive(Clone, Debug)]
+pub struct RecvHalf(pub(super) Arc<Stream>);
+impl Sealed for RecvHalf {}
+multimacro! {
+    RecvHalf,
+    forward_rbv(Stream, *),
+    arc_accessors,
+    forward_sync_ref_read,
+    forward_as_handle(unix),
+    derive_sync_mut_read,
+}
+impl traits::RecvHalf for RecvHalf {
+    type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_recv_timeout(timeout)
+    }
+}
+
+/// [`Stream`]'s send half, implemented using [`Arc`].
+#[derive(Clone, Debug)]
+pub struct SendHalf(pub(super) Arc<Stream>);
+impl Sealed for SendHalf {}
+multimacro! {
+    SendHalf,
+    forward_rbv(Stream, *),
+    arc_accessors,
+    forward_sync_ref_write,
+    forward_as_handle(unix),
+    derive_sync_mut_write,
+}
+impl traits::SendHalf for SendHalf {
+    type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_send_timeout(timeout)
+    }
+}
@@ -0,0 +1,146 @@
+use {
+    super::{
+        super::{
+            bad_token, handshake::MAX_PENDING, port_file::PortFile, tcp_name,
+            Listener as SyncListener, Token, HANDSHAKE_TIMEOUT, TOKEN_LEN,
+        },
+        Stream,
+    },
+    crate::{
+        local_socket::{
+            prelude::*, traits::tokio as traits, ListenerNonblockingMode, ListenerOptions, Name,
+        },
+        Sealed,
+    },
+    std::{io, sync::Arc},
+    tokio::{
+        io::AsyncReadExt as _,
+        net::{TcpListener, TcpStream},
+        sync::{
+            mpsc::{self, UnboundedReceiver, UnboundedSender},
+            Mutex, OwnedSemaphorePermit, Semaphore,
+        },
+    },
+};
+
+/// Wrapper around [`TcpListener`] that implements [`Listener`](traits::Listener).
+///
+/// Each accepted client goes through the handshake in a task of its own, spawned on the runtime
+/// that [`accept()`](traits::Listener::accept) is called on, so that clients that are slow to send
+/// the token do not hold up others. Up to 64 clients can be in the middle of the handshake at once,
+/// past which no more are accepted until one of them completes it or times out. Accepting is
+/// cancel-safe: clients that complete the handshake after the `accept()` future has been dropped
+/// are returned by the next call.
+#[derive(Debug)]
+pub struct Listener {
+    listener: TcpListener,
+    port_file: PortFile,
+    authenticated_tx: UnboundedSender<TcpStream>,
+    authenticated_rx: Mutex<UnboundedReceiver<TcpStream>>,
+    handshake_permits: Arc<Semaphore>,
+}
+impl Sealed for Listener {}
+impl traits::Listener for Listener {
+    type Stream = Stream;
+
+    fn from_options(options: ListenerOptions<'_>) -> io::Result<Self> {
+        options
+            .nonblocking(ListenerNonblockingMode::Both)
+            .create_sync_as::<SyncListener>()
+            .and_then(Self::from_nonblocking_sync)
+    }
+    async fn accept(&self) -> io::Result<Stream> {
+        let mut authenticated = self.authenticated_rx.lock().await;
+        loop {
+            tokio::select! {
+                biased;
+                // The sender is never dropped, so this cannot return None
+                Some(stream) = authenticated.recv() => return Ok(Stream::from(stream)),
+                rslt = self.accept_permitted() => {
+                    let (permit, stream) = rslt?;
+                    let token = *self.port_file.token();
+                    let authenticated_tx = self.authenticated_tx.clone();
+                    tokio::spawn(async move {
+                        // Clients that fail the handshake are dropped
+                        if let Ok(stream) = handshake(stream, &token).await {
+                            let _ = authenticated_tx.send(stream);
+                        }
+                        drop(permit);
+                    });
+                }
+            }
+        }
+    }
+
+    fn do_not_reclaim_name_on_drop(&mut self) { self.port_file.forget() }
+    fn local_name(&self) -> io::Result<Name<'static>> { Ok(tcp_name(self.port_file.path())) }
+}
+impl Listener {
+    fn from_nonblocking_sync(mut sync: SyncListener) -> io::Result<Self> {
+        let port_file = sync.port_file.take();
+        let (authenticated_tx, authenticated_rx) = mpsc::unbounded_channel();
+        Ok(Self {
+            listener: TcpListener::from_std(sync.listener)?,
+            port_file,
+            authenticated_tx,
+            authenticated_rx: Mutex::new(authenticated_rx),
+            handshake_permits: Arc::new(Semaphore::new(MAX_PENDING)),
+        })
+    }
+    /// Waits for fewer than the maximum number of clients to be in the middle of the handshake,
+    /// then accepts one.
+    async fn accept_permitted(&self) -> io::Result<(OwnedSemaphorePermit, TcpStream)> {
+        let permit = Arc::clone(&self.handshake_permits)
+            .acquire_owned()
+            .await
+            // The semaphore is never closed
+            .map_err(io::Error::other)?;
+        let (stream, _) = self.listener.accept().await?;
+        Ok((permit, stream))
+    }
+
+    /// Returns the port on the loopback interface that the listener is bound to.
+    pub fn port(&self) -> io::Result<u16> { Ok(self.listener.local_addr()?.port()) }
+}
+
+/// Receives the token from a freshly accepted client.
+async fn handshake(mut stream: TcpStream, token: &Token) -> io::Result<TcpStream> {
+    let mut received = [0; TOKEN_LEN];
+    tokio::time::timeout(HANDSHAKE_TIMEOUT, stream.read_exact(&mut received))
+        .await
+        .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))??;
+    if !token.matches(&received) {
+        return Err(bad_token());
+    }
+    stream.set_nodelay(true)?;
+    Ok(stream)
+}
+
+/// Access to the underlying implementation.
+impl Listener {
+    /// Borrows the [`TcpListener`] contained within, granting access to operations defined on it.
+    ///
+    /// Clients accepted directly from it have not been authenticated.
+    #[inline(always)]
+    pub fn inner(&self) -> &TcpListener { &self.listener }
+}
+
+/// Sets the sync `Listener` to `ListenerNonblockingMode::Both`.
+impl TryFrom<SyncListener> for Listener {
+    type Error = io::Error;
+    fn try_from(sync: SyncListener) -> io::Result<Self> {
+        sync.set_nonblocking(ListenerNonblockingMode::Both)?;
+        Self::from_nonblocking_sync(sync)
+    }
+}
+
+#[cfg(unix)]
+impl std::os::unix::io::AsFd for Listener {
+    #[inline]
+    fn as_fd(&self) -> std::os::unix::io::BorrowedFd<'_> { self.listener.as_fd() }
+}
+#[cfg(windows)]
+impl std::os::windows::io::AsSocket for Listener {
+    #[inline]
+    fn as_socket(&self) -> std::os::windows::io::BorrowedSocket<'_> { self.listener.as_socket() }
+}
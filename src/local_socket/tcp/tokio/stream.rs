@@ -0,0 +1,361 @@
+#[cfg(windows)]
+use std::os::windows::prelude::*;
+use {
+    super::super::{
+        loopback, peer_creds_unsupported, port_file, port_file_path, CONN_TIMEOUT_MSG,
+    },
+    crate::{
+        error::ReuniteError,
+        local_socket::{
+            tokio::timeouts::{Direction, Timeouts},
+            traits::{tokio as traits, StreamCommon},
+            ConnectOptions, Name, PeerCreds,
+        },
+        ConnectWaitMode, Sealed, TryClone,
+    },
+    std::{
+        io::{self, ErrorKind::WouldBlock, IoSlice, IoSliceMut},
+        net::TcpStream as SyncTcpStream,
+        pin::Pin,
+        sync::Arc,
+        task::{ready, Context, Poll},
+        time::Duration,
+    },
+    tokio::{
+        io::{AsyncRead, AsyncWrite, AsyncWriteExt as _, Interest, ReadBuf, Ready},
+        net::{
+            tcp::{OwnedReadHalf as RecvHalfImpl, OwnedWriteHalf as SendHalfImpl},
+            TcpStream,
+        },
+    },
+};
+#[cfg(unix)]
+use {
+    crate::{
+        error::ConversionError, local_socket::tcp::Stream as SyncStream,
+        os::unix::tokio_conv::convert_via_dup,
+    },
+    std::os::unix::prelude::*,
+};
+
+/// Wrapper around [`TcpStream`] that implements [`Stream`](traits::Stream).
+#[derive(Debug)]
+pub struct Stream(pub(super) TcpStream, Arc<Timeouts>);
+impl Sealed for Stream {}
+
+impl traits::Stream for Stream {
+    type RecvHalf = RecvHalf;
+    type SendHalf = SendHalf;
+
+    async fn from_options(opts: &ConnectOptions<'_>) -> io::Result<Self> {
+        let (port, token) = port_file::read(port_file_path(&opts.name)?)?;
+        let connect = TcpStream::connect(loopback(port));
+        let mut stream = match opts.get_wait_mode() {
+            ConnectWaitMode::Timeout(timeout) => tokio::time::timeout(timeout, connect)
+                .await
+                .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, CONN_TIMEOUT_MSG))?,
+            ConnectWaitMode::Deferred | ConnectWaitMode::Unbounded => connect.await,
+        }?;
+        stream.set_nodelay(true)?;
+        stream.write_all(&token.0).await?;
+        Ok(Self::from(stream))
+    }
+    #[inline]
+    fn set_recv_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Recv, timeout)
+    }
+    #[inline]
+    fn set_send_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Send, timeout)
+    }
+    #[inline]
+    fn set_idle_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set_idle(timeout)
+    }
+    #[inline]
+    async fn ready(&self, interest: Interest) -> io::Result<Ready> {
+        self.0.ready(interest).await
+    }
+    #[inline]
+    async fn readable(&self) -> io::Result<()> { self.0.readable().await }
+    #[inline]
+    async fn writable(&self) -> io::Result<()> { self.0.writable().await }
+    #[inline]
+    fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.1.record(self.0.try_read(buf))
+    }
+    #[inline]
+    fn try_read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.1.record(self.0.try_read_vectored(bufs))
+    }
+    #[inline]
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize> { self.1.record(self.0.try_write(buf)) }
+    #[inline]
+    fn try_write_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.1.record(self.0.try_write_vectored(bufs))
+    }
+    fn split(self) -> (RecvHalf, SendHalf) {
+        let (r, w) = self.0.into_split();
+        (RecvHalf(r, Arc::clone(&self.1)), SendHalf(w, self.1))
+    }
+    #[inline]
+    fn reunite(rh: RecvHalf, sh: SendHalf) -> Result<Self, ReuniteError<RecvHalf, SendHalf>> {
+        let (RecvHalf(rh, rt), SendHalf(sh, st)) = (rh, sh);
+        match rh.reunite(sh) {
+            Ok(s) => Ok(Self(s, rt)),
+            Err(tokio::net::tcp::ReuniteError(rh, sh)) => {
+                Err(ReuniteError { rh: RecvHalf(rh, rt), sh: SendHalf(sh, st) })
+            }
+        }
+    }
+}
+impl StreamCommon for Stream {
+    #[inline]
+    fn take_error(&self) -> io::Result<Option<io::Error>> { self.0.take_error() }
+    #[inline]
+    fn peer_creds(&self) -> io::Result<PeerCreds> { Err(peer_creds_unsupported()) }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> { Ok(None) }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> { Ok(None) }
+}
+
+/// Access to the underlying implementation.
+impl Stream {
+    /// Borrows the [`TcpStream`] contained within, granting access to operations defined on it.
+    #[inline(always)]
+    pub fn inner(&self) -> &TcpStream { &self.0 }
+    /// Mutably borrows the [`TcpStream`] contained within, granting access to operations defined
+    /// on it.
+    #[inline(always)]
+    pub fn inner_mut(&mut self) -> &mut TcpStream { &mut self.0 }
+}
+
+fn ioloop(
+    mut try_io: impl FnMut() -> io::Result<usize>,
+    mut poll_read_ready: impl FnMut() -> Poll<io::Result<()>>,
+) -> Poll<io::Result<usize>> {
+    loop {
+        match try_io() {
+            Err(e) if e.kind() == WouldBlock => ready!(poll_read_ready()?),
+            els => return Poll::Ready(els),
+        };
+    }
+}
+
+multimacro! {
+    Stream,
+    forward_rbv(TcpStream, &),
+    derive_tokio_mut_rw,
+    forward_as_handle(unix),
+}
+/// The stream is assumed to have already gone through the handshake.
+impl From<TcpStream> for Stream {
+    #[inline]
+    fn from(s: TcpStream) -> Self { Self(s, Arc::default()) }
+}
+impl From<Stream> for TcpStream {
+    #[inline]
+    fn from(s: Stream) -> Self { s.0 }
+}
+impl AsyncRead for &Stream {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.1
+            .poll(Direction::Recv, cx, |cx| {
+                ioloop(|| self.0.try_read_buf(buf), || self.0.poll_read_ready(cx))
+            })
+            .map(|e| e.map(|_| ()))
+    }
+}
+impl AsyncWrite for &Stream {
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.1.poll(Direction::Send, cx, |cx| {
+            ioloop(|| self.0.try_write(buf), || self.0.poll_write_ready(cx))
+        })
+    }
+    #[inline]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.1.poll(Direction::Send, cx, |cx| {
+            ioloop(|| self.0.try_write_vectored(bufs), || self.0.poll_write_ready(cx))
+        })
+    }
+    #[inline]
+    fn is_write_vectored(&self) -> bool { self.0.is_write_vectored() }
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+/// Duplicates the socket and registers the duplicate with the Tokio runtime of the current
+/// context, panicking if there is none. The clone shares
+/// [timeouts](traits::Stream::set_recv_timeout) with the original.
+impl TryClone for Stream {
+    fn try_clone(&self) -> io::Result<Self> {
+        #[cfg(unix)]
+        let dup = SyncTcpStream::from(self.0.as_fd().try_clone_to_owned()?);
+        #[cfg(windows)]
+        let dup = SyncTcpStream::from(self.0.as_socket().try_clone_to_owned()?);
+        dup.set_nonblocking(true)?;
+        Ok(Self(TcpStream::from_std(dup)?, Arc::clone(&self.1)))
+    }
+}
+/// Registers the stream with the Tokio runtime of the current context, panicking if there is none.
+/// The sync stream is returned as part of the error if registration fails.
+#[cfg(unix)]
+impl TryFrom<SyncStream> for Stream {
+    type Error = ConversionError<SyncStream>;
+    fn try_from(stream: SyncStream) -> Result<Self, Self::Error> {
+        convert_via_dup(stream, true, |fd| {
+            Ok(TcpStream::from_std(SyncTcpStream::from(fd))?.into())
+        })
+    }
+}
+/// Deregisters the stream from the Tokio runtime and puts it in blocking mode. The Tokio stream
+/// is returned as part of the error if that fails.
+#[cfg(unix)]
+impl TryFrom<Stream> for SyncStream {
+    type Error = ConversionError<Stream>;
+    fn try_from(stream: Stream) -> Result<Self, Self::Error> {
+        convert_via_dup(stream, false, |fd| Ok(Self::from(SyncTcpStream::from(fd))))
+    }
+}
+#[cfg(windows)]
+impl AsSocket for Stream {
+    #[inline]
+    fn as_socket(&self) -> BorrowedSocket<'_> { self.0.as_socket() }
+}
+
+macro_rules! tokio_accessors {
+    ($ty:ty, $inner:ty) => {
+        /// Tokio accessors.
+        impl $ty {
+            /// Borrows the underlying Tokio object, granting access to its methods.
+            #[inline]
+            pub fn as_tokio(&self) -> &$inner { &self.0 }
+            /// Extracts the underlying Tokio object.
+            #[inline]
+            pub fn into_tokio(self) -> $inner { self.0 }
+        }
+    };
+}
+
+/// [`Stream`]'s receive half, internally implemented using [`Arc`](std::sync::Arc) by Tokio.
+pub struct RecvHalf(RecvHalfImpl, Arc<Timeouts>);
+impl Sealed for RecvHalf {}
+multimacro! {
+    RecvHalf,
+    tokio_accessors(RecvHalfImpl),
+    forward_debug("local_socket::RecvHalf"),
+    derive_tokio_mut_read,
+}
+impl traits::RecvHalf for RecvHalf {
+    type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Recv, timeout)
+    }
+}
+impl AsyncRead for &RecvHalf {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.1
+            .poll(Direction::Recv, cx, |cx| {
+                ioloop(|| self.0.try_read_buf(buf), || self.0.as_ref().poll_read_ready(cx))
+            })
+            .map(|e| e.map(|_| ()))
+    }
+}
+#[cfg(unix)]
+impl AsFd for RecvHalf {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> { self.0.as_ref().as_fd() }
+}
+#[cfg(windows)]
+impl AsSocket for RecvHalf {
+    #[inline]
+    fn as_socket(&self) -> BorrowedSocket<'_> { self.0.as_ref().as_socket() }
+}
+
+/// [`Stream`]'s send half, internally implemented using [`Arc`](std::sync::Arc) by Tokio.
+pub struct SendHalf(SendHalfImpl, Arc<Timeouts>);
+impl Sealed for SendHalf {}
+multimacro! {
+    SendHalf,
+    tokio_accessors(SendHalfImpl),
+    forward_rbv(SendHalfImpl, &),
+    forward_debug("local_socket::SendHalf"),
+    derive_tokio_mut_write,
+}
+impl traits::SendHalf for SendHalf {
+    type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Send, timeout)
+    }
+}
+impl AsyncWrite for &SendHalf {
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.1.poll(Direction::Send, cx, |cx| {
+            ioloop(|| self.0.try_write(buf), || self.0.as_ref().poll_write_ready(cx))
+        })
+    }
+    #[inline]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.1.poll(Direction::Send, cx, |cx| {
+            ioloop(|| self.0.try_write_vectored(bufs), || self.0.as_ref().poll_write_ready(cx))
+        })
+    }
+    #[inline]
+    fn is_write_vectored(&self) -> bool { self.0.is_write_vectored() }
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+#[cfg(unix)]
+impl AsFd for SendHalf {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> { self.0.as_ref().as_fd() }
+}
+#[cfg(windows)]
+impl AsSocket for SendHalf {
+    #[inline]
+    fn as_socket(&self) -> BorrowedSocket<'_> { self.0.as_ref().as_socket() }
+}
@@ -4,7 +4,7 @@ use crate::os::unix::uds_local_socket::tokio as uds_impl;
 use crate::os::windows::named_pipe::local_socket::tokio as np_impl;
 use {
     super::r#trait,
-    crate::local_socket::{tokio::Stream, ListenerOptions},
+    crate::local_socket::{tcp::tokio as tcp_impl, tokio::Stream, ListenerOptions, Name},
     std::io,
 };
 
@@ -36,10 +36,14 @@ impl r#trait::Listener for Listener {
     }
     #[inline]
     async fn accept(&self) -> io::Result<Stream> {
-        dispatch!(Self: x in self => x.accept()).await.map(Stream::from)
+        dispatch!(Self: x in self => x.accept().await.map(Stream::from))
     }
     #[inline]
     fn do_not_reclaim_name_on_drop(&mut self) {
         dispatch!(Self: x in self => x.do_not_reclaim_name_on_drop())
     }
+    #[inline]
+    fn local_name(&self) -> io::Result<Name<'static>> {
+        dispatch!(Self: x in self => x.local_name())
+    }
 }
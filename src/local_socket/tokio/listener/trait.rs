@@ -1,9 +1,21 @@
 use {
     crate::{
-        local_socket::{tokio::stream::r#trait::Stream, ListenerOptions},
+        local_socket::{tokio::stream::r#trait::Stream, ListenerOptions, Name},
         Sealed,
     },
-    std::{future::Future, io},
+    futures_core::{
+        ready,
+        stream::{FusedStream, Stream as FuturesStream},
+    },
+    std::{
+        fmt::{self, Debug, Formatter},
+        future::Future,
+        io,
+        pin::Pin,
+        sync::Arc,
+        task::{Context, Poll},
+    },
+    tokio::sync::{OwnedSemaphorePermit, Semaphore},
 };
 
 /// Tokio local socket server implementations.
@@ -32,4 +44,154 @@ pub trait Listener: Send + Sync + Sized + Sealed {
 
     /// Disables [name reclamation](super::enum::Listener#name-reclamation) on the listener.
     fn do_not_reclaim_name_on_drop(&mut self);
+
+    /// Returns the name the listener is bound to. See the documentation of
+    /// [the sync equivalent](crate::local_socket::traits::Listener::local_name) for details.
+    fn local_name(&self) -> io::Result<Name<'static>>;
+}
+
+/// Methods derived from the interface of [`Listener`].
+pub trait ListenerExt: Listener {
+    /// Creates an infinite [`Stream`](futures_core::Stream) which calls
+    /// [`.accept()`](Listener::accept) each time it is polled for a new item. This is the Tokio
+    /// counterpart of [`incoming()`](crate::local_socket::traits::ListenerExt::incoming) on sync
+    /// listeners.
+    ///
+    /// The stream does not limit the number of connections that can be in flight at the same time.
+    /// When connections are handled by `StreamExt::for_each_concurrent`, that limit is imposed by
+    /// its own argument, since it stops polling for new connections once the limit is reached.
+    /// For cases in which connections outlive their handler futures, such as when they are moved
+    /// into spawned tasks, see [`.incoming_limited()`](Self::incoming_limited).
+    #[inline]
+    fn incoming(&self) -> Incoming<'_, Self> { self.into() }
+
+    /// Like [`.incoming()`](Self::incoming), but stops accepting connections while
+    /// `max_concurrent` of them are in flight.
+    ///
+    /// Each connection is yielded together with a [`ConnectionPermit`] that counts it toward the
+    /// limit until dropped. The permit can be moved into a spawned task alongside the connection,
+    /// so that the listener keeps new clients waiting until an earlier one is done being served.
+    ///
+    /// # Panics
+    /// If `max_concurrent` is zero or exceeds [`Semaphore::MAX_PERMITS`].
+    #[inline]
+    fn incoming_limited(&self, max_concurrent: usize) -> LimitedIncoming<'_, Self> {
+        LimitedIncoming::new(self, max_concurrent)
+    }
+}
+impl<T: Listener> ListenerExt for T {}
+
+type AcceptFuture<'a, S> = Pin<Box<dyn Future<Output = io::Result<S>> + Send + Sync + 'a>>;
+
+/// An infinite stream of incoming client connections of a Tokio [`Listener`].
+///
+/// This stream is created by the [`incoming()`](ListenerExt::incoming) method on
+/// [`ListenerExt`] – see its documentation for more.
+pub struct Incoming<'a, L: Listener> {
+    listener: &'a L,
+    accept: Option<AcceptFuture<'a, L::Stream>>,
+}
+impl<'a, L: Listener> From<&'a L> for Incoming<'a, L> {
+    fn from(listener: &'a L) -> Self { Self { listener, accept: None } }
 }
+impl<L: Listener> Incoming<'_, L> {
+    fn poll_accept(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<L::Stream>> {
+        let listener = self.listener;
+        let accept = self.accept.get_or_insert_with(|| Box::pin(listener.accept()));
+        let rslt = ready!(accept.as_mut().poll(cx));
+        self.accept = None;
+        Poll::Ready(rslt)
+    }
+}
+impl<L: Listener> FuturesStream for Incoming<'_, L> {
+    type Item = io::Result<L::Stream>;
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_accept(cx).map(Some)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (usize::MAX, None) }
+}
+impl<L: Listener> FusedStream for Incoming<'_, L> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool { false }
+}
+impl<L: Listener + Debug> Debug for Incoming<'_, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Incoming")
+            .field("listener", self.listener)
+            .field("accepting", &self.accept.is_some())
+            .finish()
+    }
+}
+
+type AcquireFuture = Pin<Box<dyn Future<Output = OwnedSemaphorePermit> + Send + Sync>>;
+
+/// An infinite stream of incoming client connections of a Tokio [`Listener`] that limits how many
+/// of them can be in flight at the same time.
+///
+/// This stream is created by the [`incoming_limited()`](ListenerExt::incoming_limited) method on
+/// [`ListenerExt`] – see its documentation for more.
+pub struct LimitedIncoming<'a, L: Listener> {
+    incoming: Incoming<'a, L>,
+    semaphore: Arc<Semaphore>,
+    acquire: Option<AcquireFuture>,
+    permit: Option<OwnedSemaphorePermit>,
+}
+impl<'a, L: Listener> LimitedIncoming<'a, L> {
+    fn new(listener: &'a L, max_concurrent: usize) -> Self {
+        assert!(max_concurrent != 0, "concurrent connection limit must be nonzero");
+        Self {
+            incoming: listener.into(),
+            semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            acquire: None,
+            permit: None,
+        }
+    }
+    /// Returns the number of connections that can be accepted before the limit is reached.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.semaphore.available_permits().saturating_add(usize::from(self.permit.is_some()))
+    }
+}
+impl<L: Listener> FuturesStream for LimitedIncoming<'_, L> {
+    type Item = io::Result<(L::Stream, ConnectionPermit)>;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let slf = self.get_mut();
+        if slf.permit.is_none() {
+            let semaphore = &slf.semaphore;
+            let acquire = slf.acquire.get_or_insert_with(|| {
+                let semaphore = Arc::clone(semaphore);
+                Box::pin(async move {
+                    // The semaphore is never closed
+                    semaphore.acquire_owned().await.expect("semaphore closed")
+                })
+            });
+            slf.permit = Some(ready!(acquire.as_mut().poll(cx)));
+            slf.acquire = None;
+        }
+        // The permit is kept across errors and handed out with the next connection instead
+        let conn = ready!(slf.incoming.poll_accept(cx))?;
+        let permit = slf.permit.take().expect("permit acquired above");
+        Poll::Ready(Some(Ok((conn, ConnectionPermit(permit)))))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (usize::MAX, None) }
+}
+impl<L: Listener> FusedStream for LimitedIncoming<'_, L> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool { false }
+}
+impl<L: Listener + Debug> Debug for LimitedIncoming<'_, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LimitedIncoming")
+            .field("incoming", &self.incoming)
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+/// Token that counts a connection yielded by [`LimitedIncoming`] toward its concurrency limit
+/// for as long as it is not dropped.
+#[derive(Debug)]
+pub struct ConnectionPermit(#[allow(dead_code)] OwnedSemaphorePermit);
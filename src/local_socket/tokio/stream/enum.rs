@@ -1,16 +1,24 @@
-#[cfg(unix)]
-use crate::os::unix::uds_local_socket::tokio as uds_impl;
 #[cfg(windows)]
 use crate::os::windows::named_pipe::local_socket::tokio as np_impl;
+#[cfg(unix)]
+use crate::{
+    error::ConversionError,
+    local_socket::{tcp as tcp_sync, Stream as SyncStream},
+    os::unix::uds_local_socket::{self as uds_sync, tokio as uds_impl},
+    TryClone,
+};
 use {
     super::r#trait,
-    crate::local_socket::{traits::StreamCommon, ConnectOptions, PeerCreds},
+    crate::local_socket::{
+        tcp::tokio as tcp_impl, traits::StreamCommon, ConnectOptions, Name, PeerCreds,
+    },
     std::{
-        io,
+        io::{self, IoSlice, IoSliceMut},
         pin::Pin,
         task::{Context, Poll},
+        time::Duration,
     },
-    tokio::io::{AsyncRead, AsyncWrite, ReadBuf},
+    tokio::io::{AsyncRead, AsyncWrite, Interest, ReadBuf, Ready},
 };
 
 impmod! {local_socket::dispatch_tokio as dispatch}
@@ -80,6 +88,46 @@ impl r#trait::Stream for Stream {
     async fn from_options(options: &ConnectOptions<'_>) -> io::Result<Self> {
         dispatch::connect(options).await
     }
+    #[inline]
+    fn set_recv_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        dispatch!(Self: x in self => x.set_recv_timeout(timeout))
+    }
+    #[inline]
+    fn set_send_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        dispatch!(Self: x in self => x.set_send_timeout(timeout))
+    }
+    #[inline]
+    fn set_idle_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        dispatch!(Self: x in self => x.set_idle_timeout(timeout))
+    }
+    #[inline]
+    async fn ready(&self, interest: Interest) -> io::Result<Ready> {
+        dispatch!(Self: x in self => x.ready(interest).await)
+    }
+    #[inline]
+    async fn readable(&self) -> io::Result<()> {
+        dispatch!(Self: x in self => x.readable().await)
+    }
+    #[inline]
+    async fn writable(&self) -> io::Result<()> {
+        dispatch!(Self: x in self => x.writable().await)
+    }
+    #[inline]
+    fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        dispatch!(Self: x in self => x.try_read(buf))
+    }
+    #[inline]
+    fn try_read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        dispatch!(Self: x in self => x.try_read_vectored(bufs))
+    }
+    #[inline]
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        dispatch!(Self: x in self => x.try_write(buf))
+    }
+    #[inline]
+    fn try_write_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        dispatch!(Self: x in self => x.try_write_vectored(bufs))
+    }
     fn split(self) -> (RecvHalf, SendHalf) {
         match self {
             #[cfg(windows)]
@@ -92,6 +140,10 @@ impl r#trait::Stream for Stream {
                 let (rh, sh) = s.split();
                 (RecvHalf::UdSocket(rh), SendHalf::UdSocket(sh))
             }
+            Stream::Tcp(s) => {
+                let (rh, sh) = s.split();
+                (RecvHalf::Tcp(rh), SendHalf::Tcp(sh))
+            }
         }
     }
     fn reunite(rh: RecvHalf, sh: SendHalf) -> ReuniteResult {
@@ -104,7 +156,9 @@ impl r#trait::Stream for Stream {
             (RecvHalf::UdSocket(rh), SendHalf::UdSocket(sh)) => {
                 uds_impl::Stream::reunite(rh, sh).map(From::from).map_err(|e| e.convert_halves())
             }
-            #[allow(unreachable_patterns)]
+            (RecvHalf::Tcp(rh), SendHalf::Tcp(sh)) => {
+                tcp_impl::Stream::reunite(rh, sh).map(From::from).map_err(|e| e.convert_halves())
+            }
             (rh, sh) => Err(ReuniteError { rh, sh }),
         }
     }
@@ -116,6 +170,14 @@ impl StreamCommon for Stream {
     }
     #[inline]
     fn peer_creds(&self) -> io::Result<PeerCreds> { dispatch!(Self: x in self => x.peer_creds()) }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> {
+        dispatch!(Self: x in self => x.local_name())
+    }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> {
+        dispatch!(Self: x in self => x.peer_name())
+    }
 }
 multimacro! {
     Stream,
@@ -123,6 +185,51 @@ multimacro! {
     dispatch_write,
 }
 
+/// Duplicates the file descriptor and registers the duplicate with the Tokio runtime of the current
+/// context, panicking if there is none.
+#[cfg(unix)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl TryClone for Stream {
+    #[inline]
+    fn try_clone(&self) -> io::Result<Self> {
+        dispatch!(Self: x in self => x.try_clone().map(Self::from))
+    }
+}
+/// Registers the stream with the Tokio runtime of the current context, panicking if there is none.
+/// The sync stream is returned as part of the error if registration fails.
+#[cfg(unix)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl TryFrom<SyncStream> for Stream {
+    type Error = ConversionError<SyncStream>;
+    fn try_from(stream: SyncStream) -> Result<Self, Self::Error> {
+        match stream {
+            SyncStream::UdSocket(s) => uds_impl::Stream::try_from(s)
+                .map(From::from)
+                .map_err(|e| e.map_source(From::from)),
+            SyncStream::Tcp(s) => tcp_impl::Stream::try_from(s)
+                .map(From::from)
+                .map_err(|e| e.map_source(From::from)),
+        }
+    }
+}
+/// Deregisters the stream from the Tokio runtime and puts it in blocking mode. The Tokio stream
+/// is returned as part of the error if that fails.
+#[cfg(unix)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl TryFrom<Stream> for SyncStream {
+    type Error = ConversionError<Stream>;
+    fn try_from(stream: Stream) -> Result<Self, Self::Error> {
+        match stream {
+            Stream::UdSocket(s) => uds_sync::Stream::try_from(s)
+                .map(From::from)
+                .map_err(|e| e.map_source(From::from)),
+            Stream::Tcp(s) => tcp_sync::Stream::try_from(s)
+                .map(From::from)
+                .map_err(|e| e.map_source(From::from)),
+        }
+    }
+}
+
 mkenum!(
 /// Receive half of a Tokio-based local socket stream, obtained by splitting a [`Stream`].
 ///
@@ -130,6 +237,11 @@ mkenum!(
 "local_socket::tokio::" RecvHalf);
 impl r#trait::RecvHalf for RecvHalf {
     type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        dispatch!(Self: x in self => x.set_timeout(timeout))
+    }
 }
 multimacro! {
     RecvHalf,
@@ -143,6 +255,11 @@ mkenum!(
 "local_socket::tokio::" SendHalf);
 impl r#trait::SendHalf for SendHalf {
     type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        dispatch!(Self: x in self => x.set_timeout(timeout))
+    }
 }
 multimacro! {
     SendHalf,
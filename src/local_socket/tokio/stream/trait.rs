@@ -6,8 +6,12 @@ use {
         local_socket::{traits::StreamCommon, ConnectOptions, Name},
         Sealed,
     },
-    std::{future::Future, io},
-    tokio::io::{AsyncRead, AsyncWrite},
+    std::{
+        future::Future,
+        io::{self, IoSlice, IoSliceMut},
+        time::Duration,
+    },
+    tokio::io::{AsyncRead, AsyncWrite, Interest, Ready},
 };
 
 /// Tokio local socket stream implementations.
@@ -31,6 +35,79 @@ pub trait Stream:
         async { ConnectOptions::new().name(name).connect_tokio_as::<Self>().await }
     }
 
+    /// Sets the receive timeout to the specified value. If set to `None` (the default), reads will
+    /// wait indefinitely.
+    ///
+    /// Unlike [the sync equivalent](crate::local_socket::traits::Stream::set_recv_timeout), the
+    /// timeout is enforced by [`poll_read`](AsyncRead::poll_read) rather than by the OS: a read
+    /// that has been waiting for data for longer than the timeout fails with
+    /// [`TimedOut`](io::ErrorKind::TimedOut). The wait begins with the first poll that cannot
+    /// complete and ends with the next one that does, whether or not the future that was being
+    /// polled is dropped in the meantime.
+    ///
+    /// The timeout is shared with the [receive half](Self::RecvHalf) after the stream is split.
+    ///
+    /// Timeouts require the [time driver](tokio::runtime::Builder::enable_time) to be enabled on
+    /// the runtime. Streams on which no timeout is set do not make use of it.
+    ///
+    /// # Errors
+    /// An error is returned if `timeout` is `Some(Duration::ZERO)`.
+    fn set_recv_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+    /// Sets the send timeout to the specified value. If set to `None` (the default), writes will
+    /// wait indefinitely.
+    ///
+    /// See [`.set_recv_timeout()`](Self::set_recv_timeout) for the details, which apply here with
+    /// [`poll_write`](AsyncWrite::poll_write) in place of `poll_read`.
+    fn set_send_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+    /// Sets the idle timeout to the specified value. If set to `None` (the default), the stream
+    /// can stay idle indefinitely.
+    ///
+    /// While the idle timeout is set, reads and writes that are waiting for the stream to become
+    /// ready fail with [`TimedOut`](io::ErrorKind::TimedOut) once nothing has been successfully
+    /// read or written in either direction for longer than the timeout. The time of the last
+    /// activity is reset when this method is called.
+    ///
+    /// As with the [receive timeout](Self::set_recv_timeout), the idle timeout is shared with the
+    /// halves of a split stream, requires the time driver and cannot be zero.
+    fn set_idle_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Waits for any of the requested ready states.
+    ///
+    /// The function may complete without the stream being ready. This is a false-positive and
+    /// attempting an operation will return with [`WouldBlock`](io::ErrorKind::WouldBlock). The
+    /// readiness can be used together with the `try_*` methods, such as
+    /// [`.try_read()`](Self::try_read), to perform I/O with custom buffer management.
+    fn ready(&self, interest: Interest) -> impl Future<Output = io::Result<Ready>> + Send + Sync;
+    /// Waits for the stream to become readable. This is equivalent to
+    /// `.ready(Interest::READABLE)`, except the resulting [`Ready`] value is discarded.
+    ///
+    /// See [`.ready()`](Self::ready) for the caveats.
+    fn readable(&self) -> impl Future<Output = io::Result<()>> + Send + Sync;
+    /// Waits for the stream to become writable. This is equivalent to
+    /// `.ready(Interest::WRITABLE)`, except the resulting [`Ready`] value is discarded.
+    ///
+    /// See [`.ready()`](Self::ready) for the caveats.
+    fn writable(&self) -> impl Future<Output = io::Result<()>> + Send + Sync;
+
+    /// Tries to receive data from the stream into the provided buffer, returning how many bytes
+    /// were read, or [`WouldBlock`](io::ErrorKind::WouldBlock) if no data is available.
+    ///
+    /// This is typically paired with [`.readable()`](Self::readable). Readiness is cleared when
+    /// this method returns `WouldBlock`, so that the next call to `.readable()` waits for new
+    /// data to arrive.
+    fn try_read(&self, buf: &mut [u8]) -> io::Result<usize>;
+    /// Like [`.try_read()`](Self::try_read), but reads into a sequence of buffers.
+    fn try_read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize>;
+    /// Tries to send data from the provided buffer into the stream, returning how many bytes
+    /// were written, or [`WouldBlock`](io::ErrorKind::WouldBlock) if the stream is not ready to
+    /// accept data.
+    ///
+    /// This is typically paired with [`.writable()`](Self::writable), with the same readiness
+    /// semantics as for [`.try_read()`](Self::try_read).
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize>;
+    /// Like [`.try_write()`](Self::try_write), but writes from a sequence of buffers.
+    fn try_write_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize>;
+
     /// Splits a stream into a receive half and a send half.
     ///
     /// You probably want to avoid this mechanism for the following reasons:
@@ -64,6 +141,10 @@ pub trait RecvHalf:
 {
     /// The stream type the half is split from.
     type Stream: Stream;
+
+    /// Sets the receive timeout to the specified value. See
+    /// [`Stream::set_recv_timeout()`] for the details.
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
 }
 
 /// Send halves of Tokio [`Stream`]s, obtained through [`.split()`](Stream::split).
@@ -76,6 +157,10 @@ pub trait SendHalf:
 {
     /// The stream type the half is split from.
     type Stream: Stream;
+
+    /// Sets the send timeout to the specified value. See
+    /// [`Stream::set_send_timeout()`] for the details.
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
 }
 
 /// [`ReuniteResult`](crate::error::ReuniteResult) for the [Tokio `Stream` trait](Stream).
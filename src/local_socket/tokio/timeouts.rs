@@ -0,0 +1,153 @@
+//! Receive, send and idle timeouts for Tokio local socket streams, enforced by the `poll_read` and
+//! `poll_write` implementations.
+
+use {
+    std::{
+        future::Future,
+        io,
+        pin::Pin,
+        sync::{Mutex, MutexGuard},
+        task::{Context, Poll},
+        time::{Duration, Instant},
+    },
+    tokio::time::{sleep_until, Sleep},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Recv,
+    Send,
+}
+
+#[derive(Debug, Default)]
+struct Timer {
+    timeout: Option<Duration>,
+    /// When the ongoing wait for readiness started, and the sleep that ends it.
+    pending: Option<(Instant, Pin<Box<Sleep>>)>,
+}
+
+#[derive(Debug)]
+struct Idle {
+    timeout: Option<Duration>,
+    last_activity: Instant,
+}
+
+/// Timeout state shared between a stream and its halves.
+///
+/// No timer is created while no timeouts are set, which means that streams without timeouts can
+/// be used with runtimes that don't have the time driver enabled.
+#[derive(Debug)]
+pub(crate) struct Timeouts {
+    recv: Mutex<Timer>,
+    send: Mutex<Timer>,
+    idle: Mutex<Idle>,
+}
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            recv: Mutex::default(),
+            send: Mutex::default(),
+            idle: Mutex::new(Idle { timeout: None, last_activity: Instant::now() }),
+        }
+    }
+}
+
+fn lock<T>(m: &Mutex<T>) -> MutexGuard<'_, T> { m.lock().unwrap_or_else(|e| e.into_inner()) }
+
+fn check_nonzero(timeout: Option<Duration>) -> io::Result<()> {
+    if timeout == Some(Duration::ZERO) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot set a zero duration timeout",
+        ));
+    }
+    Ok(())
+}
+
+fn timed_out(dir: Direction) -> io::Error {
+    let msg = match dir {
+        Direction::Recv => "receive timed out",
+        Direction::Send => "send timed out",
+    };
+    io::Error::new(io::ErrorKind::TimedOut, msg)
+}
+
+impl Timeouts {
+    fn timer(&self, dir: Direction) -> &Mutex<Timer> {
+        match dir {
+            Direction::Recv => &self.recv,
+            Direction::Send => &self.send,
+        }
+    }
+
+    pub fn set(&self, dir: Direction, timeout: Option<Duration>) -> io::Result<()> {
+        check_nonzero(timeout)?;
+        let mut timer = lock(self.timer(dir));
+        timer.timeout = timeout;
+        timer.pending = None;
+        Ok(())
+    }
+    pub fn set_idle(&self, timeout: Option<Duration>) -> io::Result<()> {
+        check_nonzero(timeout)?;
+        let mut idle = lock(&self.idle);
+        idle.timeout = timeout;
+        idle.last_activity = Instant::now();
+        Ok(())
+    }
+
+    /// Counts a successful I/O operation as activity for the purposes of the idle timeout.
+    pub fn record<T>(&self, rslt: io::Result<T>) -> io::Result<T> {
+        if rslt.is_ok() {
+            let mut idle = lock(&self.idle);
+            if idle.timeout.is_some() {
+                idle.last_activity = Instant::now();
+            }
+        }
+        rslt
+    }
+
+    /// Polls `f`, failing with `TimedOut` if it has been returning `Pending` for longer than the
+    /// timeout of the given direction, or if the stream has been idle for longer than the idle
+    /// timeout.
+    pub fn poll<T>(
+        &self,
+        dir: Direction,
+        cx: &mut Context<'_>,
+        f: impl FnOnce(&mut Context<'_>) -> Poll<io::Result<T>>,
+    ) -> Poll<io::Result<T>> {
+        if let Poll::Ready(rslt) = f(cx) {
+            lock(self.timer(dir)).pending = None;
+            return Poll::Ready(self.record(rslt));
+        }
+
+        let idle_deadline = {
+            let idle = lock(&self.idle);
+            idle.timeout.and_then(|t| idle.last_activity.checked_add(t))
+        };
+        let mut timer = lock(self.timer(dir));
+        if timer.timeout.is_none() && idle_deadline.is_none() {
+            return Poll::Pending;
+        }
+        let now = Instant::now();
+        let timeout = timer.timeout;
+        let (started, sleep) =
+            timer.pending.get_or_insert_with(|| (now, Box::pin(sleep_until(now.into()))));
+        let own_deadline = timeout.and_then(|t| started.checked_add(t));
+        let Some(deadline) = own_deadline.into_iter().chain(idle_deadline).min() else {
+            return Poll::Pending;
+        };
+        let expired = if deadline <= now {
+            true
+        } else {
+            if sleep.deadline() != deadline.into() {
+                sleep.as_mut().reset(deadline.into());
+            }
+            sleep.as_mut().poll(cx).is_ready()
+        };
+        if expired {
+            timer.pending = None;
+            return Poll::Ready(Err(timed_out(dir)));
+        }
+        Poll::Pending
+    }
+}
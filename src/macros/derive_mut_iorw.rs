@@ -1,5 +1,5 @@
-//! Macros that derive `Read` and `Write` (and their Tokio counterparts) on all `T` that satisfy
-//! `for<'a> &'a T: Trait` for the corresponding trait.
+//! Macros that derive `Read` and `Write` (and their Tokio and futures-io counterparts) on all `T`
+//! that satisfy `for<'a> &'a T: Trait` for the corresponding trait.
 
 macro_rules! derive_sync_mut_read {
     ($({$($lt:tt)*})? $ty:ty) => {
@@ -118,3 +118,78 @@ macro_rules! derive_tokio_mut_rw {
         derive_tokio_mut_write!($({$($lt)*})? $ty);
     };
 }
+
+macro_rules! derive_futures_mut_read {
+    ($({$($lt:tt)*})? $ty:ty) => {
+        const _: () = {
+            use ::futures_io::AsyncRead;
+            use ::std::{io::{self, IoSliceMut}, pin::Pin, task::{Context, Poll}};
+            impl $(<$($lt)*>)? AsyncRead for $ty {
+                #[inline(always)]
+                fn poll_read(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                    buf: &mut [u8],
+                ) -> Poll<io::Result<usize>> {
+                    AsyncRead::poll_read(Pin::new(&mut &*self), cx, buf)
+                }
+                #[inline(always)]
+                fn poll_read_vectored(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                    bufs: &mut [IoSliceMut<'_>],
+                ) -> Poll<io::Result<usize>> {
+                    AsyncRead::poll_read_vectored(Pin::new(&mut &*self), cx, bufs)
+                }
+            }
+        };
+    };
+}
+
+macro_rules! derive_futures_mut_write {
+    ($({$($lt:tt)*})? $ty:ty) => {
+        const _: () = {
+            use ::futures_io::AsyncWrite;
+            use ::std::{io::{self, IoSlice}, pin::Pin, task::{Context, Poll}};
+            impl $(<$($lt)*>)? AsyncWrite for $ty {
+                #[inline(always)]
+                fn poll_write(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                    buf: &[u8],
+                ) -> Poll<io::Result<usize>> {
+                    AsyncWrite::poll_write(Pin::new(&mut &*self), cx, buf)
+                }
+                #[inline(always)]
+                fn poll_write_vectored(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                    bufs: &[IoSlice<'_>],
+                ) -> Poll<io::Result<usize>> {
+                    AsyncWrite::poll_write_vectored(Pin::new(&mut &*self), cx, bufs)
+                }
+                #[inline(always)]
+                fn poll_flush(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                ) -> Poll<io::Result<()>> {
+                    AsyncWrite::poll_flush(Pin::new(&mut &*self), cx)
+                }
+                #[inline(always)]
+                fn poll_close(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                ) -> Poll<io::Result<()>> {
+                    AsyncWrite::poll_close(Pin::new(&mut &*self), cx)
+                }
+            }
+        };
+    };
+}
+
+macro_rules! derive_futures_mut_rw {
+    ($({$($lt:tt)*})? $ty:ty) => {
+        derive_futures_mut_read!($({$($lt)*})? $ty);
+        derive_futures_mut_write!($({$($lt)*})? $ty);
+    };
+}
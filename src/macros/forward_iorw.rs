@@ -259,3 +259,78 @@ macro_rules! forward_tokio_ref_rw {
         forward_tokio_ref_write!($({$($lt)*})? $ty);
     };
 }
+
+macro_rules! forward_futures_ref_read {
+    ($({$($lt:tt)*})? $ty:ty) => {
+        const _: () = {
+            use ::futures_io::AsyncRead;
+            use ::std::{io::{self, IoSliceMut}, pin::Pin, task::{Context, Poll}};
+            impl $(<$($lt)*>)? AsyncRead for &$ty {
+                #[inline(always)]
+                fn poll_read(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                    buf: &mut [u8],
+                ) -> Poll<io::Result<usize>> {
+                    Pin::new(&mut (**self).refwd()).poll_read(cx, buf)
+                }
+                #[inline(always)]
+                fn poll_read_vectored(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                    bufs: &mut [IoSliceMut<'_>],
+                ) -> Poll<io::Result<usize>> {
+                    Pin::new(&mut (**self).refwd()).poll_read_vectored(cx, bufs)
+                }
+            }
+        };
+    };
+}
+
+macro_rules! forward_futures_ref_write {
+    ($({$($lt:tt)*})? $ty:ty) => {
+        const _: () = {
+            use ::futures_io::AsyncWrite;
+            use ::std::{io::{self, IoSlice}, pin::Pin, task::{Context, Poll}};
+            impl $(<$($lt)*>)? AsyncWrite for &$ty {
+                #[inline(always)]
+                fn poll_write(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                    buf: &[u8],
+                ) -> Poll<io::Result<usize>> {
+                    Pin::new(&mut (**self).refwd()).poll_write(cx, buf)
+                }
+                #[inline(always)]
+                fn poll_write_vectored(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                    bufs: &[IoSlice<'_>],
+                ) -> Poll<io::Result<usize>> {
+                    Pin::new(&mut (**self).refwd()).poll_write_vectored(cx, bufs)
+                }
+                #[inline(always)]
+                fn poll_flush(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                ) -> Poll<io::Result<()>> {
+                    Pin::new(&mut (**self).refwd()).poll_flush(cx)
+                }
+                #[inline(always)]
+                fn poll_close(
+                    self: Pin<&mut Self>,
+                    cx: &mut Context<'_>,
+                ) -> Poll<io::Result<()>> {
+                    Pin::new(&mut (**self).refwd()).poll_close(cx)
+                }
+            }
+        };
+    };
+}
+
+macro_rules! forward_futures_ref_rw {
+    ($({$($lt:tt)*})? $ty:ty) => {
+        forward_futures_ref_read!($({$($lt)*})? $ty);
+        forward_futures_ref_write!($({$($lt)*})? $ty);
+    };
+}
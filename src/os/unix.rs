@@ -11,15 +11,24 @@
 
 pub(crate) mod imports;
 
-mod c_wrappers;
+pub(crate) mod c_wrappers;
 mod fdops;
+#[cfg(feature = "mio")]
+mod mio_source;
+mod reclaim_guard;
+#[cfg(feature = "tokio")]
+pub(crate) mod tokio_conv;
 mod ud_addr;
 // Exported into child modules specifically, not this file.
 use fdops::*;
 
 pub mod fifo_file;
 pub mod local_socket;
+pub mod selector;
 pub mod uds_local_socket;
+#[cfg(all(target_os = "linux", feature = "io_uring"))]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(all(target_os = "linux", feature = "io_uring"))))]
+pub mod uring_local_socket;
 pub mod unnamed_pipe;
 
 mod unixprelude {
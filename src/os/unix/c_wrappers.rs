@@ -1,6 +1,9 @@
 use {
     super::unixprelude::*,
-    crate::{os::unix::ud_addr::TerminatedUdAddr, timeout_expiry},
+    crate::{
+        os::unix::ud_addr::{TerminatedUdAddr, UdAddr},
+        timeout_expiry,
+    },
     libc::AF_UNIX,
     std::{
         ffi::CStr,
@@ -64,7 +67,7 @@ fn set_flflags(fd: BorrowedFd<'_>, flags: c_int) -> io::Result<()> {
     unsafe { fcntl_int(fd, libc::F_SETFL, flags) }.map(drop)
 }
 
-pub(super) fn set_nonblocking(fd: BorrowedFd<'_>, nonblocking: bool) -> io::Result<()> {
+pub(crate) fn set_nonblocking(fd: BorrowedFd<'_>, nonblocking: bool) -> io::Result<()> {
     #[cfg(not(any(target_os = "linux", target_os = "android")))]
     {
         let old_flags = get_flflags(fd)? & !libc::O_NONBLOCK;
@@ -78,6 +81,10 @@ pub(super) fn set_nonblocking(fd: BorrowedFd<'_>, nonblocking: bool) -> io::Resu
     }
 }
 
+pub(crate) fn get_nonblocking(fd: BorrowedFd<'_>) -> io::Result<bool> {
+    Ok(get_flflags(fd)? & libc::O_NONBLOCK != 0)
+}
+
 /// Like [`set_nonblocking`], but assumes the file descriptor has not been exposed to anyone who
 /// could've used `fcntl(F_SETFL)` on it.
 pub(super) fn fast_set_nonblocking(fd: BorrowedFd<'_>, nonblocking: bool) -> io::Result<()> {
@@ -125,6 +132,28 @@ pub(super) fn duplicate_fd(fd: BorrowedFd<'_>) -> io::Result<OwnedFd> {
     }}
 }
 
+/// Opens a file with the given flags, always adding `O_CLOEXEC`.
+pub(super) fn open(path: &CStr, flags: c_int) -> io::Result<OwnedFd> {
+    let fd = unsafe { libc::open(path.as_ptr(), flags | libc::O_CLOEXEC) }.fd_or_errno()?;
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+pub(super) fn mkfifo(path: &CStr, mode: mode_t) -> io::Result<()> {
+    unsafe { libc::mkfifo(path.as_ptr(), mode) != -1 }.true_val_or_errno(())
+}
+
+/// Applies the given mode to the FIFO that was just created at the given path by `mkfifo()`,
+/// without following symlinks and failing if the FIFO has been replaced in the meantime. On failure,
+/// the FIFO is deleted unless something else has taken its place.
+pub(super) fn chmod_new_fifo(path: &CStr, mode: mode_t) -> io::Result<()> {
+    let id = own_file(path, libc::S_IFIFO)?;
+    let rslt = set_owner_and_mode(path, libc::S_IFIFO, id, None, None, Some(mode));
+    if rslt.is_err() && lstat(path).is_ok_and(|st| (st.st_dev, st.st_ino) == id) {
+        let _ = unlink(path);
+    }
+    rslt
+}
+
 pub(super) fn unlink(path: &CStr) -> io::Result<()> {
     unsafe { libc::unlink(path.as_ptr()) != -1 }.true_val_or_errno(())
 }
@@ -232,19 +261,152 @@ fn listen(fd: BorrowedFd<'_>) -> io::Result<()> {
     unsafe { libc::listen(fd.as_raw_fd(), BACKLOG) != -1 }.true_val_or_errno(())
 }
 
+/// Device and inode numbers of a file.
+type FileId = (libc::dev_t, libc::ino_t);
+
+fn lstat(path: &CStr) -> io::Result<libc::stat> {
+    let mut st = MaybeUninit::<libc::stat>::uninit();
+    unsafe {
+        libc::fstatat(libc::AT_FDCWD, path.as_ptr(), st.as_mut_ptr(), libc::AT_SYMLINK_NOFOLLOW)
+            != -1
+    }
+    .true_val_or_errno(())?;
+    Ok(unsafe { st.assume_init() })
+}
+
+fn file_replaced(ty: mode_t) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        if ty == libc::S_IFIFO {
+            "FIFO file was replaced before its mode could be changed"
+        } else {
+            "socket file was replaced before its owner could be changed"
+        },
+    )
+}
+
+/// Identifies the file at the given path without following symlinks, failing if it is not of the
+/// given type (`S_IFSOCK` or `S_IFIFO`) and owned by the effective user ID of the process, which is
+/// the case if it got replaced after `bind()` or `mkfifo()` by someone with write access to its
+/// directory.
+fn own_file(path: &CStr, ty: mode_t) -> io::Result<FileId> {
+    let st = lstat(path)?;
+    let euid = unsafe { libc::geteuid() };
+    if st.st_mode & libc::S_IFMT != ty || st.st_uid != euid {
+        return Err(file_replaced(ty));
+    }
+    Ok((st.st_dev, st.st_ino))
+}
+
+/// Changes the owner and group of the file of type `ty` identified by `id` at the given path,
+/// leaving the ones that are `None` unchanged, and then applies the given mode to it.
+///
+/// The file is opened with `O_PATH`, checked to still be the same file, and then modified via the
+/// resulting file descriptor, so that neither a symlink nor a different file that took its place
+/// in the meantime is modified.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn set_owner_and_mode(
+    path: &CStr,
+    ty: mode_t,
+    id: FileId,
+    owner: Option<uid_t>,
+    group: Option<gid_t>,
+    mode: Option<mode_t>,
+) -> io::Result<()> {
+    let file =
+        unsafe { libc::open(path.as_ptr(), libc::O_PATH | libc::O_NOFOLLOW | libc::O_CLOEXEC) }
+            .fd_or_errno()
+            .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) })?;
+    let mut st = MaybeUninit::<libc::stat>::uninit();
+    unsafe { libc::fstat(file.as_raw_fd(), st.as_mut_ptr()) != -1 }.true_val_or_errno(())?;
+    let st = unsafe { st.assume_init() };
+    if (st.st_dev, st.st_ino) != id {
+        return Err(file_replaced(ty));
+    }
+
+    if owner.is_some() || group.is_some() {
+        // -1 stands for "unchanged"
+        let (owner, group) = (owner.unwrap_or(uid_t::MAX), group.unwrap_or(gid_t::MAX));
+        let empty = b"\0".as_ptr().cast();
+        unsafe {
+            libc::fchownat(file.as_raw_fd(), empty, owner, group, libc::AT_EMPTY_PATH) != -1
+        }
+        .true_val_or_errno(())?;
+    }
+    if let Some(mode) = mode {
+        // fchmod() does not work on O_PATH file descriptors, but the magic link in /proc/self/fd
+        // refers to the file the descriptor is open on rather than to whatever is at its path.
+        let proc_path = std::ffi::CString::new(format!("/proc/self/fd/{}", file.as_raw_fd()))
+            .map_err(io::Error::other)?;
+        unsafe { libc::chmod(proc_path.as_ptr(), mode) != -1 }.true_val_or_errno(())?;
+    }
+    Ok(())
+}
+/// Changes the owner and group of the file of type `ty` identified by `id` at the given path,
+/// leaving the ones that are `None` unchanged, and then applies the given mode to it.
+///
+/// Symlinks are not followed. There is no way to open a socket file on this platform, nor to open a
+/// FIFO without side effects for processes waiting on it, and thus the check that the file at the
+/// path is still the one identified by `id` cannot be performed atomically with the modification.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn set_owner_and_mode(
+    path: &CStr,
+    ty: mode_t,
+    id: FileId,
+    owner: Option<uid_t>,
+    group: Option<gid_t>,
+    mode: Option<mode_t>,
+) -> io::Result<()> {
+    if own_file(path, ty)? != id {
+        return Err(file_replaced(ty));
+    }
+    let nofollow = libc::AT_SYMLINK_NOFOLLOW;
+    if owner.is_some() || group.is_some() {
+        // -1 stands for "unchanged"
+        let (owner, group) = (owner.unwrap_or(uid_t::MAX), group.unwrap_or(gid_t::MAX));
+        unsafe { libc::fchownat(libc::AT_FDCWD, path.as_ptr(), owner, group, nofollow) != -1 }
+            .true_val_or_errno(())?;
+    }
+    if let Some(mode) = mode {
+        unsafe { libc::fchmodat(libc::AT_FDCWD, path.as_ptr(), mode, nofollow) != -1 }
+            .true_val_or_errno(())?;
+    }
+    Ok(())
+}
+
 pub(super) fn create_listener(
     ty: c_int,
     addr: TerminatedUdAddr<'_>,
     nonblocking: bool,
     mode: Option<mode_t>,
+    owner: Option<uid_t>,
+    group: Option<gid_t>,
 ) -> io::Result<OwnedFd> {
+    let chown = owner.is_some() || group.is_some();
+    if chown && addr.path().is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "owner and group can only be set for sockets with filesystem paths",
+        ));
+    }
     let sock = create_socket(ty, nonblocking)?;
     if let Some(mode) = mode {
         // This used to forbid modes with the executable bit set, but no longer does. That is the
-        // OS's business, not ours.
-        set_socket_mode(sock.as_fd(), mode)?;
+        // OS's business, not ours. If the ownership is to be changed, the socket must not become
+        // accessible to the old group and others in the meantime.
+        set_socket_mode(sock.as_fd(), if chown { mode & 0o700 } else { mode })?;
     }
     bind(sock.as_fd(), addr)?;
+    if chown {
+        // If the socket file is no longer ours, it's not ours to delete either.
+        let id = own_file(addr.path(), libc::S_IFSOCK)?;
+        if let Err(e) = set_owner_and_mode(addr.path(), libc::S_IFSOCK, id, owner, group, mode) {
+            if lstat(addr.path()).is_ok_and(|st| (st.st_dev, st.st_ino) == id) {
+                let _ = unlink(addr.path());
+            }
+            return Err(e);
+        }
+    }
     listen(sock.as_fd())?;
     if !CAN_CREATE_NONBLOCKING && nonblocking {
         set_nonblocking(sock.as_fd(), true)?;
@@ -278,6 +440,26 @@ pub(super) fn create_client(
     Ok((sock, inprog))
 }
 
+type GetNameFn = unsafe extern "C" fn(c_int, *mut libc::sockaddr, *mut socklen_t) -> c_int;
+fn get_addr(fd: BorrowedFd<'_>, f: GetNameFn) -> io::Result<UdAddr> {
+    let mut addr = UdAddr::new();
+    #[allow(clippy::cast_possible_truncation)]
+    let mut addrlen = size_of::<libc::sockaddr_un>() as socklen_t;
+    unsafe { f(fd.as_raw_fd(), addr.addr_ptr_mut().cast(), &mut addrlen) != -1 }
+        .true_val_or_errno(())?;
+    // SAFETY: the system call has just filled it in
+    unsafe { addr.set_addrlen(addrlen) };
+    Ok(addr)
+}
+/// Retrieves the address a socket is bound to.
+pub(super) fn getsockname(fd: BorrowedFd<'_>) -> io::Result<UdAddr> {
+    get_addr(fd, libc::getsockname)
+}
+/// Retrieves the address the peer of a connected socket is bound to.
+pub(super) fn getpeername(fd: BorrowedFd<'_>) -> io::Result<UdAddr> {
+    get_addr(fd, libc::getpeername)
+}
+
 pub(super) fn take_error(fd: BorrowedFd<'_>) -> io::Result<Option<io::Error>> {
     let errno = unsafe { getsockopt(fd, libc::SOL_SOCKET, libc::SO_ERROR)? };
     Ok((errno != 0).then(|| io::Error::from_raw_os_error(errno)))
@@ -325,11 +507,19 @@ pub(super) fn poll_loop(
     }
 }
 
-pub(super) fn poll(
+pub(crate) fn poll(
     fd: BorrowedFd<'_>,
     events: c_short,
     timeout: Option<Duration>,
 ) -> io::Result<c_short> {
+    let mut fds = [libc::pollfd { fd: fd.as_raw_fd(), events, revents: 0 }];
+    poll_fds(&mut fds, timeout)?;
+    Ok(fds[0].revents)
+}
+
+/// Like [`poll`], but for multiple file descriptors, whose `revents` are filled in. Interruption
+/// by a signal is treated as a timeout, leaving all `revents` at zero.
+pub(crate) fn poll_fds(fds: &mut [libc::pollfd], timeout: Option<Duration>) -> io::Result<()> {
     // NetBSD pollts is identical to ppoll, but named differently for historical
     // reasons. Recent NetBSD versions provide an alias named ppoll to ease
     // porting of Linux programs, but since I bothered to look at the source
@@ -358,7 +548,9 @@ pub(super) fn poll(
     ))]
     use libc::ppoll;
 
-    let mut fd = libc::pollfd { fd: fd.as_raw_fd(), events, revents: 0 };
+    fds.iter_mut().for_each(|fd| fd.revents = 0);
+    #[allow(clippy::cast_possible_truncation)]
+    let nfds = fds.len() as libc::nfds_t;
 
     #[cfg(any(
         target_os = "linux",
@@ -371,8 +563,8 @@ pub(super) fn poll(
     let scret = unsafe {
         let timeout = timeout.map(duration_to_timespec).transpose()?;
         ppoll(
-            &mut fd,
-            1,
+            fds.as_mut_ptr(),
+            nfds,
             timeout.as_ref().map(crate::ref2ptr).unwrap_or(ptr::null()),
             ptr::null(),
         )
@@ -389,12 +581,13 @@ pub(super) fn poll(
     let scret = unsafe {
         let timeout =
             timeout.map(|t| c_int::try_from(t.as_millis()).unwrap_or(c_int::MAX)).unwrap_or(-1);
-        libc::poll(&mut fd, 1, timeout)
+        libc::poll(fds.as_mut_ptr(), nfds, timeout)
     };
 
-    let ret = (scret >= 0).true_val_or_errno(fd.revents);
+    let ret = (scret >= 0).true_val_or_errno(());
     if ret.as_ref().err().and_then(io::Error::raw_os_error) == Some(libc::EINTR) {
-        return Ok(0);
+        fds.iter_mut().for_each(|fd| fd.revents = 0);
+        return Ok(());
     }
     ret
 }
@@ -21,13 +21,29 @@
 //! standard [`File`](std::fs::File)s, opened either only for sending or only for receiving.
 //! Deletion works the same way as with any regular file, via
 //! [`remove_file()`](std::fs::remove_file).
+//!
+//! Alternatively, [`FifoOptions`] can create and open FIFO files in one go, bound the time spent
+//! waiting for the other end, and delete the FIFO file once the resulting [`Recver`] or [`Sender`]
+//! is dropped.
+//!
+//! With the `tokio` feature enabled, the `tokio` submodule provides a sender and a receiver that
+//! open FIFO files without blocking the runtime and implement Tokio's asynchronous I/O traits.
 
+mod options;
+pub use options::*;
 use {
-    super::unixprelude::*,
-    crate::OrErrno,
-    std::{ffi::CString, io, path::Path},
+    super::{c_wrappers, reclaim_guard::ReclaimGuard, unixprelude::*, FdOps},
+    std::{
+        ffi::CString,
+        io::{self, prelude::*, IoSlice},
+        path::Path,
+    },
 };
 
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
+pub mod tokio;
+
 /// Creates a FIFO file at the specified path with the specified permissions.
 ///
 /// Since the `mode` parameter is masked with the [`umask`], it's best to leave it at `0o777` unless
@@ -43,6 +59,89 @@ pub fn create_fifo<P: AsRef<Path>>(path: P, mode: mode_t) -> io::Result<()> {
 }
 fn _create_fifo(path: &Path, mode: mode_t) -> io::Result<()> {
     let path = CString::new(path.as_os_str().as_bytes())?;
-    unsafe { libc::mkfifo(path.as_bytes_with_nul().as_ptr().cast(), mode) != -1 }
-        .true_val_or_errno(())
+    c_wrappers::mkfifo(&path, mode)
+}
+
+/// Handle to the receiving end of a FIFO file, opened via [`FifoOptions`].
+///
+/// The core functionality is exposed via the [`Read`] trait.
+#[derive(Debug)]
+pub struct Recver {
+    fd: FdOps,
+    dummy: Option<OwnedFd>,
+    reclaim: ReclaimGuard,
+}
+impl Recver {
+    /// Disarms the deletion of the FIFO file on drop, if it was
+    /// [enabled](FifoOptions::unlink_on_drop).
+    #[inline]
+    pub fn do_not_unlink_on_drop(&mut self) { self.reclaim.forget(); }
+    #[inline(always)]
+    fn refwd(&self) -> &FdOps { &self.fd }
+}
+multimacro! {
+    Recver,
+    forward_sync_ref_read,
+    derive_sync_mut_read,
+}
+impl AsFd for Recver {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> { self.fd.as_fd() }
+}
+/// Disarms the deletion of the FIFO file on drop and closes the dummy writer, if any.
+impl From<Recver> for OwnedFd {
+    #[inline]
+    fn from(mut rx: Recver) -> Self {
+        rx.reclaim.forget();
+        rx.fd.0
+    }
+}
+impl From<OwnedFd> for Recver {
+    #[inline]
+    fn from(fd: OwnedFd) -> Self {
+        Self { fd: FdOps(fd), dummy: None, reclaim: ReclaimGuard::default() }
+    }
+}
+
+/// Handle to the sending end of a FIFO file, opened via [`FifoOptions`].
+///
+/// The core functionality is exposed via the [`Write`] trait.
+#[derive(Debug)]
+pub struct Sender {
+    fd: FdOps,
+    reclaim: ReclaimGuard,
+}
+impl Sender {
+    /// Disarms the deletion of the FIFO file on drop, if it was
+    /// [enabled](FifoOptions::unlink_on_drop).
+    #[inline]
+    pub fn do_not_unlink_on_drop(&mut self) { self.reclaim.forget(); }
+}
+impl Write for &Sender {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> { (&self.fd).write(buf) }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&self.fd).write_vectored(bufs)
+    }
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    // FUTURE is_write_vectored
+}
+derive_sync_mut_write!(Sender);
+impl AsFd for Sender {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> { self.fd.as_fd() }
+}
+/// Disarms the deletion of the FIFO file on drop.
+impl From<Sender> for OwnedFd {
+    #[inline]
+    fn from(mut tx: Sender) -> Self {
+        tx.reclaim.forget();
+        tx.fd.0
+    }
+}
+impl From<OwnedFd> for Sender {
+    #[inline]
+    fn from(fd: OwnedFd) -> Self { Self { fd: FdOps(fd), reclaim: ReclaimGuard::default() } }
 }
@@ -0,0 +1,248 @@
+use {
+    super::{Recver, Sender},
+    crate::{
+        os::unix::{c_wrappers, reclaim_guard::ReclaimGuard, unixprelude::*, FdOps},
+        timeout_expiry,
+    },
+    std::{
+        ffi::{CStr, CString},
+        io,
+        path::Path,
+        sync::mpsc::{self, RecvTimeoutError},
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+const TIMEOUT_MSG: &str = "timed out while waiting for the other end of the FIFO file to open it";
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Builder for opening (and optionally creating) [FIFO files](super).
+///
+/// Unlike opening a FIFO file via [`File`](std::fs::File), which blocks for as long as it takes
+/// for the other end to be opened, this builder allows the wait to be
+/// [bounded](Self::open_timeout).
+///
+/// # Examples
+/// ```no_run
+/// use {interprocess::os::unix::fifo_file::FifoOptions, std::{io::prelude::*, time::Duration}};
+///
+/// let mut rx = FifoOptions::new()
+///     .create(true)
+///     .mode(0o600)
+///     .unlink_on_drop(true)
+///     .open_timeout(Duration::from_secs(5))
+///     .open_recver("/tmp/example.fifo")?;
+/// let mut buf = String::new();
+/// rx.read_to_string(&mut buf)?;
+/// # std::io::Result::<()>::Ok(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FifoOptions {
+    create: bool,
+    mode: Option<mode_t>,
+    open_timeout: Option<Duration>,
+    dummy_writer: bool,
+    unlink_on_drop: bool,
+}
+
+/// Creation.
+impl FifoOptions {
+    /// Returns a default set of FIFO file options.
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+}
+
+/// Option setters.
+impl FifoOptions {
+    builder_setters! {
+        /// Sets whether the FIFO file is to be created if it does not exist.
+        ///
+        /// This is disabled by default.
+        create: bool,
+        /// Sets the permissions the FIFO file is to be created with.
+        ///
+        /// Unlike the `mode` parameter of [`create_fifo()`](super::create_fifo), this is not
+        /// subject to the [`umask`](https://en.wikipedia.org/wiki/Umask): the permissions are
+        /// explicitly set to exactly this value after creation. If not set, `0o666` masked with
+        /// the `umask` is used. This has no effect if the file already exists.
+        ///
+        /// Symlinks are not followed when setting the permissions. If the FIFO file gets replaced
+        /// by something else between its creation and that point, opening fails with
+        /// [`PermissionDenied`](io::ErrorKind::PermissionDenied).
+        mode: mode_t,
+        /// Sets the maximum amount of time opening the FIFO file may wait for the other end to be
+        /// opened. Once it elapses, opening fails with [`TimedOut`](io::ErrorKind::TimedOut).
+        ///
+        /// By default, the wait is unbounded.
+        ///
+        /// ## Implementation details
+        /// Senders wait by repeatedly attempting a nonblocking open with an increasing delay
+        /// between attempts. Receivers perform a regular blocking open in a helper thread, which,
+        /// on timeout, is unblocked by briefly opening the FIFO file for sending.
+        open_timeout: Duration,
+        /// Sets whether receivers are to keep a dummy sending handle to the FIFO file open for
+        /// their whole lifetime.
+        ///
+        /// This is the portable equivalent of opening a FIFO file with `O_RDWR`: the receiver
+        /// never observes end of file, even when no writers are present or when they come and go,
+        /// and opening it never waits for a writer to appear. Reads block until data is available
+        /// instead.
+        ///
+        /// This has no effect on senders. It is disabled by default.
+        dummy_writer: bool,
+        /// Sets whether the FIFO file is to be deleted when the resulting handle is dropped. If the
+        /// FIFO file was [created](Self::create) but could not be opened, it is deleted right away.
+        ///
+        /// This is disabled by default.
+        unlink_on_drop: bool,
+    }
+}
+
+/// Opening.
+impl FifoOptions {
+    /// Opens the FIFO file at the given path for receiving.
+    ///
+    /// ## System calls
+    /// - `mkfifo` (if [creating](Self::create))
+    /// - `fstatat`, then `open` with `O_PATH`, `fstat` and `chmod` on Linux or `fchmodat`
+    ///   elsewhere (if creating and a [mode](Self::mode) is set)
+    /// - `open`
+    /// - `fcntl` (if a [dummy writer](Self::dummy_writer) is used)
+    pub fn open_recver<P: AsRef<Path>>(&self, path: P) -> io::Result<Recver> {
+        self._open_recver(path.as_ref())
+    }
+    fn _open_recver(&self, path: &Path) -> io::Result<Recver> {
+        let (path, creation_guard) = self.prepare(path)?;
+        if self.dummy_writer {
+            // Opening for receiving in nonblocking mode never waits, and opening for sending can
+            // then never fail with ENXIO since there is a reader.
+            let fd = c_wrappers::open(&path, libc::O_RDONLY | libc::O_NONBLOCK)?;
+            let dummy = c_wrappers::open(&path, libc::O_WRONLY | libc::O_NONBLOCK)?;
+            c_wrappers::set_nonblocking(fd.as_fd(), false)?;
+            let reclaim = self.reclaim_guard(path, creation_guard);
+            return Ok(Recver { fd: FdOps(fd), dummy: Some(dummy), reclaim });
+        }
+        let fd = match self.open_timeout {
+            Some(timeout) => open_recver_with_timeout(&path, timeout)?,
+            None => c_wrappers::open(&path, libc::O_RDONLY)?,
+        };
+        let reclaim = self.reclaim_guard(path, creation_guard);
+        Ok(Recver { fd: FdOps(fd), dummy: None, reclaim })
+    }
+
+    /// Opens the FIFO file at the given path for sending.
+    ///
+    /// ## System calls
+    /// - `mkfifo` (if [creating](Self::create))
+    /// - `fstatat`, then `open` with `O_PATH`, `fstat` and `chmod` on Linux or `fchmodat`
+    ///   elsewhere (if creating and a [mode](Self::mode) is set)
+    /// - `open` (repeatedly if there is an [open timeout](Self::open_timeout))
+    /// - `fcntl` (if there is an open timeout)
+    pub fn open_sender<P: AsRef<Path>>(&self, path: P) -> io::Result<Sender> {
+        self._open_sender(path.as_ref())
+    }
+    fn _open_sender(&self, path: &Path) -> io::Result<Sender> {
+        let (path, creation_guard) = self.prepare(path)?;
+        let fd = match self.open_timeout {
+            Some(timeout) => open_sender_with_timeout(&path, timeout)?,
+            None => c_wrappers::open(&path, libc::O_WRONLY)?,
+        };
+        let reclaim = self.reclaim_guard(path, creation_guard);
+        Ok(Sender { fd: FdOps(fd), reclaim })
+    }
+
+    /// Creates the FIFO file if necessary. The returned reclaim guard deletes it if it was created
+    /// here and opening subsequently fails.
+    fn prepare(&self, path: &Path) -> io::Result<(CString, ReclaimGuard)> {
+        let path = CString::new(path.as_os_str().as_bytes())?;
+        let created = self.create && create_if_missing(&path, self.mode)?;
+        let mut creation_guard =
+            ReclaimGuard::with_path(self.unlink_on_drop && created, path.clone());
+        if let (true, Some(mode)) = (created, self.mode) {
+            // If the FIFO file is no longer ours, it's not ours to delete either, and if it is,
+            // this takes care of deleting it.
+            if let Err(e) = c_wrappers::chmod_new_fifo(&path, mode) {
+                creation_guard.forget();
+                return Err(e);
+            }
+        }
+        Ok((path, creation_guard))
+    }
+    /// Replaces the guard from `prepare` with one that deletes the FIFO file regardless of who
+    /// created it, now that it has been opened.
+    fn reclaim_guard(&self, path: CString, mut creation_guard: ReclaimGuard) -> ReclaimGuard {
+        creation_guard.forget();
+        ReclaimGuard::with_path(self.unlink_on_drop, path)
+    }
+}
+
+/// Returns `true` if the FIFO file was created and `false` if it already existed.
+fn create_if_missing(path: &CStr, mode: Option<mode_t>) -> io::Result<bool> {
+    // The umask can only remove permissions, so the file never ends up being more accessible than
+    // requested between mkfifo and chmod.
+    match c_wrappers::mkfifo(path, mode.unwrap_or(0o666)) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn timed_out() -> io::Error { io::Error::new(io::ErrorKind::TimedOut, TIMEOUT_MSG) }
+
+fn open_sender_with_timeout(path: &CStr, timeout: Duration) -> io::Result<OwnedFd> {
+    let end = timeout_expiry(timeout)?;
+    let mut delay = MIN_RETRY_DELAY;
+    loop {
+        match c_wrappers::open(path, libc::O_WRONLY | libc::O_NONBLOCK) {
+            Ok(fd) => {
+                c_wrappers::set_nonblocking(fd.as_fd(), false)?;
+                return Ok(fd);
+            }
+            Err(e) if e.raw_os_error() == Some(libc::ENXIO) => {
+                let remaining = end.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(timed_out());
+                }
+                thread::sleep(delay.min(remaining));
+                delay = delay.saturating_mul(2).min(MAX_RETRY_DELAY);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn open_recver_with_timeout(path: &CStr, timeout: Duration) -> io::Result<OwnedFd> {
+    let (tx, rx) = mpsc::sync_channel(1);
+    let thread_path = path.to_owned();
+    thread::Builder::new().name("FIFO file opener".to_owned()).spawn(move || {
+        let _ = tx.send(c_wrappers::open(&thread_path, libc::O_RDONLY));
+    })?;
+    match rx.recv_timeout(timeout) {
+        Ok(rslt) => return rslt,
+        Err(RecvTimeoutError::Timeout) => {}
+        Err(RecvTimeoutError::Disconnected) => {
+            return Err(io::Error::other("FIFO file opener thread panicked"))
+        }
+    }
+    // Unblock the helper thread by posing as a writer, retrying for as long as the thread has not
+    // reached the point of being counted as a reader yet.
+    loop {
+        match c_wrappers::open(path, libc::O_WRONLY | libc::O_NONBLOCK) {
+            Ok(unblocker) => {
+                let _ = rx.recv();
+                drop(unblocker);
+                break;
+            }
+            Err(e) if e.raw_os_error() == Some(libc::ENXIO) => {
+                if !matches!(rx.recv_timeout(MIN_RETRY_DELAY), Err(RecvTimeoutError::Timeout)) {
+                    break;
+                }
+            }
+            // The FIFO file has likely been deleted. Leave the thread to finish on its own.
+            Err(..) => break,
+        }
+    }
+    Err(timed_out())
+}
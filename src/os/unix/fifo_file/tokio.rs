@@ -0,0 +1,208 @@
+//! Tokio-based asynchronous FIFO files.
+//!
+//! Opening a FIFO file the usual way blocks until the other side also opens it, which is
+//! unacceptable in asynchronous code. The types in this module open FIFO files in nonblocking mode
+//! instead and wait for the peer using the Tokio reactor where possible:
+//! - [`Recver::open()`] returns immediately; the first read waits for a writer to appear.
+//! - [`Sender::open()`] waits for a reader to appear. FIFO files cannot signal that a reader has
+//!   opened them, so this is done by periodically retrying the open with an increasing delay.
+//!   [`Sender::try_open()`] does a single attempt.
+//!
+//! Both types must be created within the context of a Tokio runtime with I/O enabled.
+
+use {
+    crate::os::unix::{c_wrappers, unixprelude::*, FdOps},
+    std::{
+        ffi::CString,
+        io,
+        path::Path,
+        pin::Pin,
+        task::{ready, Context, Poll},
+        time::Duration,
+    },
+    tokio::io::{unix::AsyncFd, AsyncRead, AsyncWrite, Interest, ReadBuf, Ready},
+};
+
+fn open_nonblocking(path: &Path, flags: c_int) -> io::Result<OwnedFd> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    c_wrappers::open(&path, flags | libc::O_NONBLOCK)
+}
+
+/// Tokio-based handle to the receiving end of a FIFO file.
+///
+/// The core functionality is exposed via the [`AsyncRead`] trait.
+///
+/// ## Platform-specific behavior
+/// ### Linux
+/// Reading before any writer has opened the FIFO file waits for one to appear. Once all writers
+/// have closed the file, end of file is reported.
+///
+/// ### Other Unix systems
+/// Some systems report end of file instead of waiting if no writer has opened the FIFO file yet.
+/// Open the receiver after the sender if this is undesirable.
+#[derive(Debug)]
+pub struct Recver(AsyncFd<FdOps>);
+impl Recver {
+    /// Opens the FIFO file at the given path for receiving.
+    ///
+    /// This never waits for a writer to open the file.
+    ///
+    /// ## System calls
+    /// - `open`
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_nb(open_nonblocking(path.as_ref(), libc::O_RDONLY)?)
+    }
+    fn from_nb(fd: OwnedFd) -> io::Result<Self> {
+        Ok(Self(AsyncFd::with_interest(FdOps(fd), Interest::READABLE)?))
+    }
+}
+
+impl AsyncRead for Recver {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        let slf = self.get_mut();
+        loop {
+            let fd = slf.0.get_ref().as_raw_fd();
+            let mut readiness = ready!(slf.0.poll_read_ready_mut(cx))?;
+            unsafe {
+                // SAFETY(unfilled_mut): we are not de-initializing anything
+                // SAFETY(borrow_raw): we're getting it from an OwnedFd that we don't drop
+                match FdOps::read(BorrowedFd::borrow_raw(fd), buf.unfilled_mut()) {
+                    // A FIFO file with no writers reads as end of file, even if no writer has
+                    // ever opened it. Only a hangup means that the writers are actually gone.
+                    Ok(0) if !readiness.ready().is_read_closed() => {
+                        readiness.clear_ready_matching(Ready::READABLE);
+                    }
+                    Ok(bytes_read) => {
+                        buf.assume_init(bytes_read);
+                        buf.advance(bytes_read);
+                        break Poll::Ready(Ok(()));
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        readiness.clear_ready_matching(Ready::READABLE);
+                    }
+                    Err(e) => break Poll::Ready(Err(e)),
+                }
+            }
+        }
+    }
+}
+
+impl TryFrom<OwnedFd> for Recver {
+    type Error = io::Error;
+    fn try_from(fd: OwnedFd) -> io::Result<Self> {
+        c_wrappers::set_nonblocking(fd.as_fd(), true)?;
+        Self::from_nb(fd)
+    }
+}
+impl From<Recver> for OwnedFd {
+    #[inline]
+    fn from(rx: Recver) -> Self { rx.0.into_inner().0 }
+}
+forward_as_handle!(Recver, unix);
+
+/// Tokio-based handle to the sending end of a FIFO file.
+///
+/// The core functionality is exposed via the [`AsyncWrite`] trait.
+#[derive(Debug)]
+pub struct Sender(AsyncFd<FdOps>);
+impl Sender {
+    const MIN_RETRY_DELAY: Duration = Duration::from_millis(1);
+    const MAX_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+    /// Opens the FIFO file at the given path for sending, waiting for a reader to open it if there
+    /// is none.
+    ///
+    /// Since the arrival of a reader cannot be waited for directly, this retries opening the file
+    /// with a delay that starts at 1 millisecond and doubles with every attempt up to 100
+    /// milliseconds. Errors other than the absence of a reader are returned immediately.
+    ///
+    /// ## System calls
+    /// - `open`, repeatedly
+    pub async fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut delay = Self::MIN_RETRY_DELAY;
+        loop {
+            match Self::try_open(path) {
+                Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+                    tokio::time::sleep(delay).await;
+                    delay = delay.saturating_mul(2).min(Self::MAX_RETRY_DELAY);
+                }
+                els => return els,
+            }
+        }
+    }
+    /// Opens the FIFO file at the given path for sending if it has a reader.
+    ///
+    /// If there is no reader, fails with [`ConnectionRefused`](io::ErrorKind::ConnectionRefused)
+    /// instead of waiting for one to appear.
+    ///
+    /// ## System calls
+    /// - `open`
+    pub fn try_open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let fd = match open_nonblocking(path.as_ref(), libc::O_WRONLY) {
+            Err(e) if e.raw_os_error() == Some(libc::ENXIO) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionRefused,
+                    "FIFO file has no reader",
+                ))
+            }
+            els => els?,
+        };
+        Self::from_nb(fd)
+    }
+    fn from_nb(fd: OwnedFd) -> io::Result<Self> {
+        Ok(Self(AsyncFd::with_interest(FdOps(fd), Interest::WRITABLE)?))
+    }
+}
+
+impl AsyncWrite for Sender {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let slf = self.get_mut();
+        loop {
+            let fd = slf.0.get_ref().as_raw_fd();
+            let mut readiness = ready!(slf.0.poll_write_ready_mut(cx))?;
+            unsafe {
+                // SAFETY(borrow_raw): we're getting it from an OwnedFd that we don't drop
+                match FdOps::write(BorrowedFd::borrow_raw(fd), buf) {
+                    Ok(bytes_written) => break Poll::Ready(Ok(bytes_written)),
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        readiness.clear_ready_matching(Ready::WRITABLE);
+                    }
+                    Err(e) => break Poll::Ready(Err(e)),
+                }
+            }
+        }
+    }
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl TryFrom<OwnedFd> for Sender {
+    type Error = io::Error;
+    fn try_from(fd: OwnedFd) -> io::Result<Self> {
+        c_wrappers::set_nonblocking(fd.as_fd(), true)?;
+        Self::from_nb(fd)
+    }
+}
+impl From<Sender> for OwnedFd {
+    #[inline]
+    fn from(tx: Sender) -> Self { tx.0.into_inner().0 }
+}
+forward_as_handle!(Sender, unix);
@@ -1,13 +1,30 @@
 //! Unix-specific local socket features.
 
+#[cfg(feature = "async")]
+pub(crate) mod dispatch_async_io;
 pub(crate) mod dispatch_sync;
 #[cfg(feature = "tokio")]
 pub(crate) mod dispatch_tokio;
 pub(crate) mod name_type;
 pub(crate) mod peer_creds;
+pub(crate) mod runtime_dir;
 
-use crate::{local_socket::ListenerOptions, Sealed};
-pub use name_type::*;
+use {
+    crate::{
+        local_socket::{traits, ConnectOptions, ListenerOptions, Name, NameInner},
+        timeout_expiry, Sealed,
+    },
+    std::{
+        io,
+        iter::FusedIterator,
+        path::Path,
+        time::{Duration, Instant},
+    },
+};
+pub use {
+    name_type::*,
+    runtime_dir::{RuntimeDir, RuntimeDirSource},
+};
 
 /// Unix-specific [listener options](ListenerOptions).
 #[allow(private_bounds)]
@@ -32,6 +49,103 @@ pub trait ListenerOptionsExt: Sized + Sealed {
     /// of a racy fallback, but it did not receive adoption, and was removed in 2.3.0.
     #[must_use = builder_must_use!()]
     fn mode(self, mode: libc::mode_t) -> Self;
+
+    /// Sets whether the listener is to be bound to a unique name in the Linux abstract namespace
+    /// picked by the kernel, in which case the [name](ListenerOptions::name) is ignored.
+    ///
+    /// The assigned name can be retrieved via
+    /// [`.local_name()`](crate::local_socket::traits::Listener::local_name) and passed to clients
+    /// by some other means, such as an environment variable of a child process.
+    /// [Name reclamation](crate::local_socket::Listener#name-reclamation) and
+    /// [`try_overwrite`](ListenerOptions::try_overwrite) have no effect, as abstract namespace
+    /// names are released automatically and never collide with existing ones.
+    ///
+    /// This is disabled by default.
+    ///
+    /// ## System calls
+    /// - `bind` with an address consisting of only the address family
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    #[must_use = builder_must_use!()]
+    fn autobind(self, autobind: bool) -> Self;
+
+    /// Sets the mode (Unix permissions) of directories that listener creation creates when the
+    /// directory the socket is to reside in does not exist. This currently only happens for
+    /// names produced by `SpecialDirUdSocket`.
+    ///
+    /// By default, such directories are created with mode `0o777` with the umask applied to it.
+    /// The mode set here is instead applied exactly, without the umask taking effect. Directories
+    /// that already exist are left as they are.
+    ///
+    /// ## System calls
+    /// - `mkdir`
+    /// - `chmod`
+    #[must_use = builder_must_use!()]
+    fn dir_mode(self, mode: libc::mode_t) -> Self;
+
+    /// Sets whether the directory the socket is to be created in is to be checked to be owned by
+    /// the effective user ID of the process or by root and to not be writable by its group or by
+    /// other users before the socket is bound.
+    ///
+    /// If the check fails, listener creation fails with
+    /// [`PermissionDenied`](std::io::ErrorKind::PermissionDenied). This prevents other users from
+    /// replacing the socket with their own after the listener is created or intercepting its
+    /// name before, which they could do in directories they can write to. Note that this rules
+    /// out shared directories such as `/tmp`. Names in the Linux abstract namespace are not
+    /// subject to the check. For [loopback TCP](crate::local_socket::LoopbackTcp) names, the
+    /// directory of the port file is checked.
+    ///
+    /// This is disabled by default.
+    ///
+    /// ## System calls
+    /// - `stat`
+    #[must_use = builder_must_use!()]
+    fn verify_parent_dir(self, verify: bool) -> Self;
+
+    /// Sets the user ID to be made the owner of the socket file.
+    ///
+    /// Changing the owner to a user other than the current one generally requires the process to
+    /// be privileged. Failure to apply the owner fails listener creation, with the socket file
+    /// removed. Only filesystem paths have an owner; for names in the Linux abstract namespace and
+    /// for [autobind](Self::autobind), listener creation fails with
+    /// [`Unsupported`](std::io::ErrorKind::Unsupported). For
+    /// [loopback TCP](crate::local_socket::LoopbackTcp) names, the owner is applied to the port
+    /// file before it is put in place, and the rest of this section does not apply.
+    ///
+    /// # Implementation notes
+    /// The owner and group are changed after `bind()`. To make sure that the socket is never
+    /// accessible to anyone who is not meant to access it, combine this with
+    /// [`mode`](Self::mode): the socket is then bound with only the owner bits of the mode set,
+    /// and receives the full mode only once its owner and group have been changed. Without a
+    /// mode, the socket is accessible under its original ownership, according to the umask, for a
+    /// brief period of time.
+    ///
+    /// Symlinks are never followed. If the socket file gets replaced with something other than a
+    /// socket owned by the effective user ID of the process before its owner is changed, which can
+    /// only be done by someone with write access to its directory, listener creation fails with
+    /// [`PermissionDenied`](std::io::ErrorKind::PermissionDenied) and the file is left as is.
+    ///
+    /// ## System calls
+    /// - `fstatat`
+    /// - `open` with `O_PATH` (Linux and Android)
+    /// - `fstat` (Linux and Android)
+    /// - `fchownat`
+    /// - `chmod` on `/proc/self/fd/` (Linux and Android)
+    /// - `fchmodat` (other Unices)
+    #[must_use = builder_must_use!()]
+    fn owner(self, owner: libc::uid_t) -> Self;
+
+    /// Sets the group ID to be made the group of the socket file.
+    ///
+    /// A process may generally only change the group of a file it owns to one of the groups it is
+    /// a member of, unless it is privileged. Otherwise, this behaves the same as
+    /// [`owner`](Self::owner), including with regard to its interaction with
+    /// [`mode`](Self::mode).
+    ///
+    /// ## System calls
+    /// Same as [`owner`](Self::owner).
+    #[must_use = builder_must_use!()]
+    fn group(self, group: libc::gid_t) -> Self;
 }
 
 impl ListenerOptionsExt for ListenerOptions<'_> {
@@ -40,4 +154,147 @@ impl ListenerOptionsExt for ListenerOptions<'_> {
         self.set_mode(mode);
         self
     }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[inline(always)]
+    fn autobind(mut self, autobind: bool) -> Self {
+        self.set_autobind(autobind);
+        self
+    }
+    #[inline(always)]
+    fn dir_mode(mut self, mode: libc::mode_t) -> Self {
+        self.set_dir_mode(mode);
+        self
+    }
+    #[inline(always)]
+    fn verify_parent_dir(mut self, verify: bool) -> Self {
+        self.set_verify_parent_dir(verify);
+        self
+    }
+    #[inline(always)]
+    fn owner(mut self, owner: libc::uid_t) -> Self {
+        self.set_owner(owner);
+        self
+    }
+    #[inline(always)]
+    fn group(mut self, group: libc::gid_t) -> Self {
+        self.set_group(group);
+        self
+    }
+}
+
+/// Unix-specific [client options](ConnectOptions).
+#[allow(private_bounds)]
+pub trait ConnectOptionsExt: Sized + Sealed {
+    /// Requires the server to have the given effective user ID, as reported by
+    /// [`PeerCreds::euid()`](crate::local_socket::PeerCreds::euid), failing the connection
+    /// otherwise.
+    ///
+    /// This is checked in the same manner as [`expect_server`](ConnectOptions::expect_server). If
+    /// the user ID of the server cannot be determined on the current platform, the check fails.
+    ///
+    /// ## System calls
+    /// - `getsockopt`
+    #[must_use = builder_must_use!()]
+    fn expect_server_euid(self, euid: libc::uid_t) -> Self;
+    /// Requires the server to have the given effective group ID, as reported by
+    /// [`PeerCreds::egid()`](crate::local_socket::PeerCreds::egid), failing the connection
+    /// otherwise.
+    ///
+    /// This is checked in the same manner as [`expect_server`](ConnectOptions::expect_server). If
+    /// the group ID of the server cannot be determined on the current platform, the check fails.
+    ///
+    /// ## System calls
+    /// - `getsockopt`
+    #[must_use = builder_must_use!()]
+    fn expect_server_egid(self, egid: libc::gid_t) -> Self;
+}
+
+impl ConnectOptionsExt for ConnectOptions<'_> {
+    #[inline(always)]
+    fn expect_server_euid(mut self, euid: libc::uid_t) -> Self {
+        self.set_server_euid(euid);
+        self
+    }
+    #[inline(always)]
+    fn expect_server_egid(mut self, egid: libc::gid_t) -> Self {
+        self.set_server_egid(egid);
+        self
+    }
+}
+
+/// Unix-specific extensions to [local socket listeners](traits::Listener).
+#[allow(private_bounds)]
+pub trait ListenerExt: traits::Listener + Sealed {
+    /// Like [`accept()`](traits::Listener::accept), but gives up with a
+    /// [`TimedOut`](io::ErrorKind::TimedOut) error if no client connects within the given amount
+    /// of time. This works regardless of whether the listener is in nonblocking mode.
+    ///
+    /// # Implementation notes
+    /// If the listener is in blocking mode and multiple threads accept connections concurrently,
+    /// a thread that loses the race for a connection it was woken up for may block until the next
+    /// one arrives, regardless of the timeout. In nonblocking mode, the wait is resumed instead.
+    #[inline]
+    fn accept_timeout(&self, timeout: Duration) -> io::Result<Self::Stream> {
+        self.accept_deadline(timeout_expiry(timeout)?)
+    }
+    /// Like [`accept_timeout()`](Self::accept_timeout), but with a point in time at which to give
+    /// up instead of a duration.
+    fn accept_deadline(&self, deadline: Instant) -> io::Result<Self::Stream>;
+    /// Returns an infinite iterator that [accepts](Self::accept_timeout) connections with the
+    /// given timeout for each one, producing a [`TimedOut`](io::ErrorKind::TimedOut) error
+    /// whenever it expires.
+    #[inline]
+    fn incoming_timeout(&self, timeout: Duration) -> IncomingTimeout<'_, Self> {
+        IncomingTimeout { listener: self, timeout }
+    }
+}
+
+pub(crate) fn accept_timed_out() -> io::Error {
+    io::Error::new(io::ErrorKind::TimedOut, "timed out while waiting for a client to connect")
+}
+
+/// Infinite iterator over incoming client connections of a listener, with a timeout for each
+/// one.
+///
+/// This iterator is created by the [`incoming_timeout()`](ListenerExt::incoming_timeout) method
+/// on [`ListenerExt`] – see its documentation for more.
+#[derive(Debug)]
+pub struct IncomingTimeout<'a, L: ?Sized> {
+    listener: &'a L,
+    timeout: Duration,
+}
+impl<L: ListenerExt> Iterator for IncomingTimeout<'_, L> {
+    type Item = io::Result<L::Stream>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> { Some(self.listener.accept_timeout(self.timeout)) }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (usize::MAX, None) }
+}
+impl<L: ListenerExt> FusedIterator for IncomingTimeout<'_, L> {}
+
+/// Unix-specific extensions to [local socket names](Name).
+#[allow(private_bounds)]
+pub trait NameExt: Sealed {
+    /// Returns the filesystem path the name refers to, or `None` if it does not refer to the
+    /// filesystem.
+    ///
+    /// Names produced by [`RuntimeDirUdSocket`] are filesystem paths, so the directory such a
+    /// name was resolved to is the [parent](Path::parent) of the returned path. For
+    /// [`LoopbackTcp`](crate::local_socket::LoopbackTcp) names, the path of the port file is
+    /// returned.
+    ///
+    /// Names produced by the deprecated [`SpecialDirUdSocket`] are only resolved to paths when a
+    /// listener is created or a connection is made, and thus `None` is returned for them.
+    #[allow(deprecated)]
+    fn fs_path(&self) -> Option<&Path>;
+}
+
+impl NameExt for Name<'_> {
+    #[inline]
+    fn fs_path(&self) -> Option<&Path> {
+        match &self.0 {
+            NameInner::UdSocketPath(path) | NameInner::Tcp(path) => Some(Path::new(path)),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,24 @@
+use {
+    super::super::uds_local_socket::async_io as uds_impl,
+    crate::local_socket::{
+        async_io::{prelude::*, Listener, Stream},
+        tcp::async_io as tcp_impl,
+        ConnectOptions, ListenerOptions,
+    },
+    std::io,
+};
+
+#[inline]
+pub fn listen(options: ListenerOptions<'_>) -> io::Result<Listener> {
+    if options.name.0.is_tcp() {
+        return options.create_async_io_as::<tcp_impl::Listener>().map(Listener::from);
+    }
+    options.create_async_io_as::<uds_impl::Listener>().map(Listener::from)
+}
+#[inline]
+pub async fn connect(options: &ConnectOptions<'_>) -> io::Result<Stream> {
+    if options.name.0.is_tcp() {
+        return tcp_impl::Stream::from_options(options).await.map(Stream::from);
+    }
+    uds_impl::Stream::from_options(options).await.map(Stream::from)
+}
@@ -1,14 +1,22 @@
 use {
     super::super::uds_local_socket as uds_impl,
-    crate::local_socket::{ConnectOptions, Listener, ListenerOptions, Stream},
+    crate::local_socket::{
+        prelude::*, tcp as tcp_impl, ConnectOptions, Listener, ListenerOptions, Stream,
+    },
     std::io,
 };
 
 #[inline]
 pub fn listen(options: ListenerOptions<'_>) -> io::Result<Listener> {
+    if options.name.0.is_tcp() {
+        return options.create_sync_as::<tcp_impl::Listener>().map(Listener::from);
+    }
     options.create_sync_as::<uds_impl::Listener>().map(Listener::from)
 }
 #[inline]
 pub fn connect(options: &ConnectOptions<'_>) -> io::Result<Stream> {
-    options.connect_sync_as::<uds_impl::Stream>().map(Stream::from)
+    if options.name.0.is_tcp() {
+        return tcp_impl::Stream::from_options(options).map(Stream::from);
+    }
+    uds_impl::Stream::from_options(options).map(Stream::from)
 }
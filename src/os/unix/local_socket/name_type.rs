@@ -1,5 +1,10 @@
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::os::unix::ud_addr::SUN_LEN;
 use {
-    crate::local_socket::{Name, NameInner, NameType, NamespacedNameType, PathNameType},
+    super::runtime_dir::{self, RuntimeDir},
+    crate::local_socket::{
+        Name, NameError, NameInner, NameType, NamespacedNameType, PathNameType,
+    },
     std::{
         borrow::Cow,
         ffi::{CStr, OsStr, OsString},
@@ -8,40 +13,50 @@ use {
     },
 };
 
-fn c2os(ccow: Cow<'_, CStr>) -> Cow<'_, OsStr> {
+pub(crate) fn c2os(ccow: Cow<'_, CStr>) -> Cow<'_, OsStr> {
     match ccow {
         Cow::Borrowed(cstr) => Cow::Borrowed(OsStr::from_bytes(cstr.to_bytes())),
         Cow::Owned(cstring) => Cow::Owned(OsString::from_vec(cstring.into_bytes())),
     }
 }
 
+fn check_no_nul(name: &OsStr) -> Result<(), NameError> {
+    match name.as_bytes().iter().position(|&b| b == 0) {
+        Some(pos) => Err(NameError::InteriorNul(pos)),
+        None => Ok(()),
+    }
+}
+
 tag_enum!(
 /// [Mapping](NameType) that produces local socket names referring to Unix domain sockets bound to
 /// the filesystem.
 ///
 /// For Unix domain sockets residing in the Linux abstract namespace, see `AbstractNsUdSocket`
 /// instead.
+///
+/// ## Platform-specific behavior
+/// ### Linux and Android
+/// Paths that are too long to fit into the address structure are supported by opening their
+/// parent directory and binding or connecting to the socket via `/proc/self/fd/`. Only the last
+/// path component is subject to the length limit in this case.
+///
+/// ### Other Unix systems
+/// Paths that are too long to fit into the address structure (typically around 100 bytes) yield
+/// an error when used.
 FilesystemUdSocket);
 impl NameType for FilesystemUdSocket {
     fn is_supported() -> bool { true }
 }
 impl PathNameType<OsStr> for FilesystemUdSocket {
     #[inline]
-    fn map(path: Cow<'_, OsStr>) -> io::Result<Name<'_>> {
-        for b in path.as_bytes() {
-            if *b == 0 {
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    "filesystem paths cannot contain interior nuls",
-                ));
-            }
-        }
+    fn map(path: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
+        check_no_nul(&path)?;
         Ok(Name(NameInner::UdSocketPath(path)))
     }
 }
 impl PathNameType<CStr> for FilesystemUdSocket {
     #[inline]
-    fn map(path: Cow<'_, CStr>) -> io::Result<Name<'_>> { Self::map(c2os(path)) }
+    fn map(path: Cow<'_, CStr>) -> Result<Name<'_>, NameError> { Self::map(c2os(path)) }
 }
 
 tag_enum!(
@@ -52,7 +67,7 @@ tag_enum!(
 /// [namespaced name type](NamespacedNameType) on those systems.
 #[deprecated = "\
     inconsistent and suboptimal selection of temporary directory, may not work on Android; use \
-    FilesystemUdSocket directly instead"]
+    RuntimeDirUdSocket or FilesystemUdSocket directly instead"]
 SpecialDirUdSocket);
 #[allow(deprecated)]
 impl NameType for SpecialDirUdSocket {
@@ -61,28 +76,87 @@ impl NameType for SpecialDirUdSocket {
 #[allow(deprecated)]
 impl NamespacedNameType<OsStr> for SpecialDirUdSocket {
     #[inline]
-    fn map(name: Cow<'_, OsStr>) -> io::Result<Name<'_>> {
-        for b in name.as_bytes() {
-            if *b == 0 {
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    "special directory-bound names cannot contain interior nuls",
-                ));
-            }
-        }
+    fn map(name: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
+        check_no_nul(&name)?;
         Ok(Name(NameInner::UdSocketPseudoNs(name)))
     }
 }
 #[allow(deprecated)]
 impl NamespacedNameType<CStr> for SpecialDirUdSocket {
     #[inline]
-    fn map(name: Cow<'_, CStr>) -> io::Result<Name<'_>> { Self::map(c2os(name)) }
+    fn map(name: Cow<'_, CStr>) -> Result<Name<'_>, NameError> { Self::map(c2os(name)) }
+}
+
+tag_enum!(
+/// [Mapping](NameType) that produces local socket names referring to Unix domain sockets bound to
+/// the filesystem inside of the user's runtime directory.
+///
+/// The runtime directory is chosen at the time of mapping by going through the following chain of
+/// candidates and picking the first one that is suitable:
+/// 1. The value of the `XDG_RUNTIME_DIR` environment variable, if it is an absolute path to a
+///    directory that is owned by the effective user ID of the process and has mode `0700`.
+/// 2. `/run/user/<uid>`, where `<uid>` is the effective user ID of the process, subject to the
+///    same checks.
+/// 3. `interprocess-<uid>` inside of the temporary directory, which is the value of the `TMPDIR`
+///    environment variable if it is an absolute path, and `/tmp` (`/data/local/tmp` on Android)
+///    otherwise. It is created with mode `0700` if it does not exist. Mapping fails with
+///    [`NameError::Io`] of kind [`PermissionDenied`](io::ErrorKind::PermissionDenied) if it does
+///    exist but is not a directory (symbolic links are not followed) owned by the effective user
+///    ID with mode `0700`.
+///
+/// The chosen directory can be inspected via [`runtime_dir()`](Self::runtime_dir). The resulting
+/// names are ordinary [filesystem paths](FilesystemUdSocket), and thus the directory a particular
+/// name was resolved to can be retrieved from the name itself via
+/// [`NameExt`](super::NameExt).
+///
+/// Names may not be empty, be `.` or `..`, or contain slashes
+/// ([`NameError::InvalidComponent`]) or nul bytes ([`NameError::InteriorNul`]).
+///
+/// Note that [`GenericNamespaced`](crate::local_socket::GenericNamespaced) does not use this
+/// mapping.
+RuntimeDirUdSocket);
+impl RuntimeDirUdSocket {
+    /// Resolves the runtime directory that names are currently mapped to, as described in the
+    /// [type-level documentation](Self).
+    ///
+    /// This may create the last-resort fallback directory.
+    #[inline]
+    pub fn runtime_dir() -> io::Result<RuntimeDir> { runtime_dir::resolve() }
+}
+impl NameType for RuntimeDirUdSocket {
+    fn is_supported() -> bool { true }
+}
+impl NamespacedNameType<OsStr> for RuntimeDirUdSocket {
+    fn map(name: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
+        check_no_nul(&name)?;
+        let bytes = name.as_bytes();
+        if matches!(bytes, b"" | b"." | b"..") || bytes.contains(&b'/') {
+            return Err(NameError::InvalidComponent);
+        }
+        let mut path = runtime_dir::resolve()?.into_path();
+        path.push(name);
+        Ok(Name(NameInner::UdSocketPath(Cow::Owned(path.into_os_string()))))
+    }
+}
+impl NamespacedNameType<CStr> for RuntimeDirUdSocket {
+    #[inline]
+    fn map(name: Cow<'_, CStr>) -> Result<Name<'_>, NameError> { Self::map(c2os(name)) }
 }
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 tag_enum!(
 /// [Mapping](NameType) that produces local socket names referring to Unix domain sockets bound to
 /// the Linux abstract namespace.
+///
+/// Abstract namespace names are arbitrary byte strings, which may contain nul bytes. They are
+/// used verbatim, without a nul terminator being appended, which allows interoperating with
+/// programs that bind to binary names. Use [`OsStrExt::from_bytes()`] to make such a name out of
+/// a byte slice.
+///
+/// Names longer than the address structure can hold (107 bytes) are rejected with
+/// [`NameError::TooLong`].
+///
+/// [`OsStrExt::from_bytes()`]: std::os::unix::ffi::OsStrExt::from_bytes
 #[cfg_attr(feature = "doc_cfg", doc(cfg(any(target_os = "linux", target_os = "android"))))]
 AbstractNsUdSocket);
 #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -94,47 +168,55 @@ impl NameType for AbstractNsUdSocket {
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl NamespacedNameType<OsStr> for AbstractNsUdSocket {
     #[inline]
-    fn map(name: Cow<'_, OsStr>) -> io::Result<Name<'_>> {
+    fn map(name: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
         let name = match name {
             Cow::Borrowed(b) => Cow::Borrowed(b.as_bytes()),
             Cow::Owned(o) => Cow::Owned(o.into_vec()),
         };
+        // The leading nul byte takes up one byte of sun_path
+        let max = SUN_LEN.saturating_sub(1);
+        if name.len() > max {
+            return Err(NameError::TooLong { max, actual: name.len() });
+        }
         Ok(Name(NameInner::UdSocketNs(name)))
     }
 }
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl NamespacedNameType<CStr> for AbstractNsUdSocket {
     #[inline]
-    fn map(name: Cow<'_, CStr>) -> io::Result<Name<'_>> { Self::map(c2os(name)) }
+    fn map(name: Cow<'_, CStr>) -> Result<Name<'_>, NameError> { Self::map(c2os(name)) }
 }
 
 macro_rules! map_generic {
     (path $name:ident for $str:ident) => {
-        pub(crate) fn $name(path: Cow<'_, $str>) -> io::Result<Name<'_>> {
+        pub(crate) fn $name(path: Cow<'_, $str>) -> Result<Name<'_>, NameError> {
             FilesystemUdSocket::map(path)
         }
     };
-    (namespaced $name:ident for $str:ident) => {
-        #[allow(deprecated)]
-        pub(crate) fn $name(name: Cow<'_, $str>) -> io::Result<Name<'_>> {
-            #[cfg(any(target_os = "linux", target_os = "android"))]
-            {
-                AbstractNsUdSocket::map(name)
-            }
-            #[cfg(not(any(target_os = "linux", target_os = "android")))]
-            {
-                SpecialDirUdSocket::map(name)
-            }
-        }
-    };
     ($($type:ident $name:ident for $str:ident)+) => {$(
         map_generic!($type $name for $str);
     )+};
 }
 
 map_generic! {
-    path       map_generic_path_osstr       for OsStr
-    path       map_generic_path_cstr        for CStr
-    namespaced map_generic_namespaced_osstr for OsStr
-    namespaced map_generic_namespaced_cstr  for CStr
+    path map_generic_path_osstr for OsStr
+    path map_generic_path_cstr  for CStr
+}
+
+#[allow(deprecated)]
+pub(crate) fn map_generic_namespaced_osstr(name: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
+    // Nul bytes are only accepted when AbstractNsUdSocket is used explicitly, so that the generic
+    // mapping accepts the same names on all platforms.
+    check_no_nul(&name)?;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        AbstractNsUdSocket::map(name)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+        SpecialDirUdSocket::map(name)
+    }
+}
+pub(crate) fn map_generic_namespaced_cstr(name: Cow<'_, CStr>) -> Result<Name<'_>, NameError> {
+    map_generic_namespaced_osstr(c2os(name))
 }
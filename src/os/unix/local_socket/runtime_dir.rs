@@ -0,0 +1,94 @@
+use std::{
+    env,
+    fs::{self, Metadata},
+    io,
+    os::unix::{
+        fs::{DirBuilderExt, PermissionsExt},
+        prelude::*,
+    },
+    path::{Path, PathBuf},
+};
+
+/// A directory that [`RuntimeDirUdSocket`](super::RuntimeDirUdSocket) resolves names to, along with
+/// the step of the fallback chain it was found by.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RuntimeDir {
+    path: PathBuf,
+    source: RuntimeDirSource,
+}
+impl RuntimeDir {
+    /// Returns the path to the directory.
+    #[inline]
+    pub fn path(&self) -> &Path { &self.path }
+    /// Returns the step of the fallback chain the directory was found by.
+    #[inline]
+    pub fn source(&self) -> RuntimeDirSource { self.source }
+    /// Extracts the path to the directory.
+    #[inline]
+    pub fn into_path(self) -> PathBuf { self.path }
+}
+
+/// The step of the [`RuntimeDirUdSocket`](super::RuntimeDirUdSocket) fallback chain by which a
+/// [`RuntimeDir`] was found.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RuntimeDirSource {
+    /// The directory was specified by the `XDG_RUNTIME_DIR` environment variable.
+    XdgRuntimeDir,
+    /// The directory is `/run/user/<uid>`, where `<uid>` is the effective user ID of the process.
+    RunUser,
+    /// The directory is `interprocess-<uid>` inside of the temporary directory, where `<uid>` is
+    /// the effective user ID of the process.
+    TempDir,
+}
+
+/// Resolves the runtime directory according to the fallback chain described in the documentation
+/// of `RuntimeDirUdSocket`.
+pub(crate) fn resolve() -> io::Result<RuntimeDir> {
+    // SAFETY: always safe
+    let uid = unsafe { libc::geteuid() };
+    let found = |path, source| Ok(RuntimeDir { path, source });
+
+    if let Some(path) = env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from) {
+        // The XDG Base Directory Specification says that relative paths are to be ignored.
+        if path.is_absolute() && fs::metadata(&path).is_ok_and(|m| is_private_dir(&m, uid)) {
+            return found(path, RuntimeDirSource::XdgRuntimeDir);
+        }
+    }
+
+    let path = PathBuf::from(format!("/run/user/{uid}"));
+    if fs::metadata(&path).is_ok_and(|m| is_private_dir(&m, uid)) {
+        return found(path, RuntimeDirSource::RunUser);
+    }
+
+    let mut path = tempdir();
+    path.push(format!("interprocess-{uid}"));
+    match fs::DirBuilder::new().mode(0o700).create(&path) {
+        // The umask may have left the directory with fewer permissions than needed.
+        Ok(()) => fs::set_permissions(&path, fs::Permissions::from_mode(0o700))?,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+    // Symlinks are not followed here, since anyone could have created one in the temporary
+    // directory before us.
+    if !fs::symlink_metadata(&path).is_ok_and(|m| is_private_dir(&m, uid)) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "fallback runtime directory is not a directory owned by the current user with mode \
+            0700",
+        ));
+    }
+    found(path, RuntimeDirSource::TempDir)
+}
+
+fn is_private_dir(meta: &Metadata, uid: libc::uid_t) -> bool {
+    meta.is_dir() && meta.uid() == uid && meta.mode() & 0o777 == 0o700
+}
+
+fn tempdir() -> PathBuf {
+    let default = if cfg!(target_os = "android") { "/data/local/tmp" } else { "/tmp" };
+    env::var_os("TMPDIR")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| PathBuf::from(default))
+}
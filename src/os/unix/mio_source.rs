@@ -0,0 +1,71 @@
+//! [`mio::event::Source`] implementations for local sockets and unnamed pipes.
+
+use {
+    super::{c_wrappers, uds_local_socket as uds, unixprelude::*},
+    crate::{local_socket, unnamed_pipe},
+    mio::{event::Source, unix::SourceFd, Interest, Registry, Token},
+    std::io,
+};
+
+macro_rules! impl_source {
+    ($docs:tt $($ty:ty),+ $(,)?) => {$(
+        impl_source!(@impl $docs $ty);
+    )+};
+    (@impl { $(#[$attr:meta])* } $ty:ty) => {
+        $(#[$attr])*
+        #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "mio")))]
+        impl Source for $ty {
+            fn register(
+                &mut self,
+                registry: &Registry,
+                token: Token,
+                interests: Interest,
+            ) -> io::Result<()> {
+                let fd = self.as_fd();
+                c_wrappers::set_nonblocking(fd, true)?;
+                SourceFd(&fd.as_raw_fd()).register(registry, token, interests)
+            }
+            fn reregister(
+                &mut self,
+                registry: &Registry,
+                token: Token,
+                interests: Interest,
+            ) -> io::Result<()> {
+                SourceFd(&self.as_fd().as_raw_fd()).reregister(registry, token, interests)
+            }
+            fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+                SourceFd(&self.as_fd().as_raw_fd()).deregister(registry)
+            }
+        }
+    };
+}
+
+impl_source! {
+    {
+        /// Registration puts the listener in nonblocking mode for accepting, turning a
+        /// [`ListenerNonblockingMode`](local_socket::ListenerNonblockingMode) of `Neither` into
+        /// `Accept` and `Stream` into `Both`. Whether accepted streams are nonblocking is left
+        /// unchanged; they are put in nonblocking mode when they are registered themselves.
+        ///
+        /// Deregistration does not switch the listener back to blocking mode.
+    }
+    uds::Listener, local_socket::Listener,
+}
+impl_source! {
+    {
+        /// Registration puts the stream in nonblocking mode, which deregistration does not undo.
+        ///
+        /// The halves of a split stream share the same file descriptor, and thus the same
+        /// registration. Only one of them can be registered with a given [`Registry`] at a time,
+        /// with an interest that covers both of their needs.
+    }
+    uds::Stream, uds::RecvHalf, uds::SendHalf,
+    local_socket::Stream, local_socket::RecvHalf, local_socket::SendHalf,
+}
+impl_source! {
+    {
+        /// Registration puts the pipe end in nonblocking mode, which deregistration does not
+        /// undo.
+    }
+    unnamed_pipe::Sender, unnamed_pipe::Recver,
+}
@@ -0,0 +1,57 @@
+use {
+    super::{c_wrappers, ud_addr::TerminatedUdAddr, unixprelude::*},
+    std::{
+        ffi::{CStr, CString, OsStr},
+        fmt::{self, Debug, Formatter},
+    },
+};
+
+/// Performs name reclamation when dropped.
+#[derive(Clone, Default)]
+pub(super) struct ReclaimGuard(Box<[u8]>);
+impl ReclaimGuard {
+    fn disarmed() -> Self { Self(Box::new([])) }
+    /// Creates a reclamation guard for the given address. If `cond` is false, creates a disarmed
+    /// guard instead.
+    pub(super) fn new(cond: bool, addr: TerminatedUdAddr<'_>) -> Self {
+        if !cond
+            || addr.inner().path().is_empty()
+            || cfg!(any(target_os = "linux", target_os = "android"))
+                && addr.inner().path().first() == Some(&0)
+        {
+            return Self::disarmed();
+        }
+        Self(addr.full_path().to_owned().into_bytes_with_nul().into_boxed_slice())
+    }
+    /// Creates a reclamation guard for the given filesystem path. If `cond` is false, creates a
+    /// disarmed guard instead.
+    pub(super) fn with_path(cond: bool, path: CString) -> Self {
+        if !cond || path.is_empty() {
+            return Self::disarmed();
+        }
+        Self(path.into_bytes_with_nul().into_boxed_slice())
+    }
+    /// Takes ownership of the reclaim guard, leaving a disarmed one in place.
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    pub(super) fn take(&mut self) -> Self { Self(std::mem::take(&mut self.0)) }
+    /// Disarms the reclaim guard. It will not do anything when dropped.
+    pub(super) fn forget(&mut self) { self.0 = Box::new([]); }
+    fn as_c_str(&self) -> Option<&CStr> {
+        // SAFETY: the only constructors that produce a non-empty one get
+        //         it from into_bytes_with_nul
+        (!self.0.is_empty()).then(|| unsafe { CStr::from_bytes_with_nul_unchecked(&self.0) })
+    }
+}
+impl Drop for ReclaimGuard {
+    fn drop(&mut self) {
+        if let Some(s) = self.as_c_str() {
+            let _ = c_wrappers::unlink(s);
+        }
+    }
+}
+impl Debug for ReclaimGuard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = self.as_c_str().map(|s| OsStr::from_bytes(s.to_bytes()));
+        f.debug_tuple("ReclaimGuard").field(&s).finish()
+    }
+}
@@ -0,0 +1,478 @@
+//! Readiness multiplexing for sync local sockets, unnamed pipes and other file descriptors.
+//!
+//! A [`Selector`] waits for any of a set of registered objects to become ready for I/O, allowing
+//! one thread to serve many nonblocking sync connections without an external event loop crate.
+//! Objects are registered with a [`Token`] of the caller's choosing, which is reported back in
+//! the [`Event`]s produced by [`Selector::select()`].
+//!
+//! ```no_run
+//! use {
+//!     interprocess::{
+//!         local_socket::{prelude::*, GenericNamespaced, ListenerNonblockingMode, ListenerOptions},
+//!         os::unix::selector::{Events, Interest, Selector, Token, Trigger},
+//!     },
+//!     std::io,
+//! };
+//! let listener = ListenerOptions::new()
+//!     .name("example.sock".to_ns_name::<GenericNamespaced>()?)
+//!     .nonblocking(ListenerNonblockingMode::Both)
+//!     .create_sync()?;
+//! let selector = Selector::new()?;
+//! selector.register(&listener, Token(0), Interest::READABLE, Trigger::Level)?;
+//! let mut events = Events::with_capacity(64);
+//! loop {
+//!     selector.select(&mut events, None)?;
+//!     for event in &events {
+//!         if event.token() == Token(0) {
+//!             match listener.accept() {
+//!                 Ok(conn) => { /* register the connection with a token of its own... */ }
+//!                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+//!                 Err(e) => return Err(e.into()),
+//!             }
+//!         }
+//!     }
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use {
+    super::{c_wrappers, unixprelude::*},
+    crate::OrErrno,
+    std::{
+        fmt::{self, Debug, Formatter},
+        io,
+        ops::{BitOr, BitOrAssign},
+        sync::{
+            atomic::{AtomicUsize, Ordering::Relaxed},
+            Mutex,
+        },
+        time::Duration,
+    },
+};
+
+/// Identifier of a registered object, reported back in the [`Event`]s concerning it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Token(pub usize);
+
+/// Set of readiness kinds a registration is interested in.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Interest(u8);
+impl Interest {
+    /// Readiness for receiving, or, for listeners, for accepting a connection.
+    pub const READABLE: Self = Self(1);
+    /// Readiness for sending.
+    pub const WRITABLE: Self = Self(2);
+    /// Returns `true` if the set includes [readability](Self::READABLE).
+    #[inline]
+    pub const fn is_readable(self) -> bool { self.0 & Self::READABLE.0 != 0 }
+    /// Returns `true` if the set includes [writability](Self::WRITABLE).
+    #[inline]
+    pub const fn is_writable(self) -> bool { self.0 & Self::WRITABLE.0 != 0 }
+}
+impl BitOr for Interest {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self { Self(self.0 | rhs.0) }
+}
+impl BitOrAssign for Interest {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) { self.0 |= rhs.0 }
+}
+impl Debug for Interest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interest")
+            .field("readable", &self.is_readable())
+            .field("writable", &self.is_writable())
+            .finish()
+    }
+}
+
+/// The manner in which readiness of a registration is reported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    /// Readiness is reported by every [`select()`](Selector::select) call for as long as the
+    /// object remains ready.
+    Level,
+    /// Readiness is reported once when the object becomes ready, and then not again until it
+    /// stops being ready (e.g. after a read fails with
+    /// [`WouldBlock`](io::ErrorKind::WouldBlock)) and becomes ready again.
+    ///
+    /// Only supported by the epoll backend, i.e. on Linux and Android.
+    Edge,
+}
+
+/// Readiness event reported by [`Selector::select()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    token: Token,
+    flags: u8,
+}
+const EV_READABLE: u8 = 1;
+const EV_WRITABLE: u8 = 2;
+const EV_HANGUP: u8 = 4;
+const EV_ERROR: u8 = 8;
+impl Event {
+    /// Returns the token of the registration the event concerns.
+    #[inline]
+    pub const fn token(&self) -> Token { self.token }
+    /// Returns `true` if the object is ready for receiving or accepting.
+    #[inline]
+    pub const fn is_readable(&self) -> bool { self.flags & EV_READABLE != 0 }
+    /// Returns `true` if the object is ready for sending.
+    #[inline]
+    pub const fn is_writable(&self) -> bool { self.flags & EV_WRITABLE != 0 }
+    /// Returns `true` if the peer has hung up, in which case receiving reports end of file.
+    #[inline]
+    pub const fn is_hangup(&self) -> bool { self.flags & EV_HANGUP != 0 }
+    /// Returns `true` if an error is pending on the object, or if the file descriptor is invalid.
+    #[inline]
+    pub const fn is_error(&self) -> bool { self.flags & EV_ERROR != 0 }
+}
+
+/// Buffer of [`Event`]s filled in by [`Selector::select()`].
+#[derive(Clone, Debug)]
+pub struct Events {
+    events: Vec<Event>,
+    capacity: usize,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    raw: Vec<libc::epoll_event>,
+}
+impl Events {
+    /// Creates a buffer that holds up to the given number of events per
+    /// [`select()`](Selector::select) call. Zero is treated as one.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            events: Vec::with_capacity(capacity),
+            capacity,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            raw: Vec::with_capacity(capacity),
+        }
+    }
+    /// Returns the maximum number of events per [`select()`](Selector::select) call.
+    #[inline]
+    pub fn capacity(&self) -> usize { self.capacity }
+    /// Returns the number of events reported by the last [`select()`](Selector::select) call.
+    #[inline]
+    pub fn len(&self) -> usize { self.events.len() }
+    /// Returns `true` if the last [`select()`](Selector::select) call reported no events.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.events.is_empty() }
+    /// Iterates through the events reported by the last [`select()`](Selector::select) call.
+    #[inline]
+    pub fn iter(&self) -> std::iter::Copied<std::slice::Iter<'_, Event>> {
+        self.events.iter().copied()
+    }
+}
+impl<'a> IntoIterator for &'a Events {
+    type Item = Event;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, Event>>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+/// Waits for readiness of multiple registered objects.
+///
+/// On Linux and Android, this is implemented using epoll; elsewhere, and when created with
+/// [`new_poll()`](Self::new_poll), the set of registrations is kept in memory and passed to
+/// `poll` on every [`select()`](Self::select) call.
+///
+/// Any object that implements [`AsFd`] can be registered, including sync local socket listeners
+/// and streams, unnamed pipe ends and FIFO files. Objects should be put in nonblocking mode, as a
+/// readiness event does not guarantee that the next I/O operation will not block, for instance
+/// if another thread gets to the data first.
+///
+/// An object must be [deregistered](Self::deregister) before it is closed. Closing a registered
+/// object is not an error with epoll, but may lead to error events with `poll` and to events
+/// concerning an unrelated object that reuses the file descriptor.
+pub struct Selector(Backend);
+enum Backend {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Epoll(OwnedFd),
+    /// The registrations and the index at which the next scan for ready ones begins.
+    Poll(Mutex<Vec<Registration>>, AtomicUsize),
+}
+#[derive(Debug)]
+struct Registration {
+    fd: RawFd,
+    token: Token,
+    interest: Interest,
+}
+
+impl Selector {
+    /// Creates a selector using the best backend for the platform.
+    ///
+    /// ## System calls
+    /// - `epoll_create1` (Linux, Android)
+    pub fn new() -> io::Result<Self> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let fd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+            let fd = (fd != -1).true_val_or_errno(fd)?;
+            Ok(Self(Backend::Epoll(unsafe { OwnedFd::from_raw_fd(fd) })))
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        {
+            Ok(Self::new_poll())
+        }
+    }
+    /// Creates a selector that uses `poll` even on platforms that have a better mechanism.
+    #[inline]
+    pub fn new_poll() -> Self { Self(Backend::Poll(Mutex::default(), AtomicUsize::new(0))) }
+
+    /// Registers an object with the given token, interest and trigger mode.
+    ///
+    /// Registering the same file descriptor twice fails with
+    /// [`AlreadyExists`](io::ErrorKind::AlreadyExists), and [edge triggering](Trigger::Edge) is
+    /// [`Unsupported`](io::ErrorKind::Unsupported) with the `poll` backend.
+    ///
+    /// ## System calls
+    /// - `epoll_ctl` (epoll backend)
+    pub fn register(
+        &self,
+        object: &impl AsFd,
+        token: Token,
+        interest: Interest,
+        trigger: Trigger,
+    ) -> io::Result<()> {
+        let fd = object.as_fd().as_raw_fd();
+        match &self.0 {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::Epoll(epfd) => {
+                epoll_ctl(epfd.as_fd(), libc::EPOLL_CTL_ADD, fd, token, interest, trigger)
+            }
+            Backend::Poll(regs, ..) => {
+                check_level(trigger)?;
+                let mut regs = lock(regs);
+                if regs.iter().any(|r| r.fd == fd) {
+                    return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+                }
+                regs.push(Registration { fd, token, interest });
+                Ok(())
+            }
+        }
+    }
+    /// Changes the token, interest and trigger mode of a registered object.
+    ///
+    /// Fails with [`NotFound`](io::ErrorKind::NotFound) if the object is not registered.
+    ///
+    /// ## System calls
+    /// - `epoll_ctl` (epoll backend)
+    pub fn reregister(
+        &self,
+        object: &impl AsFd,
+        token: Token,
+        interest: Interest,
+        trigger: Trigger,
+    ) -> io::Result<()> {
+        let fd = object.as_fd().as_raw_fd();
+        match &self.0 {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::Epoll(epfd) => {
+                epoll_ctl(epfd.as_fd(), libc::EPOLL_CTL_MOD, fd, token, interest, trigger)
+            }
+            Backend::Poll(regs, ..) => {
+                check_level(trigger)?;
+                let mut regs = lock(regs);
+                let reg = regs.iter_mut().find(|r| r.fd == fd).ok_or(io::ErrorKind::NotFound)?;
+                reg.token = token;
+                reg.interest = interest;
+                Ok(())
+            }
+        }
+    }
+    /// Removes an object from the selector.
+    ///
+    /// Fails with [`NotFound`](io::ErrorKind::NotFound) if the object is not registered.
+    ///
+    /// ## System calls
+    /// - `epoll_ctl` (epoll backend)
+    pub fn deregister(&self, object: &impl AsFd) -> io::Result<()> {
+        let fd = object.as_fd().as_raw_fd();
+        match &self.0 {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::Epoll(epfd) => unsafe {
+                libc::epoll_ctl(epfd.as_raw_fd(), libc::EPOLL_CTL_DEL, fd, std::ptr::null_mut())
+                    != -1
+            }
+            .true_val_or_errno(()),
+            Backend::Poll(regs, ..) => {
+                let mut regs = lock(regs);
+                let idx = regs.iter().position(|r| r.fd == fd).ok_or(io::ErrorKind::NotFound)?;
+                regs.swap_remove(idx);
+                Ok(())
+            }
+        }
+    }
+
+    /// Waits until at least one registered object is ready or the timeout expires, and fills
+    /// `events` with the readiness events, replacing its previous contents.
+    ///
+    /// `events` is left empty if the timeout expires or the wait is interrupted by a signal.
+    /// With the `poll` backend, registration changes made by other threads during the wait only
+    /// take effect on the next call. If more objects are ready than `events` holds, the `poll`
+    /// backend reports the remaining ones first on the next call, while epoll does so by itself.
+    ///
+    /// ## System calls
+    /// - `epoll_wait` (epoll backend)
+    /// - `poll`/`ppoll` (`poll` backend)
+    pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        events.events.clear();
+        match &self.0 {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::Epoll(epfd) => epoll_wait(epfd.as_fd(), events, timeout),
+            Backend::Poll(regs, start) => {
+                let (mut fds, tokens): (Vec<_>, Vec<_>) =
+                    lock(regs).iter().map(|r| (reg_to_pollfd(r), r.token)).unzip();
+                c_wrappers::poll_fds(&mut fds, timeout)?;
+                // Picks up where the previous call left off, so that objects late in the list are
+                // not starved when more of them are ready than the buffer holds
+                let len = fds.len();
+                let first = start.load(Relaxed).checked_rem(len).unwrap_or(0);
+                let ready = (first..len)
+                    .chain(0..first)
+                    .filter_map(|i| Some((i, fds.get(i)?, *tokens.get(i)?)))
+                    .filter(|(_, fd, _)| fd.revents != 0);
+                for (i, fd, token) in ready.take(events.capacity) {
+                    events.events.push(Event { token, flags: poll_flags(fd.revents) });
+                    start.store(i.saturating_add(1), Relaxed);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Debug for Selector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::Epoll(epfd) => f.debug_tuple("Selector").field(&epfd.as_raw_fd()).finish(),
+            Backend::Poll(regs, ..) => f.debug_tuple("Selector").field(&*lock(regs)).finish(),
+        }
+    }
+}
+
+fn lock(regs: &Mutex<Vec<Registration>>) -> std::sync::MutexGuard<'_, Vec<Registration>> {
+    // The registration list is never left in an inconsistent state
+    regs.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn check_level(trigger: Trigger) -> io::Result<()> {
+    match trigger {
+        Trigger::Level => Ok(()),
+        Trigger::Edge => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "edge triggering is not supported by the poll backend",
+        )),
+    }
+}
+
+fn reg_to_pollfd(reg: &Registration) -> libc::pollfd {
+    let mut events = 0;
+    if reg.interest.is_readable() {
+        events |= libc::POLLIN;
+    }
+    if reg.interest.is_writable() {
+        events |= libc::POLLOUT;
+    }
+    libc::pollfd { fd: reg.fd, events, revents: 0 }
+}
+
+fn poll_flags(revents: c_short) -> u8 {
+    let mut flags = 0;
+    if revents & libc::POLLIN != 0 {
+        flags |= EV_READABLE;
+    }
+    if revents & libc::POLLOUT != 0 {
+        flags |= EV_WRITABLE;
+    }
+    if revents & libc::POLLHUP != 0 {
+        flags |= EV_HANGUP;
+    }
+    if revents & (libc::POLLERR | libc::POLLNVAL) != 0 {
+        flags |= EV_ERROR;
+    }
+    flags
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn epoll_ctl(
+    epfd: BorrowedFd<'_>,
+    op: c_int,
+    fd: RawFd,
+    token: Token,
+    interest: Interest,
+    trigger: Trigger,
+) -> io::Result<()> {
+    let mut events = 0;
+    if interest.is_readable() {
+        // Not requested otherwise, as it would keep level-triggered registrations that only
+        // await writability ready for as long as the peer has shut down its sending half
+        events |= libc::EPOLLIN | libc::EPOLLRDHUP;
+    }
+    if interest.is_writable() {
+        events |= libc::EPOLLOUT;
+    }
+    if trigger == Trigger::Edge {
+        events |= libc::EPOLLET;
+    }
+    #[allow(clippy::cast_sign_loss)]
+    let mut event = libc::epoll_event { events: events as u32, u64: token.0 as u64 };
+    unsafe { libc::epoll_ctl(epfd.as_raw_fd(), op, fd, &mut event) != -1 }.true_val_or_errno(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn epoll_wait(
+    epfd: BorrowedFd<'_>,
+    events: &mut Events,
+    timeout: Option<Duration>,
+) -> io::Result<()> {
+    // Rounded up so that short timeouts don't turn into busy waiting
+    let timeout = timeout.map_or(-1, |t| {
+        let ms = t.as_millis().saturating_add(u128::from(t.subsec_nanos() % 1_000_000 != 0));
+        c_int::try_from(ms).unwrap_or(c_int::MAX)
+    });
+    let raw = &mut events.raw;
+    raw.clear();
+    // A clone of the buffer only has as much room as the original had events
+    raw.reserve(events.capacity);
+    let capacity = c_int::try_from(events.capacity).unwrap_or(c_int::MAX);
+    let n = unsafe { libc::epoll_wait(epfd.as_raw_fd(), raw.as_mut_ptr(), capacity, timeout) };
+    match (n != -1).true_val_or_errno(n) {
+        Ok(n) => {
+            // SAFETY: the kernel has initialized that many events
+            #[allow(clippy::cast_sign_loss)]
+            unsafe {
+                raw.set_len(n as usize)
+            };
+        }
+        Err(e) if e.kind() == io::ErrorKind::Interrupted => return Ok(()),
+        Err(e) => return Err(e),
+    }
+    events.events.extend(raw.iter().map(|ev| {
+        let (bits, data) = (ev.events, ev.u64);
+        #[allow(clippy::cast_possible_truncation)]
+        Event { token: Token(data as usize), flags: epoll_flags(bits) }
+    }));
+    Ok(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[allow(clippy::cast_sign_loss)]
+fn epoll_flags(bits: u32) -> u8 {
+    let mut flags = 0;
+    if bits & libc::EPOLLIN as u32 != 0 {
+        flags |= EV_READABLE;
+    }
+    if bits & libc::EPOLLOUT as u32 != 0 {
+        flags |= EV_WRITABLE;
+    }
+    if bits & (libc::EPOLLHUP | libc::EPOLLRDHUP) as u32 != 0 {
+        flags |= EV_HANGUP;
+    }
+    if bits & libc::EPOLLERR as u32 != 0 {
+        flags |= EV_ERROR;
+    }
+    flags
+}
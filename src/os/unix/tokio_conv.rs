@@ -0,0 +1,38 @@
+//! Lossless conversions between sync and Tokio objects.
+
+use {
+    super::{c_wrappers, unixprelude::*},
+    crate::error::ConversionError,
+    std::io,
+};
+
+/// Converts `src` into another object by passing a duplicate of its file descriptor, set to the
+/// given nonblocking mode, to `f`.
+///
+/// The source is dropped only after `f` succeeds, and is returned as part of the error otherwise,
+/// with its original nonblocking mode restored.
+pub(crate) fn convert_via_dup<S: AsFd, T>(
+    src: S,
+    nonblocking: bool,
+    f: impl FnOnce(OwnedFd) -> io::Result<T>,
+) -> Result<T, ConversionError<S>> {
+    let fd = src.as_fd();
+    let prepare = || {
+        let was_nonblocking = c_wrappers::get_nonblocking(fd)?;
+        let dup = c_wrappers::duplicate_fd(fd)?;
+        c_wrappers::set_nonblocking(dup.as_fd(), nonblocking)?;
+        Ok((dup, was_nonblocking))
+    };
+    let (dup, was_nonblocking) = match prepare() {
+        Ok(ok) => ok,
+        Err(e) => return Err(ConversionError::from_source_and_cause(src, e)),
+    };
+    match f(dup) {
+        Ok(t) => Ok(t),
+        Err(e) => {
+            // Nonblocking mode is shared with the duplicate
+            let _ = c_wrappers::set_nonblocking(src.as_fd(), was_nonblocking);
+            Err(ConversionError::from_source_and_cause(src, e))
+        }
+    }
+}
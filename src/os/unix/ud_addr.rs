@@ -1,5 +1,5 @@
 use {
-    crate::{os::unix::unixprelude::*, weaken_nonzero_slice},
+    crate::{local_socket::NameError, os::unix::unixprelude::*, weaken_nonzero_slice},
     libc::sockaddr_un,
     std::{
         ffi::CStr,
@@ -14,11 +14,8 @@ use {
 
 #[cold]
 #[inline(never)]
-pub(super) fn name_too_long() -> io::Error {
-    io::Error::new(
-        io::ErrorKind::InvalidInput,
-        "local socket name length exceeds capacity of sun_path of sockaddr_un",
-    )
+pub(super) fn name_too_long(max: usize, actual: usize) -> io::Error {
+    NameError::TooLong { max, actual }.into()
 }
 
 pub(super) const SUN_LEN: usize = {
@@ -95,6 +92,10 @@ impl UdAddr {
         unsafe { slice::from_raw_parts_mut(self.path_ptr_mut(), self.len()) }
     }
 
+    /// Returns a mutable pointer to the `sockaddr_un` structure that can be passed to
+    /// `getsockname` or `getpeername`.
+    pub fn addr_ptr_mut(&mut self) -> *mut sockaddr_un { self.sun.as_mut_ptr() }
+
     /// Returns a pointer to the `sockaddr_un` structure that can be passed to `bind`.
     ///
     /// Before passing this pointer to `bind`, [`write_terminator`](Self::write_terminator)
@@ -163,11 +164,11 @@ impl UdAddr {
         } else {
             self.terminator = MaybeUninit::new(0);
         }
-        TerminatedUdAddr(self)
+        TerminatedUdAddr { addr: self, full_path: None }
     }
 
     fn check_path_length(len: usize) -> io::Result<()> {
-        let true = len <= SUN_LEN else { return Err(name_too_long()) };
+        let true = len <= SUN_LEN else { return Err(name_too_long(SUN_LEN, len)) };
         Ok(())
     }
     unsafe fn write_slice(&mut self, off: usize, s: &[u8]) {
@@ -180,31 +181,56 @@ impl UdAddr {
         unsafe { self.push_slice(path) };
         Ok(())
     }
-    /// Initializes from an abstract namespace name.
+    /// Initializes from an abstract namespace name, which may contain nuls.
     #[allow(dead_code, clippy::arithmetic_side_effects)]
-    pub fn init_namespaced(&mut self, nsname: &[NonZeroU8]) -> io::Result<()> {
+    pub fn init_namespaced(&mut self, nsname: &[u8]) -> io::Result<()> {
         // Cannot overflow, as the length of slices always fits into an isize
         Self::check_path_length(nsname.len() + 1)?;
         unsafe { self.path_ptr_mut().write(0) };
         self.len = 1;
-        unsafe { self.push_slice(nsname) };
+        unsafe { self.push_slice_with_nuls(nsname) };
         Ok(())
     }
+    /// Sets the [initialized length](Self::len) from an address length returned by
+    /// `getsockname` or `getpeername`.
+    ///
+    /// # Safety
+    /// The address structure must have been filled in by the system call that returned
+    /// `addrlen`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub unsafe fn set_addrlen(&mut self, addrlen: socklen_t) {
+        let len = (addrlen as usize).saturating_sub(PATH_OFFSET).min(SUN_LEN);
+        unsafe { self.set_len(len) };
+    }
 }
 
 /// Reference to a [`UdAddr`] that is known to be nul-terminated.
 #[derive(Copy, Clone)]
-pub(super) struct TerminatedUdAddr<'a>(&'a UdAddr);
+pub(super) struct TerminatedUdAddr<'a> {
+    addr: &'a UdAddr,
+    /// The path the address stands in for, if it is a shortened alias that is only valid
+    /// temporarily.
+    full_path: Option<&'a CStr>,
+}
 impl<'a> TerminatedUdAddr<'a> {
     /// Grants read-only access to the [`UdAddr`].
-    pub const fn inner(self) -> &'a UdAddr { self.0 }
+    pub const fn inner(self) -> &'a UdAddr { self.addr }
     /// Immutably borrows the path as a nul-terminated C string.
-    pub fn path(&self) -> &CStr {
+    pub fn path(&self) -> &'a CStr {
         // SAFETY: the nul terminator is either in sun_path or immediately follows it
-        unsafe { CStr::from_ptr(self.0.path_ptr().cast()) }
+        unsafe { CStr::from_ptr(self.addr.path_ptr().cast()) }
+    }
+    /// Marks the address as a temporary alias for the given path.
+    pub fn with_full_path(self, full_path: &'a CStr) -> Self {
+        Self { full_path: Some(full_path), ..self }
     }
+    /// Returns `true` if the address is a temporary alias for a [full path](Self::full_path).
+    pub fn is_alias(&self) -> bool { self.full_path.is_some() }
+    /// Returns the path the address stands in for, which remains valid after the address has
+    /// been used. This is the same as [`path`](Self::path) unless the address is an alias.
+    pub fn full_path(&self) -> &'a CStr { self.full_path.unwrap_or_else(|| self.path()) }
 }
 impl Deref for TerminatedUdAddr<'_> {
     type Target = UdAddr;
-    fn deref(&self) -> &UdAddr { self.0 }
+    fn deref(&self) -> &UdAddr { self.addr }
 }
@@ -1,9 +1,18 @@
 //! Local sockets implemented using Unix domain sockets.
 
 mod listener;
+mod shutdown;
 mod stream;
 
-pub use {listener::*, stream::*};
+pub use {listener::*, shutdown::*, stream::*};
+
+/// Async local sockets for async-io implemented using Unix domain sockets.
+#[cfg(feature = "async")]
+pub mod async_io {
+    mod listener;
+    mod stream;
+    pub use {listener::*, stream::*};
+}
 
 /// Async Local sockets for Tokio implemented using Unix domain sockets.
 #[cfg(feature = "tokio")]
@@ -16,7 +25,7 @@ pub mod tokio {
 use {
     crate::{
         assume_nonzero_slice, check_nonzero_slice,
-        local_socket::{ListenerOptions, Name, NameInner},
+        local_socket::{ListenerOptions, Name, NameError, NameInner},
         os::unix::{
             c_wrappers,
             ud_addr::{name_too_long, TerminatedUdAddr, UdAddr, SUN_LEN},
@@ -25,11 +34,12 @@ use {
         timeout_expiry,
     },
     std::{
-        ffi::{CStr, OsStr},
-        fmt::{self, Debug, Formatter},
-        io,
+        borrow::Cow,
+        ffi::{CStr, CString, OsStr},
+        fs, io,
         mem::MaybeUninit,
         num::NonZeroU8,
+        os::unix::fs::{DirBuilderExt as _, PermissionsExt as _},
         path::Path,
         time::{Duration, Instant},
     },
@@ -37,48 +47,6 @@ use {
 
 const CONN_TIMEOUT_MSG: &str = "timed out while connecting to local socket server";
 
-/// Performs name reclamation when dropped.
-#[derive(Clone, Default)]
-struct ReclaimGuard(Box<[u8]>);
-impl ReclaimGuard {
-    fn disarmed() -> Self { Self(Box::new([])) }
-    /// Creates a reclamation guard for the given address. If `cond` is false, creates a disarmed
-    /// guard instead.
-    fn new(cond: bool, addr: TerminatedUdAddr<'_>) -> Self {
-        if !cond
-            || addr.inner().path().is_empty()
-            || cfg!(any(target_os = "linux", target_os = "android"))
-                && addr.inner().path().first() == Some(&0)
-        {
-            return Self::disarmed();
-        }
-        Self(addr.path().to_owned().into_bytes_with_nul().into_boxed_slice())
-    }
-    /// Takes ownership of the reclaim guard, leaving a disarmed one in place.
-    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
-    fn take(&mut self) -> Self { Self(std::mem::take(&mut self.0)) }
-    /// Disarms the reclaim guard. It will not do anything when dropped.
-    fn forget(&mut self) { self.0 = Box::new([]); }
-    fn as_c_str(&self) -> Option<&CStr> {
-        // SAFETY: the only constructor that produces a non-empty one gets
-        //         it from into_bytes_with_nul
-        (!self.0.is_empty()).then(|| unsafe { CStr::from_bytes_with_nul_unchecked(&self.0) })
-    }
-}
-impl Drop for ReclaimGuard {
-    fn drop(&mut self) {
-        if let Some(s) = self.as_c_str() {
-            let _ = c_wrappers::unlink(s);
-        }
-    }
-}
-impl Debug for ReclaimGuard {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let s = self.as_c_str().map(|s| OsStr::from_bytes(s.to_bytes()));
-        f.debug_tuple("ReclaimGuard").field(&s).finish()
-    }
-}
-
 /// Calls the given listener closure using `dispatch_name` to try every applicable path.
 /// If `try_overwrite` is enabled, this is repeated in a loop for every path offered by
 /// `dispatch_name` for as long as listener creation fails with `AddrInUse` with attempts to
@@ -93,12 +61,16 @@ fn listen_and_maybe_overwrite<T>(
     mut listen: impl FnMut(TerminatedUdAddr<'_>, &mut ListenerOptions<'_>) -> io::Result<T>,
 ) -> io::Result<T> {
     let end = opts.get_max_spin_time().map(timeout_expiry).transpose()?;
+    let dir_mode = opts.get_dir_mode();
     dispatch_name(
         &mut opts,
-        true,
+        Some(dir_mode),
         |opts| opts.name.borrow(),
         |opts| opts.get_max_spin_time_mut(),
         |addr, opts| {
+            if opts.get_verify_parent_dir() {
+                verify_parent_dir(addr)?;
+            }
             let mut first = true;
             loop {
                 let err = match listen(addr, opts) {
@@ -127,17 +99,51 @@ fn keep_trying_to_overwrite(e: &io::Error, options: &ListenerOptions<'_>) -> boo
     options.get_try_overwrite() && e.kind() == io::ErrorKind::AddrInUse
 }
 
+/// Converts an address returned by `getsockname` or `getpeername` to a local socket name, or
+/// returns `None` if the socket is unnamed.
+fn addr_to_name(addr: &UdAddr) -> Option<Name<'static>> {
+    let path = addr.path();
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    if let Some((0, nsname)) = path.split_first() {
+        return Some(Name(NameInner::UdSocketNs(Cow::Owned(nsname.to_vec()))));
+    }
+    // The returned length may or may not include the nul terminator.
+    let path = path.split(|&b| b == 0).next().unwrap_or_default();
+    (!path.is_empty())
+        .then(|| Name(NameInner::UdSocketPath(Cow::Owned(OsStr::from_bytes(path).to_owned()))))
+}
+
+/// Retrieves the name a listener is bound to. `long_path` is the path the listener was bound to if
+/// it was bound through a [`LongPathAlias`], since `getsockname` would then return the alias.
+fn listener_name(fd: BorrowedFd<'_>, long_path: Option<&CStr>) -> io::Result<Name<'static>> {
+    if let Some(path) = long_path {
+        let path = OsStr::from_bytes(path.to_bytes()).to_owned();
+        return Ok(Name(NameInner::UdSocketPath(Cow::Owned(path))));
+    }
+    addr_to_name(&c_wrappers::getsockname(fd)?).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::AddrNotAvailable, "socket is not bound to a name")
+    })
+}
+
+/// Retrieves the name of the local or remote end of a connected stream.
+fn stream_name(fd: BorrowedFd<'_>, peer: bool) -> io::Result<Option<Name<'static>>> {
+    let addr = if peer { c_wrappers::getpeername(fd)? } else { c_wrappers::getsockname(fd)? };
+    Ok(addr_to_name(&addr))
+}
+
 fn check_no_nul(s: &[u8]) -> io::Result<&[NonZeroU8]> {
-    let msg = "interior nul bytes are not allowed inside Unix domain socket names";
-    check_nonzero_slice(s).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, msg))
+    check_nonzero_slice(s).ok_or_else(|| {
+        NameError::InteriorNul(s.iter().position(|&b| b == 0).unwrap_or_default()).into()
+    })
 }
 
 /// Calls the given closure once for every applicable Unix domain socket address corresponding to
-/// the given local socket name. If `create_dirs` is true and the closure returns a
-/// ["benign"](fail_is_benign) error, missing directories are created and the call is retried.
+/// the given local socket name. If `create_dirs` is `Some` and the closure returns a
+/// ["benign"](fail_is_benign) error, missing directories are created with the given mode (or the
+/// default one if `None`) and the call is retried.
 fn dispatch_name<O, T>(
     o: &mut O,
-    create_dirs: bool,
+    create_dirs: Option<Option<mode_t>>,
     mut get_name: impl FnMut(&mut O) -> Name<'_>,
     mut max_spin_time: impl FnMut(&mut O) -> Option<&mut Duration>,
     mut create: impl FnMut(TerminatedUdAddr<'_>, &mut O) -> io::Result<T>,
@@ -145,8 +151,14 @@ fn dispatch_name<O, T>(
     let mut addr = UdAddr::new();
     match get_name(o).0 {
         NameInner::UdSocketPath(path) => {
-            addr.init(check_no_nul(path.as_bytes())?)?;
-            create(addr.write_terminator(), o)
+            let path = check_no_nul(path.as_bytes())?;
+            if path.len() <= SUN_LEN {
+                addr.init(path)?;
+                return create(addr.write_terminator(), o);
+            }
+            // The directory file descriptor is closed once the alias is dropped.
+            let alias = LongPathAlias::new(&mut addr, path)?;
+            create(alias.apply(addr.write_terminator()), o)
         }
 
         NameInner::UdSocketPseudoNs(name) => {
@@ -177,17 +189,66 @@ fn dispatch_name<O, T>(
 
         #[cfg(any(target_os = "linux", target_os = "android"))]
         NameInner::UdSocketNs(name) => {
-            addr.init_namespaced(check_no_nul(&name)?)?;
+            addr.init_namespaced(&name)?;
             create(addr.write_terminator(), o)
         }
+
+        NameInner::Tcp(..) => Err(NameError::Unsupported.into()),
+    }
+}
+
+/// Temporary alias for a path which is too long to fit into `sun_path`, going through
+/// `/proc/self/fd/` with an open file descriptor of the parent directory.
+struct LongPathAlias {
+    full_path: CString,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    _dirfd: OwnedFd,
+}
+impl LongPathAlias {
+    /// Opens the parent directory and writes the alias into `addr`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn new(addr: &mut UdAddr, path: &[NonZeroU8]) -> io::Result<Self> {
+        let full_path = CString::new(crate::weaken_nonzero_slice(path))?;
+        let path = Path::new(OsStr::from_bytes(full_path.to_bytes()));
+        let (Some(dir), Some(file)) = (path.parent(), path.file_name()) else {
+            return Err(name_too_long(SUN_LEN, full_path.as_bytes().len()));
+        };
+        let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        let dirfd = c_wrappers::open(
+            &CString::new(dir.as_os_str().as_bytes())?,
+            libc::O_PATH | libc::O_DIRECTORY,
+        )?;
+
+        let pfx = format!("/proc/self/fd/{}/", dirfd.as_raw_fd());
+        if pfx.len() + file.len() > SUN_LEN {
+            return Err(name_too_long(SUN_LEN.saturating_sub(pfx.len()), file.len()));
+        }
+        addr.reset_len();
+        // SAFETY: the prefix is made of ASCII digits and slashes, the file name is a part of a
+        // path that has been checked for nuls, and the bounds check is right above
+        unsafe {
+            addr.push_slice(assume_nonzero_slice(pfx.as_bytes()));
+            addr.push_slice(assume_nonzero_slice(file.as_bytes()));
+        }
+        Ok(Self { full_path, _dirfd: dirfd })
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn new(_: &mut UdAddr, path: &[NonZeroU8]) -> io::Result<Self> {
+        Err(name_too_long(SUN_LEN, path.len()))
+    }
+
+    /// Marks the terminated address as an alias of the full path.
+    fn apply<'a>(&'a self, addr: TerminatedUdAddr<'a>) -> TerminatedUdAddr<'a> {
+        addr.with_full_path(&self.full_path)
     }
 }
 
 /// Calls the given listener creation closure while handling failure by attempting to
-/// [create missing directories](create_missing_dirs) if `create` is true.
+/// [create missing directories](create_missing_dirs) with the given mode if `create` is `Some`.
 fn with_missing_dir_creat<O, T>(
     options: &mut O,
-    create: bool,
+    create: Option<Option<mode_t>>,
     addr: TerminatedUdAddr<'_>,
     mut max_spin_time: impl FnMut(&mut O) -> Option<&mut Duration>,
     mut f: impl FnMut(TerminatedUdAddr<'_>, &mut O) -> io::Result<T>,
@@ -195,31 +256,89 @@ fn with_missing_dir_creat<O, T>(
     let end = max_spin_time(options).copied().map(timeout_expiry).transpose()?;
     let mut first = true;
     loop {
-        let err = match f(addr, options) {
-            Err(e) if create && fail_is_benign(&e) => e,
-            otherwise => return otherwise,
+        let (err, mode) = match (f(addr, options), create) {
+            (Err(e), Some(mode)) if fail_is_benign(&e) => (e, mode),
+            (otherwise, _) => return otherwise,
         };
         if !continue_spin_loop(end, max_spin_time(options)) && !first {
             break Err(err);
         }
         first = false;
-        create_missing_dirs(addr).then_some(()).ok_or(err)?;
+        create_missing_dirs(addr, mode).then_some(()).ok_or(err)?;
     }
 }
 
 /// Makes it so that attempting to bind to the given address does not `ENOENT` assuming lack of
 /// an asshole that races us and `rmdir`s what we've just created. Returns `false` in case of
 /// failure.
-fn create_missing_dirs(addr: TerminatedUdAddr<'_>) -> bool {
+fn create_missing_dirs(addr: TerminatedUdAddr<'_>, mode: Option<mode_t>) -> bool {
     let path = Path::new(OsStr::from_bytes(addr.inner().path()));
     // This is the reason we erase the error
     let Some(dir) = path.parent() else { return false };
     let false = dir.as_os_str().is_empty() else { return false };
-    match std::fs::create_dir_all(dir) {
-        Ok(()) => true,
-        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => true,
-        Err(..) => false,
+    create_dir_all(dir, mode).is_ok()
+}
+
+/// Like `std::fs::create_dir_all`, but creates directories with the given mode, if any. The mode
+/// is applied exactly, regardless of the umask, but only to the directories that are created by
+/// this call.
+fn create_dir_all(dir: &Path, mode: Option<mode_t>) -> io::Result<()> {
+    let create = || fs::DirBuilder::new().mode(mode.unwrap_or(0o777)).create(dir);
+    match create() {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => return Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let Some(parent) = dir.parent().filter(|p| !p.as_os_str().is_empty()) else {
+                return Err(e);
+            };
+            create_dir_all(parent, mode)?;
+            match create() {
+                Ok(()) => {}
+                // Someone else created it in the meantime, making it not ours to chmod.
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(e) => return Err(e),
+    }
+    if let Some(mode) = mode {
+        // The umask may have left the directory with fewer permissions than requested.
+        fs::set_permissions(dir, fs::Permissions::from_mode(mode))?;
+    }
+    Ok(())
+}
+
+/// Checks that the directory the socket at the given address is to be created in is owned by the
+/// effective user or root and is not writable by anyone else, returning a `PermissionDenied` error
+/// otherwise.
+fn verify_parent_dir(addr: TerminatedUdAddr<'_>) -> io::Result<()> {
+    let path = Path::new(OsStr::from_bytes(addr.full_path().to_bytes()));
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    if addr.inner().path().first() == Some(&0) {
+        // Abstract namespace names do not reside in a directory.
+        return Ok(());
+    }
+    verify_parent_dir_of(path)
+}
+/// Checks that the directory the file at the given path is to be created in is owned by the
+/// effective user or root and is not writable by anyone else, returning a `PermissionDenied` error
+/// otherwise.
+pub(crate) fn verify_parent_dir_of(path: &Path) -> io::Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let meta = fs::metadata(dir)?;
+    // SAFETY: always safe
+    let euid = unsafe { libc::geteuid() };
+    if !matches!(meta.uid(), 0) && meta.uid() != euid || meta.mode() & 0o022 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "local socket parent directory is not owned by the current user or root, or is \
+            writable by other users",
+        ));
     }
+    Ok(())
 }
 
 /// Updates `spin_time` with the amount of time remaining until `end` is reached. If `end` has
@@ -246,7 +365,7 @@ fn write_run_user(addr: &mut UdAddr, name: &[u8]) -> io::Result<()> {
     // improves robustness of programs by preventing reliance on the
     // UID being small
     if name.len() > NMCAP {
-        return Err(name_too_long());
+        return Err(name_too_long(NMCAP, name.len()));
     }
     addr.reset_len();
     // SAFETY: proof by look at it
@@ -285,7 +404,7 @@ fn write_run_user(addr: &mut UdAddr, name: &[u8]) -> io::Result<()> {
 #[allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
 fn write_prefixed(addr: &mut UdAddr, pfx: &[NonZeroU8], name: &[u8]) -> io::Result<()> {
     if pfx.len() + name.len() > SUN_LEN {
-        return Err(name_too_long());
+        return Err(name_too_long(SUN_LEN.saturating_sub(pfx.len()), name.len()));
     }
     let name = check_no_nul(name)?;
     addr.reset_len();
@@ -0,0 +1,101 @@
+use {
+    super::Stream,
+    crate::{
+        local_socket::{
+            prelude::*, traits::async_io as traits, ListenerNonblockingMode, ListenerOptions,
+            Name,
+        },
+        os::unix::{
+            reclaim_guard::ReclaimGuard,
+            uds_local_socket::{listener::Listener as SyncListener, listener_name},
+        },
+        Sealed,
+    },
+    ::async_io::Async,
+    std::{
+        ffi::CString,
+        fmt::{self, Debug, Formatter},
+        io,
+        os::unix::{net::UnixListener, prelude::*},
+    },
+};
+
+/// Wrapper around [`UnixListener`] registered with the async-io reactor that implements
+/// [`Listener`](traits::Listener).
+pub struct Listener {
+    listener: Async<UnixListener>,
+    reclaim: ReclaimGuard,
+    long_path: Option<CString>,
+}
+impl Sealed for Listener {}
+impl traits::Listener for Listener {
+    type Stream = Stream;
+
+    fn from_options(options: ListenerOptions<'_>) -> io::Result<Self> {
+        options
+            .nonblocking(ListenerNonblockingMode::Both)
+            .create_sync_as::<SyncListener>()
+            .and_then(Self::from_nonblocking_sync)
+    }
+    async fn accept(&self) -> io::Result<Stream> {
+        let inner = self.listener.accept().await?.0;
+        Ok(Stream::from(inner))
+    }
+
+    fn do_not_reclaim_name_on_drop(&mut self) { self.reclaim.forget(); }
+    fn local_name(&self) -> io::Result<Name<'static>> {
+        listener_name(self.as_fd(), self.long_path.as_deref())
+    }
+}
+impl Listener {
+    fn from_nonblocking_sync(mut sync: SyncListener) -> io::Result<Self> {
+        let reclaim = sync.reclaim.take();
+        let long_path = sync.long_path.take();
+        Ok(Self { listener: Async::new(UnixListener::from(sync))?, reclaim, long_path })
+    }
+}
+
+/// Access to the underlying implementation.
+impl Listener {
+    /// Borrows the [`Async`]-wrapped [`UnixListener`] contained within, granting access to
+    /// operations defined on it.
+    #[inline(always)]
+    pub fn inner(&self) -> &Async<UnixListener> { &self.listener }
+}
+
+/// Sets the sync `Listener` to `ListenerNonblockingMode::Both`.
+impl TryFrom<SyncListener> for Listener {
+    type Error = io::Error;
+    fn try_from(sync: SyncListener) -> io::Result<Self> {
+        sync.set_nonblocking(ListenerNonblockingMode::Both)?;
+        Self::from_nonblocking_sync(sync)
+    }
+}
+
+impl Debug for Listener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Listener")
+            .field("fd", &self.listener.as_raw_fd())
+            .field("reclaim", &self.reclaim)
+            .field("long_path", &self.long_path)
+            .finish()
+    }
+}
+impl AsFd for Listener {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> { self.listener.as_fd() }
+}
+impl TryFrom<Listener> for OwnedFd {
+    type Error = io::Error;
+    fn try_from(mut slf: Listener) -> io::Result<Self> {
+        slf.listener.into_inner().map(|s| {
+            slf.reclaim.forget();
+            s.into()
+        })
+    }
+}
+/// Sets the listener to `ListenerNonblockingMode::Both`.
+impl TryFrom<OwnedFd> for Listener {
+    type Error = io::Error;
+    fn try_from(fd: OwnedFd) -> io::Result<Self> { Self::try_from(SyncListener::from(fd)) }
+}
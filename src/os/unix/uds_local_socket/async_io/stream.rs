@@ -0,0 +1,178 @@
+use {
+    super::super::{dispatch_name, stream_name, CONN_TIMEOUT_MSG},
+    crate::{
+        error::ReuniteError,
+        local_socket::{
+            traits::{async_io as traits, StreamCommon},
+            ConnectOptions, Name, PeerCreds,
+        },
+        os::unix::{c_wrappers, local_socket::peer_creds::PeerCreds as PeerCredsInner},
+        ConnectWaitMode, Sealed,
+    },
+    ::async_io::{Async, Timer},
+    std::{
+        future::{poll_fn, Future},
+        io,
+        os::unix::{net::UnixStream, prelude::*},
+        pin::{pin, Pin},
+        sync::Arc,
+        task::Poll,
+        time::Duration,
+    },
+};
+
+/// Wrapper around [`UnixStream`] registered with the async-io reactor that implements
+/// [`Stream`](traits::Stream).
+#[derive(Debug)]
+pub struct Stream(pub(super) Async<UnixStream>);
+impl Sealed for Stream {}
+
+impl traits::Stream for Stream {
+    type RecvHalf = RecvHalf;
+    type SendHalf = SendHalf;
+
+    async fn from_options(mut opts: &ConnectOptions<'_>) -> io::Result<Self> {
+        let (sock, inprog) = dispatch_name(
+            &mut opts,
+            None,
+            |&mut opts| opts.name.borrow(),
+            |_| None,
+            |addr, _| c_wrappers::create_client(addr, true),
+        )?;
+        let sock = Async::new(UnixStream::from(sock))?;
+        if inprog {
+            match opts.get_wait_mode() {
+                ConnectWaitMode::Deferred => {}
+                ConnectWaitMode::Timeout(timeout) => wait_for_connect(&sock, timeout).await?,
+                ConnectWaitMode::Unbounded => sock.writable().await?,
+            }
+            if let Some(e) = c_wrappers::take_error(sock.as_fd())? {
+                return Err(e);
+            }
+        }
+        Ok(Self(sock))
+    }
+    #[inline]
+    fn split(self) -> (RecvHalf, SendHalf) {
+        let arc = Arc::new(self);
+        (RecvHalf(Arc::clone(&arc)), SendHalf(arc))
+    }
+    #[inline]
+    #[allow(clippy::unwrap_in_result)]
+    fn reunite(rh: RecvHalf, sh: SendHalf) -> Result<Self, ReuniteError<RecvHalf, SendHalf>> {
+        if !Arc::ptr_eq(&rh.0, &sh.0) {
+            return Err(ReuniteError { rh, sh });
+        }
+        drop(rh);
+        let inner = Arc::into_inner(sh.0).expect("stream half inexplicably copied");
+        Ok(inner)
+    }
+}
+impl StreamCommon for Stream {
+    #[inline]
+    fn take_error(&self) -> io::Result<Option<io::Error>> { c_wrappers::take_error(self.as_fd()) }
+    #[inline]
+    fn peer_creds(&self) -> io::Result<PeerCreds> {
+        PeerCredsInner::for_socket(self.as_fd()).map(From::from)
+    }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> { stream_name(self.as_fd(), false) }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> { stream_name(self.as_fd(), true) }
+}
+
+/// Waits for the socket to become writable, which is when the connection is established, or for
+/// the timeout to expire, whichever comes first.
+async fn wait_for_connect(sock: &Async<UnixStream>, timeout: Duration) -> io::Result<()> {
+    let mut writable = pin!(sock.writable());
+    let mut timer = Timer::after(timeout);
+    poll_fn(|cx| {
+        if let Poll::Ready(rslt) = writable.as_mut().poll(cx) {
+            return Poll::Ready(rslt);
+        }
+        Pin::new(&mut timer)
+            .poll(cx)
+            .map(|_| Err(io::Error::new(io::ErrorKind::TimedOut, CONN_TIMEOUT_MSG)))
+    })
+    .await
+}
+
+/// Access to the underlying implementation.
+impl Stream {
+    /// Borrows the [`Async`]-wrapped [`UnixStream`] contained within, granting access to
+    /// operations defined on it.
+    #[inline(always)]
+    pub fn inner(&self) -> &Async<UnixStream> { &self.0 }
+}
+
+impl From<Async<UnixStream>> for Stream {
+    #[inline]
+    fn from(s: Async<UnixStream>) -> Self { Self(s) }
+}
+impl AsFd for Stream {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> { self.0.as_fd() }
+}
+impl TryFrom<Stream> for OwnedFd {
+    type Error = io::Error;
+    #[inline]
+    fn try_from(slf: Stream) -> io::Result<Self> { Ok(slf.0.into_inner()?.into()) }
+}
+impl TryFrom<OwnedFd> for Stream {
+    type Error = io::Error;
+    #[inline]
+    fn try_from(fd: OwnedFd) -> io::Result<Self> { Ok(Async::new(UnixStream::from(fd))?.into()) }
+}
+
+multimacro! {
+    Stream,
+    forward_rbv(Async<UnixStream>, &),
+    forward_futures_ref_rw,
+    derive_futures_mut_rw,
+}
+
+macro_rules! arc_accessors {
+    ($ty:ty) => {
+        /// [`Arc`] accessors.
+        impl $ty {
+            /// Borrows the [`Stream`] within the `Arc`.
+            #[inline]
+            pub fn as_stream(&self) -> &Stream { &self.0 }
+            /// Extracts the underlying `Arc<Stream>`.
+            #[inline]
+            pub fn into_arc(self) -> Arc<Stream> { self.0 }
+        }
+    };
+}
+
+/// [`Stream`]'s receive half, implemented using [`Arc`].
+#[derive(Clone, Debug)]
+pub struct RecvHalf(Arc<Stream>);
+impl Sealed for RecvHalf {}
+multimacro! {
+    RecvHalf,
+    forward_rbv(Stream, *),
+    arc_accessors,
+    forward_futures_ref_read,
+    forward_as_handle(unix),
+    derive_futures_mut_read,
+}
+impl traits::RecvHalf for RecvHalf {
+    type Stream = Stream;
+}
+
+/// [`Stream`]'s send half, implemented using [`Arc`].
+#[derive(Clone, Debug)]
+pub struct SendHalf(Arc<Stream>);
+impl Sealed for SendHalf {}
+multimacro! {
+    SendHalf,
+    forward_rbv(Stream, *),
+    arc_accessors,
+    forward_futures_ref_write,
+    forward_as_handle(unix),
+    derive_futures_mut_write,
+}
+impl traits::SendHalf for SendHalf {
+    type Stream = Stream;
+}
@@ -1,20 +1,33 @@
 use {
-    super::{listen_and_maybe_overwrite, ReclaimGuard, Stream},
+    super::{
+        listen_and_maybe_overwrite, listener_name, ListenerShutdown, Shutdown, ShutdownHandle,
+        Stream,
+    },
     crate::{
-        local_socket::{traits, ListenerNonblockingMode, ListenerOptions},
-        os::unix::c_wrappers,
+        local_socket::{traits, ListenerNonblockingMode, ListenerOptions, Name},
+        os::unix::{
+            c_wrappers,
+            local_socket::{accept_timed_out, ListenerExt},
+            reclaim_guard::ReclaimGuard,
+            ud_addr::UdAddr,
+        },
     },
     std::{
+        ffi::CString,
         io,
         iter::FusedIterator,
         os::{
-            fd::{AsFd, BorrowedFd, OwnedFd},
+            fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd},
             unix::net::UnixListener,
         },
-        sync::atomic::{
-            AtomicBool,
-            Ordering::{Acquire, Release},
+        sync::{
+            atomic::{
+                AtomicBool,
+                Ordering::{Acquire, Release},
+            },
+            Arc, OnceLock,
         },
+        time::{Duration, Instant},
     },
 };
 
@@ -24,6 +37,8 @@ pub struct Listener {
     pub(super) listener: UnixListener,
     pub(super) reclaim: ReclaimGuard,
     pub(super) nonblocking_streams: AtomicBool,
+    pub(super) long_path: Option<CString>,
+    pub(super) shutdown: OnceLock<Arc<Shutdown>>,
 }
 impl crate::Sealed for Listener {}
 impl traits::Listener for Listener {
@@ -31,31 +46,49 @@ impl traits::Listener for Listener {
 
     fn from_options(opts: ListenerOptions<'_>) -> io::Result<Self> {
         let mut reclaim = ReclaimGuard::default();
+        let mut long_path = None;
         let nonblocking_streams = AtomicBool::new(opts.get_nonblocking_stream());
-        Ok(Self {
-            listener: listen_and_maybe_overwrite(opts, |addr, opts| {
+        let listener = if opts.get_autobind() {
+            // An address consisting of only the address family makes the kernel pick a name.
+            c_wrappers::create_listener(
+                libc::SOCK_STREAM,
+                UdAddr::new().write_terminator(),
+                opts.get_nonblocking_accept(),
+                opts.get_mode(),
+                opts.get_owner(),
+                opts.get_group(),
+            )?
+        } else {
+            listen_and_maybe_overwrite(opts, |addr, opts| {
                 let rslt = c_wrappers::create_listener(
                     libc::SOCK_STREAM,
                     addr,
                     opts.get_nonblocking_accept(),
                     opts.get_mode(),
+                    opts.get_owner(),
+                    opts.get_group(),
                 )?;
                 reclaim = ReclaimGuard::new(opts.get_reclaim_name(), addr);
+                long_path = addr.is_alias().then(|| addr.full_path().to_owned());
                 Ok(rslt)
-            })
-            .map(UnixListener::from)?,
+            })?
+        };
+        Ok(Self {
+            listener: listener.into(),
             reclaim,
             nonblocking_streams,
+            long_path,
+            shutdown: OnceLock::new(),
         })
     }
     #[inline]
     fn accept(&self) -> io::Result<Stream> {
-        // TODO do our own accept4 and pass SOCK_NONBLOCK on supported platforms
-        let stream = self.listener.accept().map(|(s, _)| Stream::from(s))?;
-        if self.nonblocking_streams.load(Acquire) {
-            c_wrappers::fast_set_nonblocking(stream.as_fd(), true)?;
+        if self.shutdown.get().is_some() {
+            // In nonblocking mode, the deadline is now, and accept() reports the lack of clients
+            let nonblocking = c_wrappers::get_nonblocking(self.as_fd())?;
+            self.wait(nonblocking.then(Instant::now))?;
         }
-        Ok(stream)
+        self.accept_now()
     }
     #[inline]
     fn set_nonblocking(&self, nonblocking: ListenerNonblockingMode) -> io::Result<()> {
@@ -65,6 +98,9 @@ impl traits::Listener for Listener {
         Ok(())
     }
     fn do_not_reclaim_name_on_drop(&mut self) { self.reclaim.forget(); }
+    fn local_name(&self) -> io::Result<Name<'static>> {
+        listener_name(self.as_fd(), self.long_path.as_deref())
+    }
 }
 impl Iterator for Listener {
     type Item = io::Result<Stream>;
@@ -81,6 +117,85 @@ impl Listener {
     pub fn set_new_stream_nonblocking(&self, nonblocking: bool) {
         self.nonblocking_streams.store(nonblocking, Release);
     }
+    /// Returns a [`ShutdownHandle`] that can be used to make pending and future
+    /// [`accept()`](traits::Listener::accept) calls on this listener fail, e.g. from another
+    /// thread or a signal handling routine.
+    ///
+    /// The first call allocates a pipe, which is polled alongside the listener from then on.
+    /// Every call returns a handle to the same pipe.
+    ///
+    /// # Implementation notes
+    /// If multiple threads call `accept()` concurrently, a thread that loses the race for a
+    /// connection it was woken up for blocks until the next one arrives, regardless of the
+    /// shutdown. Having one thread accept connections and dispatch them to others avoids this.
+    ///
+    /// ## System calls
+    /// - `pipe2` (Linux, Android)
+    /// - `pipe` (others)
+    pub fn shutdown_handle(&self) -> io::Result<ShutdownHandle> {
+        let shutdown = match self.shutdown.get() {
+            Some(shutdown) => shutdown,
+            None => {
+                let new = Arc::new(Shutdown::new()?);
+                self.shutdown.get_or_init(|| new)
+            }
+        };
+        Ok(ShutdownHandle(Arc::clone(shutdown)))
+    }
+
+    fn accept_now(&self) -> io::Result<Stream> {
+        // TODO do our own accept4 and pass SOCK_NONBLOCK on supported platforms
+        let stream = self.listener.accept().map(|(s, _)| Stream::from(s))?;
+        if self.nonblocking_streams.load(Acquire) {
+            c_wrappers::fast_set_nonblocking(stream.as_fd(), true)?;
+        }
+        Ok(stream)
+    }
+    /// Polls the listener, along with the [shutdown](ShutdownHandle) pipe if there is one. Returns
+    /// `true` when a client is ready to be accepted, `false` if the deadline passes first, and an
+    /// error if the listener is shut down.
+    fn wait(&self, deadline: Option<Instant>) -> io::Result<bool> {
+        let mut fds = [
+            libc::pollfd { fd: self.as_fd().as_raw_fd(), events: libc::POLLIN, revents: 0 },
+            // Negative file descriptors are ignored by poll
+            self.shutdown
+                .get()
+                .map_or(libc::pollfd { fd: -1, events: 0, revents: 0 }, |s| s.pollfd()),
+        ];
+        loop {
+            let timeout = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+            c_wrappers::poll_fds(&mut fds, timeout)?;
+            let [listener, shutdown] = &fds;
+            if shutdown.revents != 0 {
+                return Err(io::Error::other(ListenerShutdown));
+            }
+            if listener.revents != 0 {
+                return Ok(true);
+            }
+            // Zero revents before the deadline means that poll was interrupted by a signal
+            if timeout == Some(Duration::ZERO) || deadline.is_some_and(|d| Instant::now() >= d) {
+                return Ok(false);
+            }
+        }
+    }
+}
+
+impl ListenerExt for Listener {
+    /// ## System calls
+    /// - `poll`/`ppoll`
+    /// - `accept`
+    fn accept_deadline(&self, deadline: Instant) -> io::Result<Stream> {
+        loop {
+            if !self.wait(Some(deadline))? {
+                return Err(accept_timed_out());
+            }
+            match self.accept_now() {
+                // Another thread took the client in nonblocking mode
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                otherwise => return otherwise,
+            }
+        }
+    }
 }
 
 /// Access to the underlying implementation.
@@ -101,6 +216,8 @@ impl From<UnixListener> for Listener {
             listener,
             reclaim: ReclaimGuard::default(),
             nonblocking_streams: AtomicBool::new(false),
+            long_path: None,
+            shutdown: OnceLock::new(),
         }
     }
 }
@@ -125,6 +242,8 @@ impl From<OwnedFd> for Listener {
             listener: fd.into(),
             reclaim: ReclaimGuard::default(),
             nonblocking_streams: AtomicBool::new(false),
+            long_path: None,
+            shutdown: OnceLock::new(),
         }
     }
 }
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        os::unix::unixprelude::*,
+        unnamed_pipe::{pipe, Recver, Sender},
+    },
+    std::{
+        error::Error,
+        fmt::{self, Display, Formatter},
+        io::{self, prelude::*},
+        sync::{
+            atomic::{
+                AtomicBool,
+                Ordering::{AcqRel, Acquire},
+            },
+            Arc,
+        },
+    },
+};
+
+/// Handle for shutting down a [`Listener`](super::Listener) from another thread.
+///
+/// Obtained via [`Listener::shutdown_handle()`](super::Listener::shutdown_handle). Once
+/// [`shutdown()`](Self::shutdown) is called on any clone of the handle, pending and future
+/// [`accept()`](crate::local_socket::traits::Listener::accept) calls on the listener return an
+/// error that [`ListenerShutdown::matches()`] recognizes, allowing a server thread blocked in
+/// `accept()` or iterating through
+/// [`incoming()`](crate::local_socket::traits::ListenerExt::incoming) to exit.
+///
+/// Shutting down the listener does not close it or release its name.
+#[derive(Clone, Debug)]
+pub struct ShutdownHandle(pub(super) Arc<Shutdown>);
+impl ShutdownHandle {
+    /// Shuts down the listener. Calling this more than once has no effect.
+    ///
+    /// ## System calls
+    /// - `write`
+    pub fn shutdown(&self) -> io::Result<()> {
+        if self.0.triggered.swap(true, AcqRel) {
+            return Ok(());
+        }
+        // The byte is never read out, so the pipe stays readable for all future accepts.
+        (&self.0.sender.0).write_all(&[0])
+    }
+    /// Returns `true` if [`shutdown()`](Self::shutdown) has been called.
+    #[inline]
+    pub fn is_shutdown(&self) -> bool { self.0.triggered.load(Acquire) }
+}
+
+/// Self-pipe that is polled alongside the listener.
+#[derive(Debug)]
+pub(super) struct Shutdown {
+    triggered: AtomicBool,
+    sender: Sender,
+    recver: Recver,
+}
+impl Shutdown {
+    pub(super) fn new() -> io::Result<Self> {
+        let (sender, recver) = pipe()?;
+        Ok(Self { triggered: AtomicBool::new(false), sender, recver })
+    }
+    /// Returns the `pollfd` for the read end of the pipe, which becomes readable upon shutdown.
+    pub(super) fn pollfd(&self) -> libc::pollfd {
+        libc::pollfd { fd: self.recver.as_fd().as_raw_fd(), events: libc::POLLIN, revents: 0 }
+    }
+}
+
+/// Error returned by [`accept()`](crate::local_socket::traits::Listener::accept) on a listener
+/// that has been shut down via a [`ShutdownHandle`], wrapped in an [`io::Error`] of kind
+/// [`Other`](io::ErrorKind::Other).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListenerShutdown;
+impl ListenerShutdown {
+    /// Returns `true` if the given I/O error was caused by the listener having been shut down.
+    pub fn matches(e: &io::Error) -> bool { e.get_ref().is_some_and(|e| e.is::<Self>()) }
+}
+impl Display for ListenerShutdown {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("local socket listener has been shut down")
+    }
+}
+impl Error for ListenerShutdown {}
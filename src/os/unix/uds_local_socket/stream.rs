@@ -1,11 +1,11 @@
 use {
-    super::{dispatch_name, CONN_TIMEOUT_MSG},
+    super::{dispatch_name, stream_name, CONN_TIMEOUT_MSG},
     crate::{
         error::ReuniteError,
         local_socket::{
             prelude::*,
             traits::{self, ReuniteResult},
-            ConnectOptions, PeerCreds,
+            ConnectOptions, Name, PeerCreds,
         },
         os::unix::{
             c_wrappers, local_socket::peer_creds::PeerCreds as PeerCredsInner, unixprelude::*,
@@ -35,7 +35,7 @@ impl traits::Stream for Stream {
         );
         let (stream, inprog) = dispatch_name(
             &mut opts,
-            false,
+            None,
             |&mut opts| opts.name.borrow(),
             |_| None,
             |addr, _| c_wrappers::create_client(addr, nonblocking_connect),
@@ -88,6 +88,10 @@ impl traits::StreamCommon for Stream {
     fn peer_creds(&self) -> io::Result<PeerCreds> {
         PeerCredsInner::for_socket(self.as_fd()).map(From::from)
     }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> { stream_name(self.as_fd(), false) }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> { stream_name(self.as_fd(), true) }
 }
 
 impl Read for &Stream {
@@ -2,12 +2,16 @@ use {
     super::Stream,
     crate::{
         local_socket::{
-            prelude::*, traits::tokio as traits, ListenerNonblockingMode, ListenerOptions,
+            prelude::*, traits::tokio as traits, ListenerNonblockingMode, ListenerOptions, Name,
+        },
+        os::unix::{
+            reclaim_guard::ReclaimGuard,
+            uds_local_socket::{listener::Listener as SyncListener, listener_name},
         },
-        os::unix::uds_local_socket::{listener::Listener as SyncListener, ReclaimGuard},
         Sealed,
     },
     std::{
+        ffi::CString,
         fmt::{self, Debug, Formatter},
         io,
         os::unix::prelude::*,
@@ -19,6 +23,7 @@ use {
 pub struct Listener {
     listener: UnixListener,
     reclaim: ReclaimGuard,
+    long_path: Option<CString>,
 }
 impl Sealed for Listener {}
 impl traits::Listener for Listener {
@@ -28,10 +33,7 @@ impl traits::Listener for Listener {
         options
             .nonblocking(ListenerNonblockingMode::Both)
             .create_sync_as::<SyncListener>()
-            .and_then(|mut sync| {
-                let reclaim = sync.reclaim.take();
-                Ok(Self { listener: UnixListener::from_std(sync.into())?, reclaim })
-            })
+            .and_then(Self::from_nonblocking_sync)
     }
     async fn accept(&self) -> io::Result<Stream> {
         let inner = self.listener.accept().await?.0;
@@ -39,7 +41,18 @@ impl traits::Listener for Listener {
     }
 
     fn do_not_reclaim_name_on_drop(&mut self) { self.reclaim.forget(); }
+    fn local_name(&self) -> io::Result<Name<'static>> {
+        listener_name(self.as_fd(), self.long_path.as_deref())
+    }
+}
+impl Listener {
+    fn from_nonblocking_sync(mut sync: SyncListener) -> io::Result<Self> {
+        let reclaim = sync.reclaim.take();
+        let long_path = sync.long_path.take();
+        Ok(Self { listener: UnixListener::from_std(sync.into())?, reclaim, long_path })
+    }
 }
+
 /// Access to the underlying implementation.
 impl Listener {
     /// Borrows the [`UnixListener`] contained within, granting access to operations defined on it.
@@ -56,10 +69,9 @@ impl Listener {
 // FUTURE remove handholding and assume nonblocking
 impl TryFrom<SyncListener> for Listener {
     type Error = io::Error;
-    fn try_from(mut sync: SyncListener) -> io::Result<Self> {
+    fn try_from(sync: SyncListener) -> io::Result<Self> {
         sync.set_nonblocking(ListenerNonblockingMode::Both)?;
-        let reclaim = sync.reclaim.take();
-        Ok(Self { listener: UnixListener::from_std(sync.into())?, reclaim })
+        Self::from_nonblocking_sync(sync)
     }
 }
 
@@ -68,6 +80,7 @@ impl Debug for Listener {
         f.debug_struct("Listener")
             .field("fd", &self.listener.as_raw_fd())
             .field("reclaim", &self.reclaim)
+            .field("long_path", &self.long_path)
             .finish()
     }
 }
@@ -1,25 +1,31 @@
 use {
-    super::super::{dispatch_name, CONN_TIMEOUT_MSG},
+    super::super::{dispatch_name, stream_name, CONN_TIMEOUT_MSG},
     crate::{
-        error::ReuniteError,
+        error::{ConversionError, ReuniteError},
         local_socket::{
+            tokio::timeouts::{Direction, Timeouts},
             traits::{tokio as traits, StreamCommon},
-            ConnectOptions, PeerCreds,
+            ConnectOptions, Name, PeerCreds,
         },
-        os::unix::{c_wrappers, local_socket::peer_creds::PeerCreds as PeerCredsInner},
-        ConnectWaitMode, Sealed,
+        os::unix::{
+            c_wrappers, local_socket::peer_creds::PeerCreds as PeerCredsInner,
+            tokio_conv::convert_via_dup, uds_local_socket::Stream as SyncStream,
+        },
+        ConnectWaitMode, Sealed, TryClone,
     },
     std::{
-        io::{self, ErrorKind::WouldBlock},
+        io::{self, ErrorKind::WouldBlock, IoSlice, IoSliceMut},
         os::{
             fd::{AsFd, OwnedFd},
             unix::{net::UnixStream as SyncUnixStream, prelude::BorrowedFd},
         },
         pin::Pin,
+        sync::Arc,
         task::{ready, Context, Poll},
+        time::Duration,
     },
     tokio::{
-        io::{AsyncRead, AsyncWrite, ReadBuf},
+        io::{AsyncRead, AsyncWrite, Interest, ReadBuf, Ready},
         net::{
             unix::{OwnedReadHalf as RecvHalfImpl, OwnedWriteHalf as SendHalfImpl},
             UnixStream,
@@ -29,7 +35,7 @@ use {
 
 /// Wrapper around [`UnixStream`] that implements [`Stream`](traits::Stream).
 #[derive(Debug)]
-pub struct Stream(pub(super) UnixStream);
+pub struct Stream(pub(super) UnixStream, Arc<Timeouts>);
 impl Sealed for Stream {}
 
 impl traits::Stream for Stream {
@@ -39,7 +45,7 @@ impl traits::Stream for Stream {
     async fn from_options(mut opts: &ConnectOptions<'_>) -> io::Result<Self> {
         let (sock, inprog) = dispatch_name(
             &mut opts,
-            false,
+            None,
             |&mut opts| opts.name.borrow(),
             |_| None,
             |addr, _| c_wrappers::create_client(addr, true),
@@ -59,17 +65,55 @@ impl traits::Stream for Stream {
                 ConnectWaitMode::Unbounded => sock.writable().await?,
             }
         }
-        Ok(Self(sock))
+        Ok(Self::from(sock))
+    }
+    #[inline]
+    fn set_recv_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Recv, timeout)
+    }
+    #[inline]
+    fn set_send_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Send, timeout)
+    }
+    #[inline]
+    fn set_idle_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set_idle(timeout)
+    }
+    #[inline]
+    async fn ready(&self, interest: Interest) -> io::Result<Ready> {
+        self.0.ready(interest).await
+    }
+    #[inline]
+    async fn readable(&self) -> io::Result<()> { self.0.readable().await }
+    #[inline]
+    async fn writable(&self) -> io::Result<()> { self.0.writable().await }
+    #[inline]
+    fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.1.record(self.0.try_read(buf))
+    }
+    #[inline]
+    fn try_read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.1.record(self.0.try_read_vectored(bufs))
+    }
+    #[inline]
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize> { self.1.record(self.0.try_write(buf)) }
+    #[inline]
+    fn try_write_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.1.record(self.0.try_write_vectored(bufs))
     }
     fn split(self) -> (RecvHalf, SendHalf) {
         let (r, w) = self.0.into_split();
-        (RecvHalf(r), SendHalf(w))
+        (RecvHalf(r, Arc::clone(&self.1)), SendHalf(w, self.1))
     }
     #[inline]
     fn reunite(rh: RecvHalf, sh: SendHalf) -> Result<Self, ReuniteError<RecvHalf, SendHalf>> {
-        rh.0.reunite(sh.0).map(Self::from).map_err(|tokio::net::unix::ReuniteError(rh, sh)| {
-            ReuniteError { rh: RecvHalf(rh), sh: SendHalf(sh) }
-        })
+        let (RecvHalf(rh, rt), SendHalf(sh, st)) = (rh, sh);
+        match rh.reunite(sh) {
+            Ok(s) => Ok(Self(s, rt)),
+            Err(tokio::net::unix::ReuniteError(rh, sh)) => {
+                Err(ReuniteError { rh: RecvHalf(rh, rt), sh: SendHalf(sh, st) })
+            }
+        }
     }
 }
 impl StreamCommon for Stream {
@@ -79,6 +123,10 @@ impl StreamCommon for Stream {
     fn peer_creds(&self) -> io::Result<PeerCreds> {
         PeerCredsInner::for_socket(self.as_fd()).map(From::from)
     }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> { stream_name(self.as_fd(), false) }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> { stream_name(self.as_fd(), true) }
 }
 
 /// Access to the underlying implementation.
@@ -106,11 +154,17 @@ fn ioloop(
 
 multimacro! {
     Stream,
-    pinproj_for_unpin(UnixStream),
     forward_rbv(UnixStream, &),
-    forward_tokio_rw,
+    derive_tokio_mut_rw,
     forward_as_handle(unix),
-    derive_trivial_conv(UnixStream),
+}
+impl From<UnixStream> for Stream {
+    #[inline]
+    fn from(s: UnixStream) -> Self { Self(s, Arc::default()) }
+}
+impl From<Stream> for UnixStream {
+    #[inline]
+    fn from(s: Stream) -> Self { s.0 }
 }
 impl AsyncRead for &Stream {
     #[inline]
@@ -119,7 +173,11 @@ impl AsyncRead for &Stream {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
-        ioloop(|| self.0.try_read_buf(buf), || self.0.poll_read_ready(cx)).map(|e| e.map(|_| ()))
+        self.1
+            .poll(Direction::Recv, cx, |cx| {
+                ioloop(|| self.0.try_read_buf(buf), || self.0.poll_read_ready(cx))
+            })
+            .map(|e| e.map(|_| ()))
     }
 }
 impl AsyncWrite for &Stream {
@@ -129,15 +187,19 @@ impl AsyncWrite for &Stream {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
-        ioloop(|| self.0.try_write(buf), || self.0.poll_write_ready(cx))
+        self.1.poll(Direction::Send, cx, |cx| {
+            ioloop(|| self.0.try_write(buf), || self.0.poll_write_ready(cx))
+        })
     }
     #[inline]
     fn poll_write_vectored(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
-        bufs: &[io::IoSlice<'_>],
+        bufs: &[IoSlice<'_>],
     ) -> Poll<io::Result<usize>> {
-        ioloop(|| self.0.try_write_vectored(bufs), || self.0.poll_write_ready(cx))
+        self.1.poll(Direction::Send, cx, |cx| {
+            ioloop(|| self.0.try_write_vectored(bufs), || self.0.poll_write_ready(cx))
+        })
     }
     #[inline]
     fn is_write_vectored(&self) -> bool { self.0.is_write_vectored() }
@@ -150,6 +212,34 @@ impl AsyncWrite for &Stream {
         Poll::Ready(Ok(()))
     }
 }
+/// Duplicates the file descriptor and registers the duplicate with the Tokio runtime of the current
+/// context, panicking if there is none. The clone shares
+/// [timeouts](traits::Stream::set_recv_timeout) with the original, just like duplicated file
+/// descriptors share the same socket.
+impl TryClone for Stream {
+    fn try_clone(&self) -> io::Result<Self> {
+        let fd = c_wrappers::duplicate_fd(self.as_fd())?;
+        Ok(Self(UnixStream::from_std(SyncUnixStream::from(fd))?, Arc::clone(&self.1)))
+    }
+}
+/// Registers the stream with the Tokio runtime of the current context, panicking if there is none.
+/// The sync stream is returned as part of the error if registration fails.
+impl TryFrom<SyncStream> for Stream {
+    type Error = ConversionError<SyncStream>;
+    fn try_from(stream: SyncStream) -> Result<Self, Self::Error> {
+        convert_via_dup(stream, true, |fd| {
+            Ok(UnixStream::from_std(SyncUnixStream::from(fd))?.into())
+        })
+    }
+}
+/// Deregisters the stream from the Tokio runtime and puts it in blocking mode. The Tokio stream
+/// is returned as part of the error if that fails.
+impl TryFrom<Stream> for SyncStream {
+    type Error = ConversionError<Stream>;
+    fn try_from(stream: Stream) -> Result<Self, Self::Error> {
+        convert_via_dup(stream, false, |fd| Ok(Self::from(fd)))
+    }
+}
 impl TryFrom<Stream> for OwnedFd {
     type Error = io::Error;
     #[inline]
@@ -178,17 +268,21 @@ macro_rules! tokio_accessors {
 }
 
 /// [`Stream`]'s receive half, internally implemented using [`Arc`](std::sync::Arc) by Tokio.
-pub struct RecvHalf(RecvHalfImpl);
+pub struct RecvHalf(RecvHalfImpl, Arc<Timeouts>);
 impl Sealed for RecvHalf {}
 multimacro! {
     RecvHalf,
-    pinproj_for_unpin(RecvHalfImpl),
     tokio_accessors(RecvHalfImpl),
     forward_debug("local_socket::RecvHalf"),
-    forward_tokio_read,
+    derive_tokio_mut_read,
 }
 impl traits::RecvHalf for RecvHalf {
     type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Recv, timeout)
+    }
 }
 impl AsyncRead for &RecvHalf {
     #[inline]
@@ -197,7 +291,10 @@ impl AsyncRead for &RecvHalf {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
-        ioloop(|| self.0.try_read_buf(buf), || self.0.as_ref().poll_read_ready(cx))
+        self.1
+            .poll(Direction::Recv, cx, |cx| {
+                ioloop(|| self.0.try_read_buf(buf), || self.0.as_ref().poll_read_ready(cx))
+            })
             .map(|e| e.map(|_| ()))
     }
 }
@@ -207,18 +304,22 @@ impl AsFd for RecvHalf {
 }
 
 /// [`Stream`]'s send half, internally implemented using [`Arc`](std::sync::Arc) by Tokio.
-pub struct SendHalf(SendHalfImpl);
+pub struct SendHalf(SendHalfImpl, Arc<Timeouts>);
 impl Sealed for SendHalf {}
 multimacro! {
     SendHalf,
-    pinproj_for_unpin(SendHalfImpl),
     tokio_accessors(SendHalfImpl),
     forward_rbv(SendHalfImpl, &),
     forward_debug("local_socket::SendHalf"),
-    forward_tokio_write,
+    derive_tokio_mut_write,
 }
 impl traits::SendHalf for SendHalf {
     type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Send, timeout)
+    }
 }
 impl AsyncWrite for &SendHalf {
     #[inline]
@@ -227,15 +328,19 @@ impl AsyncWrite for &SendHalf {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
-        ioloop(|| self.0.try_write(buf), || self.0.as_ref().poll_write_ready(cx))
+        self.1.poll(Direction::Send, cx, |cx| {
+            ioloop(|| self.0.try_write(buf), || self.0.as_ref().poll_write_ready(cx))
+        })
     }
     #[inline]
     fn poll_write_vectored(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
-        bufs: &[io::IoSlice<'_>],
+        bufs: &[IoSlice<'_>],
     ) -> Poll<io::Result<usize>> {
-        ioloop(|| self.0.try_write_vectored(bufs), || self.0.as_ref().poll_write_ready(cx))
+        self.1.poll(Direction::Send, cx, |cx| {
+            ioloop(|| self.0.try_write_vectored(bufs), || self.0.as_ref().poll_write_ready(cx))
+        })
     }
     #[inline]
     fn is_write_vectored(&self) -> bool { self.0.is_write_vectored() }
@@ -14,6 +14,8 @@ use {
     },
 };
 
+#[cfg(feature = "async")]
+pub(crate) mod async_io;
 #[cfg(feature = "tokio")]
 pub(crate) mod tokio;
 
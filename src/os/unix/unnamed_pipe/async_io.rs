@@ -0,0 +1,86 @@
+use {
+    super::UnnamedPipeExt,
+    crate::{
+        os::unix::{unixprelude::*, FdOps},
+        unnamed_pipe::{
+            async_io::{Recver as PubRecver, Sender as PubSender},
+            Recver as SyncRecver, Sender as SyncSender,
+        },
+    },
+    ::async_io::Async,
+    std::{
+        fmt::{self, Debug, Formatter},
+        io,
+    },
+};
+
+pub(crate) fn pipe_impl() -> io::Result<(PubSender, PubRecver)> {
+    let (tx, rx) = super::pipe(true)?;
+    Ok((PubSender(Sender::try_from_nb(tx)?), PubRecver(Recver::try_from_nb(rx)?)))
+}
+
+pub(crate) struct Recver(Async<FdOps>);
+impl Recver {
+    fn try_from_nb(rx: SyncRecver) -> io::Result<Self> { Ok(Self(Async::new(FdOps(rx.into()))?)) }
+}
+impl Debug for Recver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recver").field("fd", &self.0.as_raw_fd()).finish()
+    }
+}
+multimacro! {
+    Recver,
+    forward_rbv(Async<FdOps>, &),
+    forward_futures_ref_read,
+    derive_futures_mut_read,
+}
+
+impl TryFrom<SyncRecver> for Recver {
+    type Error = io::Error;
+    fn try_from(rx: SyncRecver) -> io::Result<Self> {
+        rx.set_nonblocking(true)?;
+        Self::try_from_nb(rx)
+    }
+}
+impl TryFrom<Recver> for OwnedFd {
+    type Error = io::Error;
+    fn try_from(rx: Recver) -> io::Result<Self> { Ok(rx.0.into_inner()?.0) }
+}
+impl TryFrom<OwnedFd> for Recver {
+    type Error = io::Error;
+    fn try_from(rx: OwnedFd) -> io::Result<Self> { SyncRecver::from(rx).try_into() }
+}
+forward_as_handle!(Recver, unix);
+
+pub(crate) struct Sender(Async<FdOps>);
+impl Sender {
+    fn try_from_nb(tx: SyncSender) -> io::Result<Self> { Ok(Self(Async::new(FdOps(tx.into()))?)) }
+}
+impl Debug for Sender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("fd", &self.0.as_raw_fd()).finish()
+    }
+}
+multimacro! {
+    Sender,
+    forward_rbv(Async<FdOps>, &),
+    forward_futures_ref_write,
+    derive_futures_mut_write,
+}
+
+impl TryFrom<SyncSender> for Sender {
+    type Error = io::Error;
+    fn try_from(tx: SyncSender) -> io::Result<Self> {
+        tx.set_nonblocking(true)?;
+        Self::try_from_nb(tx)
+    }
+}
+impl TryFrom<Sender> for OwnedFd {
+    type Error = io::Error;
+    fn try_from(tx: Sender) -> io::Result<Self> { Ok(tx.0.into_inner()?.0) }
+}
+impl TryFrom<OwnedFd> for Sender {
+    type Error = io::Error;
+    fn try_from(tx: OwnedFd) -> io::Result<Self> { SyncSender::from(tx).try_into() }
+}
+forward_as_handle!(Sender, unix);
@@ -1,11 +1,13 @@
 use {
     super::UnnamedPipeExt,
     crate::{
-        os::unix::{unixprelude::*, FdOps},
+        error::ConversionError,
+        os::unix::{tokio_conv::convert_via_dup, unixprelude::*, FdOps},
         unnamed_pipe::{
             tokio::{Recver as PubRecver, Sender as PubSender},
             Recver as SyncRecver, Sender as SyncSender,
         },
+        TryClone,
     },
     std::{
         io,
@@ -76,6 +78,32 @@ impl TryFrom<OwnedFd> for Recver {
     fn try_from(rx: OwnedFd) -> io::Result<Self> { SyncRecver::from(rx).try_into() }
 }
 forward_as_handle!(Recver);
+impl TryClone for Recver {
+    fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self(RecverImpl::with_interest(self.0.get_ref().try_clone()?, Interest::READABLE)?))
+    }
+}
+
+/// Registers the pipe end with the Tokio runtime of the current context, panicking if there is
+/// none. The sync pipe end is returned as part of the error if registration fails.
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl TryFrom<SyncRecver> for PubRecver {
+    type Error = ConversionError<SyncRecver>;
+    fn try_from(rx: SyncRecver) -> Result<Self, Self::Error> {
+        convert_via_dup(rx, true, |fd| {
+            Ok(Self(Recver(RecverImpl::with_interest(FdOps(fd), Interest::READABLE)?)))
+        })
+    }
+}
+/// Deregisters the pipe end from the Tokio runtime and puts it in blocking mode. The Tokio pipe
+/// end is returned as part of the error if that fails.
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl TryFrom<PubRecver> for SyncRecver {
+    type Error = ConversionError<PubRecver>;
+    fn try_from(rx: PubRecver) -> Result<Self, Self::Error> {
+        convert_via_dup(rx, false, |fd| Ok(Self::from(fd)))
+    }
+}
 
 #[derive(Debug)]
 pub(crate) struct Sender(SenderImpl);
@@ -133,3 +161,29 @@ impl TryFrom<OwnedFd> for Sender {
     fn try_from(tx: OwnedFd) -> io::Result<Self> { SyncSender::from(tx).try_into() }
 }
 forward_as_handle!(Sender);
+impl TryClone for Sender {
+    fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self(SenderImpl::with_interest(self.0.get_ref().try_clone()?, Interest::WRITABLE)?))
+    }
+}
+
+/// Registers the pipe end with the Tokio runtime of the current context, panicking if there is
+/// none. The sync pipe end is returned as part of the error if registration fails.
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl TryFrom<SyncSender> for PubSender {
+    type Error = ConversionError<SyncSender>;
+    fn try_from(tx: SyncSender) -> Result<Self, Self::Error> {
+        convert_via_dup(tx, true, |fd| {
+            Ok(Self(Sender(SenderImpl::with_interest(FdOps(fd), Interest::WRITABLE)?)))
+        })
+    }
+}
+/// Deregisters the pipe end from the Tokio runtime and puts it in blocking mode. The Tokio pipe
+/// end is returned as part of the error if that fails.
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl TryFrom<PubSender> for SyncSender {
+    type Error = ConversionError<PubSender>;
+    fn try_from(tx: PubSender) -> Result<Self, Self::Error> {
+        convert_via_dup(tx, false, |fd| Ok(Self::from(fd)))
+    }
+}
@@ -0,0 +1,59 @@
+//! Local sockets that perform I/O via io_uring on Linux.
+//!
+//! The types in this module are Unix domain socket local sockets, compatible with the
+//! [`uds_local_socket`](super::uds_local_socket) ones on the other end of the connection, which
+//! accept clients with a multishot accept and receive and send via rings of their own. They
+//! implement the regular
+//! [`Listener`](crate::local_socket::traits::Listener) and
+//! [`Stream`](crate::local_socket::traits::Stream) traits, and can be created via
+//! [`ListenerOptions::create_sync_as()`](crate::local_socket::ListenerOptions::create_sync_as) and
+//! [`ConnectOptions::connect_sync_as()`](crate::local_socket::ConnectOptions::connect_sync_as):
+//! ```no_run
+//! use interprocess::{
+//!     local_socket::{prelude::*, ConnectOptions, GenericNamespaced, ListenerOptions},
+//!     os::unix::uring_local_socket::{Listener, Stream},
+//! };
+//! # fn main() -> std::io::Result<()> {
+//! let name = "example.sock".to_ns_name::<GenericNamespaced>()?;
+//! let listener = ListenerOptions::new().name(name.clone()).create_sync_as::<Listener>()?;
+//! let client = ConnectOptions::new().name(name).connect_sync_as::<Stream>()?;
+//! let server = listener.accept()?;
+//! println!("using io_uring: {}", server.uses_io_uring() && client.uses_io_uring());
+//! # Ok(()) }
+//! ```
+//!
+//! If the kernel does not support io_uring or the operations used here, or has it disabled (via
+//! the `kernel.io_uring_disabled` sysctl, seccomp or otherwise), everything falls back to the same
+//! system calls that the Unix domain socket implementation makes. The
+//! [`is_available()`] function and the `uses_io_uring()` methods on the listener and stream can be
+//! used to find out whether that has happened.
+//!
+//! ## Performance
+//! Submissions are not batched: every blocking receive or send is one `io_uring_enter` call that
+//! submits the operation and waits for its completion, plus a copy between the caller's buffer
+//! and one owned by the ring. Setting up a stream additionally costs a handful of system calls,
+//! since each stream creates two rings and registers buffers with them. As such, these types do
+//! not make fewer system calls than the Unix domain socket ones and should not be expected to
+//! outperform them.
+//!
+//! ## Interrupted system calls
+//! When a ring is closed, the kernel interrupts the next blocking system call of every thread that
+//! has used it. System calls without a timeout are restarted transparently, but those with one –
+//! such as reads from sockets with `SO_RCVTIMEO` set – fail with
+//! [`Interrupted`](std::io::ErrorKind::Interrupted). The streams in this module retry those
+//! themselves, but other I/O performed by threads that use them may observe such errors.
+
+mod listener;
+mod ring;
+mod stream;
+
+use std::sync::{Mutex, MutexGuard};
+pub use {listener::*, stream::*};
+
+/// Returns `true` if io_uring is available and supports all the operations that this module uses.
+///
+/// The check is only performed once, with the result being reused by subsequent calls.
+#[inline]
+pub fn is_available() -> bool { ring::is_available() }
+
+fn lock<T>(m: &Mutex<T>) -> MutexGuard<'_, T> { m.lock().unwrap_or_else(|e| e.into_inner()) }
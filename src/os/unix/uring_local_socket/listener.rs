@@ -0,0 +1,301 @@
+use {
+    super::{
+        lock,
+        ring::{self, cqe_fd},
+        Stream,
+    },
+    crate::{
+        local_socket::{traits, ListenerNonblockingMode, ListenerOptions, Name},
+        os::unix::{
+            c_wrappers,
+            local_socket::{accept_timed_out, ListenerExt},
+            uds_local_socket as uds,
+        },
+    },
+    io_uring::{cqueue, opcode, types::Fd, IoUring},
+    std::{
+        fmt::{self, Debug, Formatter},
+        io,
+        iter::FusedIterator,
+        os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd},
+        sync::{
+            atomic::{
+                AtomicBool,
+                Ordering::{Acquire, Release},
+            },
+            Mutex, TryLockError,
+        },
+        time::Instant,
+    },
+};
+
+const ACCEPT: u64 = 0;
+const CANCEL: u64 = 1;
+/// How many clients the multishot accept may take off the listen backlog before they are picked up
+/// by `accept()`.
+const MAX_ACCEPTED_AHEAD: usize = 8;
+
+/// A ring with a multishot accept armed on the listener.
+struct AcceptRing {
+    ring: IoUring,
+    armed: bool,
+    cancelling: bool,
+    accepted_any: bool,
+}
+impl AcceptRing {
+    /// Cancels the multishot accept once enough clients have piled up in the completion queue,
+    /// so that further ones stay in the listen backlog, where they are subject to its limit. It's
+    /// rearmed once the completion queue has been drained.
+    fn limit_accepted_ahead(&mut self) {
+        if !self.armed || self.cancelling || self.ring.completion().len() < MAX_ACCEPTED_AHEAD {
+            return;
+        }
+        let entry = opcode::AsyncCancel::new(ACCEPT).build().user_data(CANCEL);
+        // SAFETY: no buffers are involved
+        if unsafe { ring::push(&mut self.ring, &entry) }.is_ok() {
+            self.cancelling = true;
+            // If this fails, the cancellation is submitted together with whatever comes next
+            let _ = self.ring.submit();
+        }
+    }
+}
+impl Drop for AcceptRing {
+    fn drop(&mut self) {
+        // Close clients that were accepted but never picked up
+        for cqe in self.ring.completion().filter(|cqe| cqe.user_data() == ACCEPT) {
+            // SAFETY: the completion is that of an accept
+            drop(unsafe { cqe_fd(cqe.result()) });
+        }
+    }
+}
+
+/// Local socket listener that accepts clients with a multishot accept on an io_uring, falling
+/// back to the [Unix domain socket implementation](uds::Listener) when io_uring is unavailable.
+///
+/// The multishot accept is armed on the first call to [`accept()`](traits::Listener::accept),
+/// after which the kernel accepts clients ahead of time, even while no thread is in `accept()`.
+/// Such clients count as connected rather than as pending in the listen backlog, so the backlog
+/// stops limiting how many connections can be waiting on the listener. To keep that number
+/// bounded nonetheless, the multishot accept is cancelled once 8 clients have been accepted
+/// ahead of time, and is rearmed once they have been picked up. Clients that connect before the
+/// cancellation takes effect are still accepted, so up to about one backlog's worth more may
+/// pile up in a burst. Clients that are never picked up are closed when the listener is dropped.
+///
+/// If the kernel turns out not to support multishot accept (Linux 5.19 is required), the ring is
+/// dropped and the listener permanently switches to plain `accept` calls.
+///
+/// In nonblocking mode, `accept()` only reaps clients that the ring has already accepted.
+pub struct Listener {
+    ring: Mutex<Option<AcceptRing>>,
+    uring_active: AtomicBool,
+    nonblocking_accept: AtomicBool,
+    nonblocking_streams: AtomicBool,
+    inner: uds::Listener,
+}
+impl crate::Sealed for Listener {}
+impl traits::Listener for Listener {
+    type Stream = Stream;
+
+    fn from_options(opts: ListenerOptions<'_>) -> io::Result<Self> {
+        let nonblocking_accept = opts.get_nonblocking_accept();
+        let nonblocking_streams = opts.get_nonblocking_stream();
+        let inner = <uds::Listener as traits::Listener>::from_options(opts)?;
+        Self::with_flags(inner, nonblocking_accept, nonblocking_streams)
+    }
+    fn accept(&self) -> io::Result<Stream> {
+        let nonblocking_streams = self.nonblocking_streams.load(Acquire);
+        let nonblocking = self.nonblocking_accept.load(Acquire);
+        let fd = if self.uring_active.load(Acquire) {
+            self.accept_uring(nonblocking, None)?
+        } else {
+            None
+        };
+        let Some(fd) = fd else {
+            return traits::Listener::accept(&self.inner)
+                .map(|s| Stream::from_uds(s, nonblocking_streams));
+        };
+        self.finish_accept(fd)
+    }
+    fn set_nonblocking(&self, nonblocking: ListenerNonblockingMode) -> io::Result<()> {
+        use ListenerNonblockingMode::*;
+        self.nonblocking_accept.store(matches!(nonblocking, Accept | Both), Release);
+        self.nonblocking_streams.store(matches!(nonblocking, Stream | Both), Release);
+        if !self.uses_io_uring() {
+            self.apply_nonblocking_to_inner()?;
+        }
+        Ok(())
+    }
+    fn do_not_reclaim_name_on_drop(&mut self) {
+        traits::Listener::do_not_reclaim_name_on_drop(&mut self.inner);
+    }
+    fn local_name(&self) -> io::Result<Name<'static>> {
+        traits::Listener::local_name(&self.inner)
+    }
+}
+impl Iterator for Listener {
+    type Item = io::Result<Stream>;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> { Some(traits::Listener::accept(self)) }
+}
+impl FusedIterator for Listener {}
+
+impl Listener {
+    fn with_flags(
+        inner: uds::Listener,
+        nonblocking_accept: bool,
+        nonblocking_streams: bool,
+    ) -> io::Result<Self> {
+        let ring = ring::new_ring(16).map(|ring| AcceptRing {
+            ring,
+            armed: false,
+            cancelling: false,
+            accepted_any: false,
+        });
+        if ring.is_some() {
+            // The ring does the waiting, and a nonblocking listener would make it fail instead
+            inner.inner().set_nonblocking(false)?;
+        }
+        let slf = Self {
+            uring_active: AtomicBool::new(ring.is_some()),
+            ring: Mutex::new(ring),
+            nonblocking_accept: AtomicBool::new(nonblocking_accept),
+            nonblocking_streams: AtomicBool::new(nonblocking_streams),
+            inner,
+        };
+        if !slf.uses_io_uring() {
+            slf.apply_nonblocking_to_inner()?;
+        }
+        Ok(slf)
+    }
+
+    fn finish_accept(&self, fd: OwnedFd) -> io::Result<Stream> {
+        let nonblocking_streams = self.nonblocking_streams.load(Acquire);
+        if nonblocking_streams {
+            c_wrappers::fast_set_nonblocking(fd.as_fd(), true)?;
+        }
+        Ok(Stream::from_uds(uds::Stream::from(fd), nonblocking_streams))
+    }
+
+    /// Accepts a client using the ring, returning `None` if the listener has fallen back to plain
+    /// system calls. If a deadline is given, the wait fails with `TimedOut` once it passes.
+    ///
+    /// A multishot accept that was cancelled to bound the amount of clients accepted ahead of time
+    /// completes with `ECANCELED` after all of those clients, and is rearmed right away.
+    fn accept_uring(
+        &self,
+        nonblocking: bool,
+        deadline: Option<Instant>,
+    ) -> io::Result<Option<OwnedFd>> {
+        let mut ring = if nonblocking {
+            // Another thread is waiting on the ring and will take the next client
+            match self.ring.try_lock() {
+                Ok(ring) => ring,
+                Err(TryLockError::Poisoned(e)) => e.into_inner(),
+                Err(TryLockError::WouldBlock) => return Err(io::ErrorKind::WouldBlock.into()),
+            }
+        } else {
+            lock(&self.ring)
+        };
+        let Some(ar) = &mut *ring else { return Ok(None) };
+        loop {
+            if !ar.armed {
+                let entry = opcode::AcceptMulti::new(Fd(self.as_fd().as_raw_fd()))
+                    .flags(libc::SOCK_CLOEXEC)
+                    .build()
+                    .user_data(ACCEPT);
+                // SAFETY: no buffers are involved
+                unsafe { ring::push(&mut ar.ring, &entry)? };
+                ar.armed = true;
+            }
+            match deadline {
+                Some(deadline) if !nonblocking => {
+                    ring::submit_and_wait_until(&mut ar.ring, deadline)?
+                }
+                _ => ring::submit_and_wait(&mut ar.ring, if nonblocking { 0 } else { 1 })?,
+            }
+            let Some(cqe) = ar.ring.completion().next() else {
+                if nonblocking {
+                    return Err(io::ErrorKind::WouldBlock.into());
+                }
+                if deadline.is_some_and(|d| Instant::now() >= d) {
+                    return Err(accept_timed_out());
+                }
+                continue;
+            };
+            if cqe.user_data() == CANCEL {
+                continue;
+            }
+            if !cqueue::more(cqe.flags()) {
+                ar.armed = false;
+                ar.cancelling = false;
+            }
+            // SAFETY: the completion is that of an accept
+            match unsafe { cqe_fd(cqe.result()) } {
+                Ok(fd) => {
+                    ar.accepted_any = true;
+                    ar.limit_accepted_ahead();
+                    return Ok(Some(fd));
+                }
+                Err(e) => match e.raw_os_error() {
+                    // Multishot accept is not supported by the kernel
+                    Some(libc::EINVAL) if !ar.accepted_any => {
+                        *ring = None;
+                        self.uring_active.store(false, Release);
+                        self.apply_nonblocking_to_inner()?;
+                        return Ok(None);
+                    }
+                    // Rearmed on the next iteration
+                    Some(libc::ECANCELED | libc::EINTR) => continue,
+                    _ => return Err(e),
+                },
+            }
+        }
+    }
+    fn apply_nonblocking_to_inner(&self) -> io::Result<()> {
+        self.inner.inner().set_nonblocking(self.nonblocking_accept.load(Acquire))?;
+        self.inner.set_new_stream_nonblocking(self.nonblocking_streams.load(Acquire));
+        Ok(())
+    }
+
+    /// Returns `true` if the listener accepts clients via io_uring and `false` if it has fallen
+    /// back to plain system calls.
+    pub fn uses_io_uring(&self) -> bool { self.uring_active.load(Acquire) }
+
+    /// Borrows the Unix domain socket listener contained within.
+    #[inline(always)]
+    pub fn inner(&self) -> &uds::Listener { &self.inner }
+}
+
+impl ListenerExt for Listener {
+    fn accept_deadline(&self, deadline: Instant) -> io::Result<Stream> {
+        let fd = if self.uring_active.load(Acquire) {
+            self.accept_uring(false, Some(deadline))?
+        } else {
+            None
+        };
+        let Some(fd) = fd else {
+            let nonblocking_streams = self.nonblocking_streams.load(Acquire);
+            return self
+                .inner
+                .accept_deadline(deadline)
+                .map(|s| Stream::from_uds(s, nonblocking_streams));
+        };
+        self.finish_accept(fd)
+    }
+}
+
+impl Debug for Listener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Listener")
+            .field("inner", &self.inner)
+            .field("uses_io_uring", &self.uses_io_uring())
+            .field("nonblocking_accept", &self.nonblocking_accept)
+            .field("nonblocking_streams", &self.nonblocking_streams)
+            .finish()
+    }
+}
+
+impl AsFd for Listener {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> { self.inner.as_fd() }
+}
@@ -0,0 +1,128 @@
+//! Thin layer over the `io-uring` crate shared by the listener and the stream.
+
+use {
+    io_uring::{
+        cqueue, opcode, squeue,
+        types::{SubmitArgs, Timespec},
+        IoUring, Probe,
+    },
+    std::{
+        io,
+        os::fd::{FromRawFd, OwnedFd},
+        sync::OnceLock,
+        time::Instant,
+    },
+};
+
+/// Checks whether the kernel lets us create rings and supports every operation this module uses.
+/// The result is computed once per process.
+pub(super) fn is_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        let Ok(ring) = IoUring::new(2) else { return false };
+        let mut probe = Probe::new();
+        if ring.submitter().register_probe(&mut probe).is_err() {
+            return false;
+        }
+        [
+            opcode::Accept::CODE,
+            opcode::Recv::CODE,
+            opcode::WriteFixed::CODE,
+            opcode::ProvideBuffers::CODE,
+            opcode::AsyncCancel::CODE,
+        ]
+        .into_iter()
+        .all(|op| probe.is_supported(op))
+    })
+}
+
+/// Creates a ring with the given number of submission queue entries, or returns `None` if
+/// io_uring is unavailable.
+pub(super) fn new_ring(entries: u32) -> Option<IoUring> {
+    if !is_available() {
+        return None;
+    }
+    IoUring::new(entries).ok()
+}
+
+/// Pushes an entry onto the submission queue without submitting it.
+///
+/// # Safety
+/// The buffers referenced by the entry must stay valid until its completion is reaped.
+pub(super) unsafe fn push(ring: &mut IoUring, entry: &squeue::Entry) -> io::Result<()> {
+    unsafe { ring.submission().push(entry) }
+        .map_err(|_| io::Error::other("io_uring submission queue is full"))
+}
+
+/// Submits all pushed entries and waits for at least `want` completions to be available,
+/// retrying on signal interruption.
+pub(super) fn submit_and_wait(ring: &mut IoUring, want: usize) -> io::Result<()> {
+    loop {
+        match ring.submit_and_wait(want) {
+            Ok(..) => return Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            // The completion queue is overflowing; the caller will reap it and come back
+            Err(e) if e.raw_os_error() == Some(libc::EBUSY) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Like [`submit_and_wait()`] with `want` set to 1, but gives up when the deadline passes. The
+/// completion queue may be empty upon return even if the deadline has not passed yet.
+pub(super) fn submit_and_wait_until(ring: &mut IoUring, deadline: Instant) -> io::Result<()> {
+    loop {
+        let timeout = Timespec::from(deadline.saturating_duration_since(Instant::now()));
+        let args = SubmitArgs::new().timespec(&timeout);
+        match ring.submitter().submit_with_args(1, &args) {
+            Ok(..) => return Ok(()),
+            Err(e) if e.raw_os_error() == Some(libc::ETIME) => return Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) if e.raw_os_error() == Some(libc::EBUSY) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Submits all pushed entries and blocks until the completion with the given user data arrives,
+/// discarding any other completions reaped along the way.
+///
+/// Never returns while the operation is still in flight, other than with an error from
+/// `io_uring_enter` itself, so that the buffers it uses can be reused right away.
+pub(super) fn complete(ring: &mut IoUring, user_data: u64) -> io::Result<cqueue::Entry> {
+    loop {
+        submit_and_wait(ring, 1)?;
+        if let Some(cqe) = ring.completion().find(|cqe| cqe.user_data() == user_data) {
+            return Ok(cqe);
+        }
+    }
+}
+
+/// Cancels the operation with the given user data and blocks until it completes, returning its
+/// completion, which reports `ECANCELED` unless the operation finished before it could be
+/// cancelled. The completion of the cancellation itself carries `cancel_user_data` and is
+/// discarded later.
+pub(super) fn cancel(
+    ring: &mut IoUring,
+    user_data: u64,
+    cancel_user_data: u64,
+) -> io::Result<cqueue::Entry> {
+    let entry = opcode::AsyncCancel::new(user_data).build().user_data(cancel_user_data);
+    // SAFETY: no buffers are involved
+    unsafe { push(ring, &entry)? };
+    complete(ring, user_data)
+}
+
+/// Converts the result field of a completion into an `io::Result`.
+pub(super) fn cqe_result(res: i32) -> io::Result<u32> {
+    u32::try_from(res).map_err(|_| io::Error::from_raw_os_error(res.saturating_neg()))
+}
+/// Like [`cqe_result()`], but takes ownership of the file descriptor that the result holds.
+///
+/// # Safety
+/// The completion must be of an operation that creates a file descriptor, and must not have been
+/// passed to this function before.
+pub(super) unsafe fn cqe_fd(res: i32) -> io::Result<OwnedFd> {
+    cqe_result(res)?;
+    Ok(unsafe { OwnedFd::from_raw_fd(res) })
+}
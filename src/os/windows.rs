@@ -19,7 +19,8 @@ mod needs_flush;
 mod linger_pool;
 mod c_wrappers;
 mod maybe_arc;
+mod private_dir;
 #[cfg(feature = "tokio")]
 mod tokio_flusher;
 
-pub(crate) use {maybe_arc::*, misc::*, needs_flush::*};
+pub(crate) use {maybe_arc::*, misc::*, needs_flush::*, private_dir::*};
@@ -1,14 +1,22 @@
 use {
     super::super::named_pipe::local_socket as np_impl,
-    crate::local_socket::{prelude::*, ConnectOptions, Listener, ListenerOptions, Stream},
+    crate::local_socket::{
+        prelude::*, tcp as tcp_impl, ConnectOptions, Listener, ListenerOptions, Stream,
+    },
     std::io,
 };
 
 #[inline]
 pub fn listen(options: ListenerOptions<'_>) -> io::Result<Listener> {
+    if options.name.0.is_tcp() {
+        return options.create_sync_as::<tcp_impl::Listener>().map(Listener::from);
+    }
     options.create_sync_as::<np_impl::Listener>().map(Listener::from)
 }
 #[inline]
 pub fn connect(options: &ConnectOptions<'_>) -> io::Result<Stream> {
+    if options.name.0.is_tcp() {
+        return tcp_impl::Stream::from_options(options).map(Stream::from);
+    }
     np_impl::Stream::from_options(options).map(Stream::from)
 }
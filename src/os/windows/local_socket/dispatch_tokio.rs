@@ -1,6 +1,7 @@
 use {
     super::super::named_pipe::local_socket::tokio as np_impl,
     crate::local_socket::{
+        tcp::tokio as tcp_impl,
         tokio::{prelude::*, Listener, Stream},
         ConnectOptions, ListenerOptions,
     },
@@ -9,9 +10,15 @@ use {
 
 #[inline]
 pub fn listen(options: ListenerOptions<'_>) -> io::Result<Listener> {
+    if options.name.0.is_tcp() {
+        return options.create_tokio_as::<tcp_impl::Listener>().map(Listener::from);
+    }
     options.create_tokio_as::<np_impl::Listener>().map(Listener::from)
 }
 #[inline]
 pub async fn connect(options: &ConnectOptions<'_>) -> io::Result<Stream> {
+    if options.name.0.is_tcp() {
+        return tcp_impl::Stream::from_options(options).await.map(Stream::from);
+    }
     np_impl::Stream::from_options(options).await.map(Stream::from)
 }
@@ -1,9 +1,9 @@
 use {
     crate::{
-        local_socket::{Name, NameInner, NameType, PathNameType},
+        local_socket::{Name, NameError, NameInner, NameType, PathNameType},
         os::windows::{convert_and_encode_path, convert_osstr},
     },
-    std::{borrow::Cow, ffi::OsStr, io},
+    std::{borrow::Cow, ffi::OsStr, os::windows::prelude::*},
 };
 
 tag_enum!(
@@ -11,7 +11,7 @@ tag_enum!(
 /// [named pipe local socket](crate::os::windows::named_pipe::local_socket) names.
 ///
 /// Named pipe paths of the form `\\HOSTNAME\pipe\PIPENAME` are passed through verbatim. Other paths
-/// yield an error, as they do not point to NPFS.
+/// yield [`NameError::InvalidPrefix`], as they do not point to NPFS.
 ///
 /// Namespaced strings have `\\.\pipe\` prepended to them – using
 /// [`ToNsName`](crate::local_socket::ToNsName) conversions implies the hostname `.`, which is the
@@ -21,25 +21,34 @@ impl NameType for NamedPipe {
     fn is_supported() -> bool { true }
 }
 impl PathNameType<OsStr> for NamedPipe {
-    fn map(path: Cow<'_, OsStr>) -> io::Result<Name<'_>> {
+    fn map(path: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
         if !is_pipefs(&path) {
-            return Err(io::Error::new(io::ErrorKind::Unsupported, "not a named pipe path"));
+            return Err(NameError::InvalidPrefix);
         }
+        check_no_nul(&path)?;
         Ok(Name(NameInner::NamedPipe(Cow::Owned(convert_osstr(&path)?))))
     }
 }
 
-pub(crate) fn map_generic_path_osstr(path: Cow<'_, OsStr>) -> io::Result<Name<'_>> {
+pub(crate) fn map_generic_path_osstr(path: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
     // TODO do something meaningful for non-NPFS paths instead of rejecting them
     // TODO normskip (`\\?\`) paths
     NamedPipe::map(path)
 }
 
-pub(crate) fn map_generic_namespaced_osstr(name: Cow<'_, OsStr>) -> io::Result<Name<'_>> {
+pub(crate) fn map_generic_namespaced_osstr(name: Cow<'_, OsStr>) -> Result<Name<'_>, NameError> {
+    check_no_nul(&name)?;
     // The prepending currently happens at a later point.
     Ok(Name(NameInner::NamedPipe(Cow::Owned(convert_and_encode_path(&name, None)?))))
 }
 
+fn check_no_nul(name: &OsStr) -> Result<(), NameError> {
+    match name.encode_wide().position(|u| u == 0) {
+        Some(pos) => Err(NameError::InteriorNul(pos)),
+        None => Ok(()),
+    }
+}
+
 #[allow(clippy::indexing_slicing, clippy::arithmetic_side_effects)] // minlen check
 fn is_pipefs(slf: &OsStr) -> bool {
     const PFX1: &[u8] = br"\\";
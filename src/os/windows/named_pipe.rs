@@ -29,8 +29,26 @@ pub use {enums::*, listener::*, stream::*, wait_timeout::*};
 pub mod local_socket {
     mod listener;
     mod stream;
+    use {
+        super::c_wrappers,
+        crate::{
+            local_socket::{Name, NameInner},
+            os::windows::winprelude::*,
+        },
+        std::{borrow::Cow, io},
+        widestring::U16CStr,
+    };
     pub use {listener::*, stream::*};
 
+    fn path_to_name(path: &U16CStr) -> Name<'static> {
+        Name(NameInner::NamedPipe(Cow::Owned(path.to_owned())))
+    }
+    /// Retrieves the path of the named pipe the handle refers to.
+    fn pipe_name(handle: BorrowedHandle<'_>) -> io::Result<Option<Name<'static>>> {
+        let path = c_wrappers::get_pipe_path(handle)?;
+        Ok(Some(Name(NameInner::NamedPipe(Cow::Owned(path)))))
+    }
+
     /// Async local sockets for Tokio implemented using named pipes.
     #[cfg(feature = "tokio")]
     pub mod tokio {
@@ -8,13 +8,18 @@ use {
         },
         HandleOrErrno, OrErrno, RawOsErrorExt, SubUsizeExt,
     },
-    std::{io, mem::MaybeUninit, ptr},
-    widestring::U16CStr,
+    std::{
+        io,
+        mem::{size_of_val, MaybeUninit},
+        ptr, slice,
+    },
+    widestring::{U16CStr, U16CString},
     windows_sys::Win32::{
         Foundation::{ERROR_PIPE_BUSY, GENERIC_READ, GENERIC_WRITE},
         Storage::FileSystem::{
-            CreateFileW, ReOpenFile, FILE_FLAG_OVERLAPPED, FILE_SHARE_READ, FILE_SHARE_WRITE,
-            FILE_WRITE_ATTRIBUTES, OPEN_EXISTING,
+            CreateFileW, FileNameInfo, GetFileInformationByHandleEx, ReOpenFile,
+            FILE_FLAG_OVERLAPPED, FILE_SHARE_READ, FILE_SHARE_WRITE, FILE_WRITE_ATTRIBUTES,
+            OPEN_EXISTING,
         },
         System::Pipes::{
             GetNamedPipeHandleStateW, GetNamedPipeInfo, PeekNamedPipe, SetNamedPipeHandleState,
@@ -101,6 +106,33 @@ pub(crate) fn set_np_handle_state(
     .true_val_or_errno(())
 }
 
+/// Retrieves the path of the named pipe the handle refers to, in the `\\.\pipe\name` form.
+pub(crate) fn get_pipe_path(handle: BorrowedHandle<'_>) -> io::Result<U16CString> {
+    // FILE_NAME_INFO is a 32-bit byte length followed by the name, which, for named pipes, is
+    // relative to the root of the named pipe filesystem and starts with a backslash. Names of
+    // named pipes are limited to 256 characters, which the buffer comfortably fits.
+    let mut buf = [0_u32; 1 + 256];
+    #[allow(clippy::cast_possible_truncation)]
+    let bufsize = size_of_val(&buf) as u32;
+    unsafe {
+        GetFileInformationByHandleEx(
+            handle.as_raw_handle(),
+            FileNameInfo,
+            buf.as_mut_ptr().cast(),
+            bufsize,
+        )
+    }
+    .true_val_or_errno(())?;
+    let [len, rest @ ..] = &buf;
+    let len = (len.to_usize() / 2).min(size_of_val(rest) / 2);
+    // SAFETY: the length is clamped to the size of the buffer, and u16 has a looser alignment
+    // requirement than u32
+    let name = unsafe { slice::from_raw_parts(rest.as_ptr().cast::<u16>(), len) };
+    let mut path = r"\\.\pipe".encode_utf16().collect::<Vec<_>>();
+    path.extend_from_slice(name);
+    Ok(U16CString::from_vec_truncate(path))
+}
+
 #[inline]
 pub(crate) fn get_flags(handle: BorrowedHandle<'_>) -> io::Result<u32> {
     let mut flags: u32 = 0;
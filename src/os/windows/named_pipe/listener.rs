@@ -120,6 +120,9 @@ impl<Rm: PipeModeTag, Sm: PipeModeTag> PipeListener<Rm, Sm> {
         }
     }
 
+    /// Returns the path the listener creates instances of the named pipe at.
+    pub(crate) fn path(&self) -> &widestring::U16CStr { &self.config.path }
+
     fn create_instance(&self, nonblocking: bool) -> io::Result<OwnedHandle> {
         self.config.create_instance(false, nonblocking, Self::STREAM_ROLE, Rm::MODE)
     }
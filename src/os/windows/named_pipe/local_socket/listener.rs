@@ -1,9 +1,9 @@
 use {
-    super::stream::Stream,
+    super::{path_to_name, stream::Stream},
     crate::{
         local_socket::{
             traits::{self, Stream as _},
-            ListenerNonblockingMode, ListenerOptions, NameInner,
+            ListenerNonblockingMode, ListenerOptions, Name, NameError, NameInner,
         },
         os::windows::{
             named_pipe::{pipe_mode::Bytes, PipeListener, PipeListenerOptions},
@@ -33,7 +33,9 @@ impl traits::Listener for Listener {
         let nonblocking = ListenerNonblockingMode::from_bool(nb_accept, nb_stream);
 
         let mut impl_options = PipeListenerOptions::new();
-        let NameInner::NamedPipe(path) = options.name.0;
+        let NameInner::NamedPipe(path) = options.name.0 else {
+            return Err(NameError::Unsupported.into());
+        };
         impl_options.path = path;
         impl_options.nonblocking = nb_accept;
         impl_options.security_descriptor = options.security_descriptor;
@@ -57,6 +59,8 @@ impl traits::Listener for Listener {
         Ok(())
     }
     fn do_not_reclaim_name_on_drop(&mut self) {}
+    #[inline]
+    fn local_name(&self) -> io::Result<Name<'static>> { Ok(path_to_name(self.listener.path())) }
 }
 
 /// Access to the underlying implementation.
@@ -1,13 +1,15 @@
 use {
+    super::pipe_name,
     crate::{
         error::{FromHandleError, ReuniteError},
         local_socket::{
             traits::{self, ReuniteResult},
-            ConnectOptions, NameInner, PeerCreds,
+            ConnectOptions, Name, NameError, NameInner, PeerCreds,
         },
         os::windows::{
             local_socket::peer_creds::PeerCreds as PeerCredsInner,
             named_pipe::{pipe_mode::Bytes, DuplexPipeStream, RecvPipeStream, SendPipeStream},
+            winprelude::*,
         },
         Sealed,
     },
@@ -36,7 +38,9 @@ impl traits::Stream for Stream {
     type SendHalf = SendHalf;
 
     fn from_options(options: &ConnectOptions<'_>) -> io::Result<Self> {
-        let NameInner::NamedPipe(path) = &options.name.0;
+        let NameInner::NamedPipe(path) = &options.name.0 else {
+            return Err(NameError::Unsupported.into());
+        };
         let stream = StreamImpl::connect_by_path(path.as_ref()).map(Self)?;
         if options.get_nonblocking_stream() {
             stream.set_nonblocking(true)?;
@@ -73,6 +77,10 @@ impl traits::StreamCommon for Stream {
     fn peer_creds(&self) -> io::Result<PeerCreds> {
         Ok(PeerCredsInner { pid: self.0.peer_process_id()? }.into())
     }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> { pipe_name(self.0.as_handle()) }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> { pipe_name(self.0.as_handle()) }
 }
 
 impl Write for &Stream {
@@ -1,7 +1,7 @@
 use {
-    super::Stream,
+    super::{super::path_to_name, Stream},
     crate::{
-        local_socket::{traits::tokio as traits, ListenerOptions, NameInner},
+        local_socket::{traits::tokio as traits, ListenerOptions, Name, NameError, NameInner},
         os::windows::named_pipe::{
             pipe_mode, tokio::PipeListener as GenericPipeListener, PipeListenerOptions,
         },
@@ -22,16 +22,20 @@ impl traits::Listener for Listener {
 
     fn from_options(options: ListenerOptions<'_>) -> io::Result<Self> {
         let mut impl_options = PipeListenerOptions::new();
-        let NameInner::NamedPipe(path) = options.name.0;
+        let NameInner::NamedPipe(path) = options.name.0 else {
+            return Err(NameError::Unsupported.into());
+        };
         impl_options.path = path;
         impl_options.security_descriptor = options.security_descriptor;
         impl_options.create_tokio().map(Self)
     }
     async fn accept(&self) -> io::Result<Stream> {
         let inner = self.0.accept().await?;
-        Ok(Stream(inner))
+        Ok(Stream::from(inner))
     }
     fn do_not_reclaim_name_on_drop(&mut self) {}
+    #[inline]
+    fn local_name(&self) -> io::Result<Name<'static>> { Ok(path_to_name(self.0.path())) }
 }
 
 /// Access to the underlying implementation.
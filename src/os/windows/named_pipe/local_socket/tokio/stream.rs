@@ -1,12 +1,14 @@
 use {
+    super::super::pipe_name,
     crate::{
         error::{FromHandleError, ReuniteError},
         local_socket::{
+            tokio::timeouts::{Direction, Timeouts},
             traits::{
                 tokio::{self as traits, ReuniteResult},
                 StreamCommon,
             },
-            ConnectOptions, NameInner, PeerCreds,
+            ConnectOptions, Name, NameError, NameInner, PeerCreds,
         },
         os::windows::{
             local_socket::peer_creds::PeerCreds as PeerCredsInner,
@@ -19,11 +21,13 @@ use {
         Sealed,
     },
     std::{
-        io,
+        io::{self, IoSlice, IoSliceMut},
         pin::Pin,
+        sync::Arc,
         task::{Context, Poll},
+        time::Duration,
     },
-    tokio::io::AsyncWrite,
+    tokio::io::{AsyncRead, AsyncWrite, Interest, ReadBuf, Ready},
 };
 
 type StreamImpl = DuplexPipeStream<Bytes>;
@@ -32,7 +36,7 @@ type SendHalfImpl = SendPipeStream<Bytes>;
 
 /// Wrapper around [`DuplexPipeStream`] that implements the [`Stream`](traits::Stream) trait.
 #[derive(Debug)]
-pub struct Stream(pub(super) StreamImpl);
+pub struct Stream(pub(super) StreamImpl, Arc<Timeouts>);
 impl Sealed for Stream {}
 
 impl traits::Stream for Stream {
@@ -41,19 +45,59 @@ impl traits::Stream for Stream {
 
     #[inline]
     async fn from_options(options: &ConnectOptions<'_>) -> io::Result<Self> {
-        let NameInner::NamedPipe(path) = &options.name.0;
-        StreamImpl::connect_by_path(path.as_ref()).await.map(Self)
+        let NameInner::NamedPipe(path) = &options.name.0 else {
+            return Err(NameError::Unsupported.into());
+        };
+        StreamImpl::connect_by_path(path.as_ref()).await.map(Self::from)
+    }
+    #[inline]
+    fn set_recv_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Recv, timeout)
+    }
+    #[inline]
+    fn set_send_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Send, timeout)
+    }
+    #[inline]
+    fn set_idle_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set_idle(timeout)
+    }
+    #[inline]
+    async fn ready(&self, interest: Interest) -> io::Result<Ready> {
+        self.0.ready(interest).await
+    }
+    #[inline]
+    async fn readable(&self) -> io::Result<()> { self.0.readable().await }
+    #[inline]
+    async fn writable(&self) -> io::Result<()> { self.0.writable().await }
+    #[inline]
+    fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.1.record(self.0.try_read(buf))
+    }
+    #[inline]
+    fn try_read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.1.record(self.0.try_read_vectored(bufs))
+    }
+    #[inline]
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize> { self.1.record(self.0.try_write(buf)) }
+    #[inline]
+    fn try_write_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.1.record(self.0.try_write_vectored(bufs))
     }
     #[inline]
     fn split(self) -> (RecvHalf, SendHalf) {
         let (r, w) = self.0.split();
-        (RecvHalf(r), SendHalf(w))
+        (RecvHalf(r, Arc::clone(&self.1)), SendHalf(w, self.1))
     }
     #[inline]
     fn reunite(rh: RecvHalf, sh: SendHalf) -> ReuniteResult<Self> {
-        StreamImpl::reunite(rh.0, sh.0).map(Self).map_err(|ReuniteError { rh, sh }| {
-            ReuniteError { rh: RecvHalf(rh), sh: SendHalf(sh) }
-        })
+        let (RecvHalf(rh, rt), SendHalf(sh, st)) = (rh, sh);
+        match StreamImpl::reunite(rh, sh) {
+            Ok(s) => Ok(Self(s, rt)),
+            Err(ReuniteError { rh, sh }) => {
+                Err(ReuniteError { rh: RecvHalf(rh, rt), sh: SendHalf(sh, st) })
+            }
+        }
     }
 }
 impl StreamCommon for Stream {
@@ -63,6 +107,10 @@ impl StreamCommon for Stream {
     fn peer_creds(&self) -> io::Result<PeerCreds> {
         Ok(PeerCredsInner { pid: self.0.peer_process_id()? }.into())
     }
+    #[inline]
+    fn local_name(&self) -> io::Result<Option<Name<'static>>> { pipe_name(self.0.as_handle()) }
+    #[inline]
+    fn peer_name(&self) -> io::Result<Option<Name<'static>>> { pipe_name(self.0.as_handle()) }
 }
 
 /// Access to the underlying implementation.
@@ -77,6 +125,17 @@ impl Stream {
     pub fn inner_mut(&mut self) -> &mut StreamImpl { &mut self.0 }
 }
 
+impl AsyncRead for &Stream {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let slf = self.get_mut();
+        slf.1.poll(Direction::Recv, cx, |cx| Pin::new(&mut &slf.0).poll_read(cx, buf))
+    }
+}
 impl AsyncWrite for &Stream {
     #[inline]
     fn poll_write(
@@ -84,7 +143,8 @@ impl AsyncWrite for &Stream {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
-        Pin::new(&mut &self.get_mut().0).poll_write(cx, buf)
+        let slf = self.get_mut();
+        slf.1.poll(Direction::Send, cx, |cx| Pin::new(&mut &slf.0).poll_write(cx, buf))
     }
     #[inline]
     fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
@@ -101,7 +161,7 @@ impl TryFrom<OwnedHandle> for Stream {
 
     fn try_from(handle: OwnedHandle) -> Result<Self, Self::Error> {
         match StreamImpl::try_from(handle) {
-            Ok(s) => Ok(Self(s)),
+            Ok(s) => Ok(Self::from(s)),
             Err(e) => Err(FromHandleError {
                 details: Default::default(),
                 cause: Some(e.details.into()),
@@ -113,38 +173,63 @@ impl TryFrom<OwnedHandle> for Stream {
 
 multimacro! {
     Stream,
-    pinproj_for_unpin(StreamImpl),
     forward_rbv(StreamImpl, &),
     forward_as_ref(StreamImpl),
     forward_as_mut(StreamImpl),
-    forward_tokio_read,
-    forward_tokio_ref_read,
     forward_as_handle,
-    derive_tokio_mut_write,
-    derive_trivial_conv(StreamImpl),
+    derive_tokio_mut_rw,
+}
+impl From<StreamImpl> for Stream {
+    #[inline]
+    fn from(s: StreamImpl) -> Self { Self(s, Arc::default()) }
+}
+impl From<Stream> for StreamImpl {
+    #[inline]
+    fn from(s: Stream) -> Self { s.0 }
 }
 
 /// Wrapper around [`RecvPipeStream`] that implements [`RecvHalf`](traits::RecvHalf).
-pub struct RecvHalf(pub(super) RecvHalfImpl);
+pub struct RecvHalf(pub(super) RecvHalfImpl, Arc<Timeouts>);
 impl Sealed for RecvHalf {}
 multimacro! {
     RecvHalf,
-    pinproj_for_unpin(RecvHalfImpl),
     forward_rbv(RecvHalfImpl, &),
     forward_as_ref(RecvHalfImpl),
     forward_as_mut(RecvHalfImpl),
-    forward_tokio_read,
-    forward_tokio_ref_read,
     forward_as_handle,
     forward_debug("local_socket::RecvHalf"),
-    derive_trivial_conv(RecvHalfImpl),
+    derive_tokio_mut_read,
 }
 impl traits::RecvHalf for RecvHalf {
     type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Recv, timeout)
+    }
+}
+impl AsyncRead for &RecvHalf {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let slf = self.get_mut();
+        slf.1.poll(Direction::Recv, cx, |cx| Pin::new(&mut &slf.0).poll_read(cx, buf))
+    }
+}
+impl From<RecvHalfImpl> for RecvHalf {
+    #[inline]
+    fn from(rh: RecvHalfImpl) -> Self { Self(rh, Arc::default()) }
+}
+impl From<RecvHalf> for RecvHalfImpl {
+    #[inline]
+    fn from(rh: RecvHalf) -> Self { rh.0 }
 }
 
 /// Wrapper around [`SendPipeStream`] that implements [`SendHalf`](traits::SendHalf).
-pub struct SendHalf(pub(super) SendHalfImpl);
+pub struct SendHalf(pub(super) SendHalfImpl, Arc<Timeouts>);
 impl Sealed for SendHalf {}
 multimacro! {
     SendHalf,
@@ -154,10 +239,22 @@ multimacro! {
     forward_as_handle,
     forward_debug("local_socket::SendHalf"),
     derive_tokio_mut_write,
-    derive_trivial_conv(SendHalfImpl),
 }
 impl traits::SendHalf for SendHalf {
     type Stream = Stream;
+
+    #[inline]
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.1.set(Direction::Send, timeout)
+    }
+}
+impl From<SendHalfImpl> for SendHalf {
+    #[inline]
+    fn from(sh: SendHalfImpl) -> Self { Self(sh, Arc::default()) }
+}
+impl From<SendHalf> for SendHalfImpl {
+    #[inline]
+    fn from(sh: SendHalf) -> Self { sh.0 }
 }
 impl AsyncWrite for &SendHalf {
     #[inline]
@@ -166,7 +263,8 @@ impl AsyncWrite for &SendHalf {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
-        Pin::new(&mut &self.get_mut().0).poll_write(cx, buf)
+        let slf = self.get_mut();
+        slf.1.poll(Direction::Send, cx, |cx| Pin::new(&mut &slf.0).poll_write(cx, buf))
     }
     #[inline]
     fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
@@ -85,6 +85,9 @@ impl<Rm: PipeModeTag, Sm: PipeModeTag> PipeListener<Rm, Sm> {
         Ok(Self::from_tokio_and_options(npserver_from_handle(handle)?, options))
     }
 
+    /// Returns the path the listener creates instances of the named pipe at.
+    pub(crate) fn path(&self) -> &widestring::U16CStr { &self.config.path }
+
     fn create_instance(&self) -> io::Result<TokioNPServer> {
         self.config
             .create_instance(false, false, Self::STREAM_ROLE, Rm::MODE)
@@ -12,6 +12,7 @@ macro_rules! same_clsrv {
 mod ctor;
 mod debug;
 mod handle;
+mod ready;
 mod recv_bytes;
 mod send;
 
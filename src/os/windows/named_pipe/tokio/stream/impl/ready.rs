@@ -0,0 +1,65 @@
+use {
+    super::*,
+    crate::os::windows::downgrade_eof,
+    std::io::{IoSlice, IoSliceMut},
+    tokio::io::{Interest, Ready},
+};
+
+/// Readiness-based I/O.
+impl<Rm: PipeModeTag, Sm: PipeModeTag> PipeStream<Rm, Sm> {
+    /// Waits for any of the requested ready states. See
+    /// [`NamedPipeClient::ready()`](TokioNPClient::ready) for more.
+    #[inline]
+    pub async fn ready(&self, interest: Interest) -> io::Result<Ready> {
+        same_clsrv!(x in self.raw.get().inner => x.ready(interest).await)
+    }
+    /// Waits for the pipe to become readable. See [`.ready()`](Self::ready) for more.
+    #[inline]
+    pub async fn readable(&self) -> io::Result<()> {
+        same_clsrv!(x in self.raw.get().inner => x.readable().await)
+    }
+    /// Waits for the pipe to become writable. See [`.ready()`](Self::ready) for more.
+    #[inline]
+    pub async fn writable(&self) -> io::Result<()> {
+        same_clsrv!(x in self.raw.get().inner => x.writable().await)
+    }
+}
+
+impl<Sm: PipeModeTag> PipeStream<pipe_mode::Bytes, Sm> {
+    /// Tries to receive data from the pipe into the provided buffer without waiting, returning
+    /// [`WouldBlock`](io::ErrorKind::WouldBlock) if no data is available.
+    ///
+    /// As with [`AsyncRead`](tokio::io::AsyncRead), the other end having disconnected is reported
+    /// as end of file.
+    #[inline]
+    pub fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        downgrade_eof(same_clsrv!(x in self.raw.get().inner => x.try_read(buf)))
+    }
+    /// Like [`.try_read()`](Self::try_read), but reads into a sequence of buffers.
+    #[inline]
+    pub fn try_read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        downgrade_eof(same_clsrv!(x in self.raw.get().inner => x.try_read_vectored(bufs)))
+    }
+}
+
+impl<Rm: PipeModeTag> PipeStream<Rm, pipe_mode::Bytes> {
+    /// Tries to send data from the provided buffer into the pipe without waiting, returning
+    /// [`WouldBlock`](io::ErrorKind::WouldBlock) if the pipe is not ready to accept data.
+    #[inline]
+    pub fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        self.mark_dirty_on_success(same_clsrv!(x in self.raw.get().inner => x.try_write(buf)))
+    }
+    /// Like [`.try_write()`](Self::try_write), but writes from a sequence of buffers.
+    #[inline]
+    pub fn try_write_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.mark_dirty_on_success(
+            same_clsrv!(x in self.raw.get().inner => x.try_write_vectored(bufs)),
+        )
+    }
+    fn mark_dirty_on_success(&self, rslt: io::Result<usize>) -> io::Result<usize> {
+        if rslt.is_ok() {
+            self.raw.get().needs_flush.mark_dirty();
+        }
+        rslt
+    }
+}
@@ -0,0 +1,155 @@
+//! Directories that only the current user has access to.
+
+use {
+    super::security_descriptor::{
+        create_security_attributes, AsSecurityDescriptorExt as _, LocalBox, SecurityDescriptor,
+    },
+    crate::{OrErrno as _, RawOsErrorExt as _, SubUsizeExt as _},
+    std::{ffi::c_void, fs, io, mem::size_of, os::windows::prelude::*, path::Path, ptr},
+    widestring::{U16CStr, U16CString},
+    windows_sys::Win32::{
+        Foundation::ERROR_ALREADY_EXISTS,
+        Security::{
+            Authorization::{
+                ConvertSidToStringSidW, GetNamedSecurityInfoW, SetNamedSecurityInfoW,
+                SE_FILE_OBJECT,
+            },
+            EqualSid, GetTokenInformation, TokenUser, DACL_SECURITY_INFORMATION,
+            OWNER_SECURITY_INFORMATION, PROTECTED_DACL_SECURITY_INFORMATION, TOKEN_QUERY,
+            TOKEN_USER,
+        },
+        Storage::FileSystem::CreateDirectoryW,
+        System::Threading::{GetCurrentProcess, OpenProcessToken},
+    },
+};
+
+/// Creates a directory that is owned by the current user and grants access to nobody else, or
+/// makes an existing one such if it is owned by the current user.
+///
+/// Fails with [`PermissionDenied`](io::ErrorKind::PermissionDenied) if the path exists but is
+/// not a directory, is a symbolic link or junction, or is owned by a different user.
+pub(crate) fn create_private_dir(path: &Path) -> io::Result<()> {
+    let user = TokenUserBuf::current()?;
+    let sid = user.sid();
+    let sid_string = sid_to_string(sid)?;
+    // Full access for the owner only, without inheriting any entries from the parent directory
+    let sddl = format!("O:{sid_string}D:P(A;OICI;FA;;;{sid_string})");
+    let sd =
+        SecurityDescriptor::deserialize(&U16CString::from_str(sddl).map_err(io::Error::other)?)?;
+    let wpath = U16CString::from_os_str(path).map_err(io::Error::other)?;
+
+    let attrs = create_security_attributes(Some(sd.borrow()), false);
+    // SAFETY: the path is nul-terminated and the security attributes are valid
+    let created = unsafe { CreateDirectoryW(wpath.as_ptr(), &attrs) };
+    if created != 0 {
+        return Ok(());
+    }
+    let e = io::Error::last_os_error();
+    if !e.raw_os_error().eeq(ERROR_ALREADY_EXISTS) {
+        return Err(e);
+    }
+
+    let meta = fs::symlink_metadata(path)?;
+    if !meta.is_dir() || meta.file_type().is_symlink() {
+        return Err(not_private("is not a directory"));
+    }
+    let mut owner = ptr::null_mut();
+    let mut existing_sd = ptr::null_mut();
+    // SAFETY: the path is nul-terminated and the out-pointers are valid
+    let err = unsafe {
+        GetNamedSecurityInfoW(
+            wpath.as_ptr(),
+            SE_FILE_OBJECT,
+            OWNER_SECURITY_INFORMATION,
+            &mut owner,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut existing_sd,
+        )
+    };
+    if err != 0 {
+        return Err(io::Error::from_raw_os_error(err as _));
+    }
+    // SAFETY: allocated on the local heap by GetNamedSecurityInfoW; the owner SID points into it
+    let _existing_sd = unsafe { LocalBox::from_raw(existing_sd) };
+    // SAFETY: both SIDs are valid
+    if unsafe { EqualSid(owner, sid) } == 0 {
+        return Err(not_private("is owned by a different user"));
+    }
+
+    // The owner may have loosened the DACL, so it is replaced with the owner-only one
+    let dacl = sd.dacl()?.map_or(ptr::null(), |(dacl, _)| dacl);
+    // SAFETY: as above, and the DACL belongs to the security descriptor, which outlives the call
+    let err = unsafe {
+        SetNamedSecurityInfoW(
+            wpath.as_ptr().cast_mut(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION | PROTECTED_DACL_SECURITY_INFORMATION,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            dacl,
+            ptr::null(),
+        )
+    };
+    if err != 0 {
+        return Err(io::Error::from_raw_os_error(err as _));
+    }
+    Ok(())
+}
+
+fn not_private(what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        format!("refusing to use the directory for port files, as it {what}"),
+    )
+}
+
+/// `TOKEN_USER` of the current process, along with the SID that it points to.
+struct TokenUserBuf(Vec<usize>);
+impl TokenUserBuf {
+    fn current() -> io::Result<Self> {
+        let mut token = ptr::null_mut();
+        // SAFETY: the pseudo-handle of the current process is always valid
+        unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) }
+            .true_val_or_errno(())?;
+        // SAFETY: just opened
+        let token = unsafe { OwnedHandle::from_raw_handle(token) };
+
+        let mut len = 0;
+        // SAFETY: querying the required length only; fails with ERROR_INSUFFICIENT_BUFFER
+        unsafe {
+            GetTokenInformation(token.as_raw_handle(), TokenUser, ptr::null_mut(), 0, &mut len)
+        };
+        // Made of usizes for the alignment of the pointer in TOKEN_USER
+        let size = len.to_usize().max(size_of::<TOKEN_USER>());
+        let mut buf = vec![0_usize; size.div_ceil(size_of::<usize>())];
+        let buflen = u32::try_from(size).map_err(io::Error::other)?;
+        // SAFETY: the buffer is valid for writes of the given length
+        unsafe {
+            GetTokenInformation(
+                token.as_raw_handle(),
+                TokenUser,
+                buf.as_mut_ptr().cast(),
+                buflen,
+                &mut len,
+            )
+        }
+        .true_val_or_errno(())?;
+        Ok(Self(buf))
+    }
+    fn sid(&self) -> *mut c_void {
+        // SAFETY: filled in by GetTokenInformation, with sufficient size and alignment
+        unsafe { (*self.0.as_ptr().cast::<TOKEN_USER>()).User.Sid }
+    }
+}
+
+fn sid_to_string(sid: *mut c_void) -> io::Result<String> {
+    let mut string = ptr::null_mut();
+    // SAFETY: the SID is valid
+    unsafe { ConvertSidToStringSidW(sid, &mut string) }.true_val_or_errno(())?;
+    // SAFETY: allocated on the local heap by ConvertSidToStringSidW and nul-terminated
+    let string = unsafe { LocalBox::from_raw(string) };
+    // SAFETY: as above
+    Ok(unsafe { U16CStr::from_ptr_str(string.as_ptr()) }.to_string_lossy())
+}
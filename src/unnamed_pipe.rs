@@ -20,6 +20,9 @@
 //! # Examples
 //! See [`pipe()`].
 
+#[cfg(all(unix, feature = "async"))]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(all(unix, feature = "async"))))]
+pub mod async_io;
 #[cfg(feature = "tokio")]
 #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
 pub mod tokio;
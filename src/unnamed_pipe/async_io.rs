@@ -0,0 +1,75 @@
+//! async-io-based asynchronous unnamed pipes.
+//!
+//! The pipe ends are registered with the reactor of the [`async-io`](https://docs.rs/async-io)
+//! crate and implement the `futures-io` traits, which makes them usable with any async runtime.
+//! See the [parent-level documentation](super) for more.
+//!
+//! # Examples
+//! See [`pipe()`].
+
+use {
+    crate::os::unix::unnamed_pipe::async_io::{
+        pipe_impl, Recver as RecverImpl, Sender as SenderImpl,
+    },
+    std::io,
+};
+
+/// Creates a new pipe with the default creation settings and returns async-io-based handles to
+/// its sending end and receiving end.
+///
+/// # Examples
+/// ```
+/// use futures_lite::io::{AsyncReadExt as _, AsyncWriteExt as _};
+/// # async_io::block_on(async {
+/// let (mut tx, mut rx) = interprocess::unnamed_pipe::async_io::pipe()?;
+/// tx.write_all(b"Hello!").await?;
+/// drop(tx);
+/// let mut msg = String::new();
+/// rx.read_to_string(&mut msg).await?;
+/// assert_eq!(msg, "Hello!");
+/// # std::io::Result::Ok(()) }).unwrap();
+/// ```
+#[inline]
+pub fn pipe() -> io::Result<(Sender, Recver)> { pipe_impl() }
+
+/// async-io-based handle to the receiving end of an unnamed pipe, created by the [`pipe()`]
+/// function together with the [sending end](Sender).
+///
+/// The core functionality is exposed via the [`AsyncRead`](futures_io::AsyncRead) trait. The type
+/// is convertible to and from file descriptors and allows its internal file descriptor to be
+/// borrowed.
+///
+/// The file descriptor is inheritable. See [module-level documentation](super) for more on how
+/// this can be used.
+pub struct Recver(pub(crate) RecverImpl);
+multimacro! {
+    Recver,
+    forward_rbv(RecverImpl, &),
+    forward_futures_ref_read,
+    derive_futures_mut_read,
+    forward_as_handle(unix),
+    forward_try_handle(io::Error, unix),
+    forward_debug,
+    derive_asraw(unix),
+}
+
+/// async-io-based handle to the sending end of an unnamed pipe, created by the [`pipe()`]
+/// function together with the [receiving end](Recver).
+///
+/// The core functionality is exposed via the [`AsyncWrite`](futures_io::AsyncWrite) trait. The
+/// type is convertible to and from file descriptors and allows its internal file descriptor to be
+/// borrowed.
+///
+/// The file descriptor is inheritable. See [module-level documentation](super) for more on how
+/// this can be used.
+pub struct Sender(pub(crate) SenderImpl);
+multimacro! {
+    Sender,
+    forward_rbv(SenderImpl, &),
+    forward_futures_ref_write,
+    derive_futures_mut_write,
+    forward_as_handle(unix),
+    forward_try_handle(io::Error, unix),
+    forward_debug,
+    derive_asraw(unix),
+}
@@ -52,6 +52,15 @@ multimacro! {
     derive_asraw,
 }
 
+/// Duplicates the file descriptor and registers the duplicate with the Tokio runtime of the current
+/// context, panicking if there is none.
+#[cfg(unix)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl crate::TryClone for Recver {
+    #[inline]
+    fn try_clone(&self) -> io::Result<Self> { self.0.try_clone().map(Self) }
+}
+
 /// Handle to the sending end of an unnamed pipe, created by the [`pipe()`] function together with
 /// the [receiving end](Recver).
 ///
@@ -72,3 +81,12 @@ multimacro! {
     forward_debug,
     derive_asraw,
 }
+
+/// Duplicates the file descriptor and registers the duplicate with the Tokio runtime of the current
+/// context, panicking if there is none.
+#[cfg(unix)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+impl crate::TryClone for Sender {
+    #[inline]
+    fn try_clone(&self) -> io::Result<Self> { self.0.try_clone().map(Self) }
+}
@@ -6,10 +6,30 @@ mod os {
     #[cfg(unix)]
     mod unix {
         mod local_socket {
+            mod accept_timeout;
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            mod autobind;
             mod fake_ns;
+            mod long_path;
             mod mode;
+            mod owner;
+            mod parent_dir;
+            mod runtime_dir;
+            mod shutdown;
             mod try_overwrite;
         }
+        #[cfg(feature = "async")]
+        mod async_io;
+        mod fifo_file;
+        #[cfg(all(target_os = "linux", feature = "io_uring"))]
+        mod io_uring;
+        #[cfg(feature = "mio")]
+        mod mio;
+        mod selector;
+        #[cfg(feature = "tokio")]
+        mod tokio_conversion;
+        #[cfg(feature = "tokio")]
+        mod tokio_fifo_file;
     }
     #[cfg(windows)]
     mod windows {
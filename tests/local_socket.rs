@@ -1,9 +1,16 @@
 // TODO test various error conditions
 
+mod name_error;
+mod name_generator;
+mod name_text;
+mod names;
 mod no_client;
 mod no_server;
+mod resilient;
 mod stream;
+mod tcp;
 mod timeout;
+mod verify_server;
 
 use {
     crate::{local_socket::prelude::*, tests::util::*},
@@ -26,8 +33,9 @@ fn test_stream(id: &'static str, path: bool) -> TestResult {
 
 #[allow(unused_imports)]
 use {
-    no_client::run_and_verify_error as test_no_client,
-    no_server::run_and_verify_error as test_no_server, timeout::main as test_timeout,
+    names::main as test_names, no_client::run_and_verify_error as test_no_client,
+    no_server::run_and_verify_error as test_no_server, tcp::roundtrip as test_tcp,
+    timeout::main as test_timeout, verify_server::main as test_verify_server,
 };
 
 macro_rules! tests {
@@ -45,6 +53,11 @@ tests! {test_stream
     stream_namespaced false
 }
 
+tests! {test_names
+    names_file       true
+    names_namespaced false
+}
+
 tests! {test_no_server
     no_server_file       true
     no_server_namespaced false
@@ -55,6 +68,28 @@ tests! {test_no_client
     no_client_namespaced false
 }
 
+tests! {test_verify_server
+    verify_server_file       true
+    verify_server_namespaced false
+}
+
+tests! {test_tcp
+    tcp_file       true
+    tcp_namespaced false
+}
+
+#[test]
+fn tcp_bad_token() -> TestResult { test_wrapper(|| tcp::bad_token(make_id!())) }
+#[test]
+fn tcp_overwrite() -> TestResult { test_wrapper(|| tcp::overwrite(make_id!())) }
+#[test]
+fn tcp_silent_client() -> TestResult { test_wrapper(|| tcp::silent_client(make_id!())) }
+#[test]
+fn tcp_nonblocking() -> TestResult { test_wrapper(|| tcp::nonblocking(make_id!())) }
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn tcp_selector() -> TestResult { test_wrapper(|| tcp::selector(make_id!())) }
+
 #[cfg(not(windows))]
 tests! {test_timeout
     timeout_file       true
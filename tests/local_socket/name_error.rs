@@ -0,0 +1,72 @@
+//! Tests the structured errors returned by local socket name mapping.
+
+use {
+    crate::{
+        local_socket::{prelude::*, GenericFilePath, GenericNamespaced, NameError},
+        tests::util::*,
+    },
+    std::io,
+};
+
+#[test]
+fn interior_nul() -> TestResult {
+    test_wrapper(|| {
+        let path = if cfg!(windows) { "\\\\.\\pipe\\a\0b" } else { "/tmp/a\0b" };
+        let err = path.to_fs_name::<GenericFilePath>().err();
+        let pos = path.find('\0').unwrap();
+        ensure_eq!(matches!(err, Some(NameError::InteriorNul(p)) if p == pos), true);
+        ensure_eq!(io::Error::from(err.unwrap()).kind(), io::ErrorKind::InvalidInput);
+        Ok(())
+    })
+}
+
+#[test]
+fn namespaced_interior_nul() -> TestResult {
+    test_wrapper(|| {
+        let err = "a\0b".to_ns_name::<GenericNamespaced>().err();
+        ensure_eq!(matches!(err, Some(NameError::InteriorNul(1))), true);
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            use crate::os::unix::local_socket::AbstractNsUdSocket;
+            ensure_eq!("a\0b".to_ns_name::<AbstractNsUdSocket>().is_ok(), true);
+        }
+        Ok(())
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn too_long() -> TestResult {
+    use crate::os::unix::local_socket::AbstractNsUdSocket;
+    test_wrapper(|| {
+        let name = "a".repeat(200);
+        let err = name.to_ns_name::<AbstractNsUdSocket>().err();
+        ensure_eq!(matches!(err, Some(NameError::TooLong { max: 107, actual: 200 })), true);
+        ensure_eq!("a".repeat(107).to_ns_name::<AbstractNsUdSocket>().is_ok(), true);
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn invalid_component() -> TestResult {
+    use crate::os::unix::local_socket::RuntimeDirUdSocket;
+    test_wrapper(|| {
+        let err = "sub/dir.sock".to_ns_name::<RuntimeDirUdSocket>().err();
+        ensure_eq!(matches!(err, Some(NameError::InvalidComponent)), true);
+        let err = "nul\0.sock".to_ns_name::<RuntimeDirUdSocket>().err();
+        ensure_eq!(matches!(err, Some(NameError::InteriorNul(3))), true);
+        Ok(())
+    })
+}
+
+#[cfg(windows)]
+#[test]
+fn invalid_prefix() -> TestResult {
+    test_wrapper(|| {
+        let err = r"C:\Users\interprocess.sock".to_fs_name::<GenericFilePath>().err();
+        ensure_eq!(matches!(err, Some(NameError::InvalidPrefix)), true);
+        ensure_eq!(io::Error::from(err.unwrap()).kind(), io::ErrorKind::Unsupported);
+        Ok(())
+    })
+}
@@ -0,0 +1,47 @@
+//! Tests the generator of unique local socket names.
+
+use {
+    crate::{
+        local_socket::{prelude::*, GenericNamespaced, ListenerOptions, NameGenerator, Stream},
+        tests::util::*,
+    },
+    std::collections::HashSet,
+};
+
+#[test]
+fn unique() -> TestResult {
+    test_wrapper(|| {
+        let names = NameGenerator::<GenericNamespaced>::with_prefix("interprocess-test-")?
+            .take(64)
+            .map(|nm| nm.map(|nm| nm.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        ensure_eq!(names.iter().collect::<HashSet<_>>().len(), names.len());
+        for name in &names {
+            ensure_eq!(name.contains("interprocess-test-"), true);
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn create() -> TestResult {
+    test_wrapper(|| {
+        let namegen = NameGenerator::<GenericNamespaced>::new()?;
+        let listener = namegen.create_sync(ListenerOptions::new()).opname("listener bind")?;
+        let name = listener.local_name().opname("local_name")?;
+        let _ = Stream::connect(name).opname("client connect")?;
+        let _ = listener.accept().opname("accept")?;
+        Ok(())
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn prefix_too_long() -> TestResult {
+    use crate::{local_socket::NameError, os::unix::local_socket::AbstractNsUdSocket};
+    test_wrapper(|| {
+        let err = NameGenerator::<AbstractNsUdSocket>::with_prefix("a".repeat(100)).err();
+        ensure_eq!(matches!(err, Some(NameError::TooLong { max: 107, actual: 116 })), true);
+        Ok(())
+    })
+}
@@ -0,0 +1,156 @@
+//! Tests the textual representation of local socket names.
+
+use {
+    crate::{
+        local_socket::{prelude::*, GenericNamespaced, LoopbackTcp, Name, NameParseError},
+        tests::util::*,
+    },
+    std::ffi::OsStr,
+};
+
+fn parse(s: &str) -> Result<Name<'static>, NameParseError> { s.parse() }
+
+fn roundtrip(name: &Name<'_>) -> TestResult {
+    let parsed = parse(&name.to_string()).opname("parse")?;
+    ensure_eq!(&parsed, name);
+    Ok(())
+}
+
+#[test]
+fn namespaced() -> TestResult {
+    test_wrapper(|| {
+        let name = "interprocess-test%name".to_ns_name::<GenericNamespaced>()?;
+        roundtrip(&name)?;
+        ensure_eq!(parse("ns:interprocess-test%25name")?, name);
+        Ok(())
+    })
+}
+
+#[test]
+fn tcp() -> TestResult {
+    test_wrapper(|| {
+        let name = "interprocess-test.port".to_ns_name::<LoopbackTcp>()?;
+        ensure_eq!(name.to_string().starts_with("tcp:"), true);
+        roundtrip(&name)?;
+        let path = std::env::temp_dir().join("interprocess test.port");
+        let name = path.as_path().to_fs_name::<LoopbackTcp>()?;
+        roundtrip(&name)?;
+        Ok(())
+    })
+}
+
+#[test]
+fn errors() -> TestResult {
+    test_wrapper(|| {
+        ensure_eq!(matches!(parse("no-scheme"), Err(NameParseError::MissingScheme)), true);
+        ensure_eq!(
+            matches!(parse("udp:127.0.0.1"), Err(NameParseError::UnknownScheme(s)) if s == "udp"),
+            true
+        );
+        ensure_eq!(matches!(parse("ns:bad%2"), Err(NameParseError::InvalidEscape(6))), true);
+        ensure_eq!(matches!(parse("ns:bad%+1"), Err(NameParseError::InvalidEscape(6))), true);
+        let unsupported = if cfg!(windows) { "unix:/tmp/x.sock" } else { r"pipe:\\.\pipe\x" };
+        ensure_eq!(
+            matches!(parse(unsupported), Err(NameParseError::UnsupportedScheme(..))),
+            true
+        );
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn unix() -> TestResult {
+    use {crate::os::unix::local_socket::FilesystemUdSocket, std::os::unix::ffi::OsStrExt};
+    test_wrapper(|| {
+        let name = "/tmp/interprocess test.sock".to_fs_name::<FilesystemUdSocket>()?;
+        ensure_eq!(name.to_string(), "unix:/tmp/interprocess test.sock");
+        roundtrip(&name)?;
+        let name = OsStr::from_bytes(b"/tmp/\xFF\n.sock").to_fs_name::<FilesystemUdSocket>()?;
+        ensure_eq!(name.to_string(), "unix:/tmp/%FF%0A.sock");
+        roundtrip(&name)?;
+        ensure_eq!(matches!(parse("unix:/tmp/%00"), Err(NameParseError::InvalidName(..))), true);
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(deprecated)]
+fn special_dir() -> TestResult {
+    use crate::os::unix::local_socket::SpecialDirUdSocket;
+    test_wrapper(|| {
+        let name = "interprocess-test.sock".to_ns_name::<SpecialDirUdSocket>()?;
+        ensure_eq!(name.to_string(), "specialdir:interprocess-test.sock");
+        // On Linux, this must not be parsed back as an abstract namespace name
+        roundtrip(&name)?;
+        Ok(())
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn abstract_ns() -> TestResult {
+    use {crate::os::unix::local_socket::AbstractNsUdSocket, std::os::unix::ffi::OsStrExt};
+    test_wrapper(|| {
+        let name = OsStr::from_bytes(b"\0bus\0").to_ns_name::<AbstractNsUdSocket>()?;
+        ensure_eq!(name.to_string(), "abstract:%00bus%00");
+        roundtrip(&name)?;
+        ensure_eq!(parse("ns:app")?.to_string(), "abstract:app");
+        Ok(())
+    })
+}
+
+#[cfg(windows)]
+#[test]
+fn pipe() -> TestResult {
+    use {crate::os::windows::local_socket::NamedPipe, std::os::windows::ffi::OsStringExt};
+    test_wrapper(|| {
+        let name = r"\\.\pipe\interprocess-test".to_fs_name::<NamedPipe>()?;
+        ensure_eq!(name.to_string(), r"pipe:\\.\pipe\interprocess-test");
+        roundtrip(&name)?;
+        let mut wide = r"\\.\pipe\".encode_utf16().collect::<Vec<_>>();
+        wide.push(0xD800);
+        let name = std::ffi::OsString::from_wide(&wide).to_fs_name::<NamedPipe>()?;
+        ensure_eq!(name.to_string(), r"pipe:\\.\pipe\%uD800");
+        roundtrip(&name)?;
+        Ok(())
+    })
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() -> TestResult {
+    test_wrapper(|| {
+        let name = "interprocess-test%name".to_ns_name::<GenericNamespaced>()?;
+        let json = serde_json::to_string(&name).opname("serialize")?;
+        let text = name.to_string();
+        let (scheme, body) = text.split_once(':').unwrap();
+        ensure_eq!(json, format!(r#"{{"{scheme}":"{body}"}}"#));
+        ensure_eq!(serde_json::from_str::<Name<'static>>(&json).opname("deserialize")?, name);
+        ensure_eq!(
+            serde_json::from_str::<Name<'static>>(r#"{"ns":"interprocess-test%25name"}"#)?,
+            name
+        );
+        ensure_eq!(serde_json::from_str::<Name<'static>>(r#"{"ns":"bad%2"}"#).is_err(), true);
+        ensure_eq!(
+            serde_json::from_str::<Name<'static>>(r#"{"udp":"127.0.0.1"}"#).is_err(),
+            true
+        );
+        let unsupported = if cfg!(windows) {
+            r#"{"unix":"/tmp/x.sock"}"#
+        } else {
+            r#"{"pipe":"\\\\.\\pipe\\x"}"#
+        };
+        ensure_eq!(serde_json::from_str::<Name<'static>>(unsupported).is_err(), true);
+        #[cfg(unix)]
+        {
+            use crate::os::unix::local_socket::FilesystemUdSocket;
+            let name = "/tmp/interprocess\n.sock".to_fs_name::<FilesystemUdSocket>()?;
+            let json = serde_json::to_string(&name).opname("serialize")?;
+            ensure_eq!(json, r#"{"unix":"/tmp/interprocess%0A.sock"}"#);
+            ensure_eq!(serde_json::from_str::<Name<'static>>(&json).opname("deserialize")?, name);
+        }
+        Ok(())
+    })
+}
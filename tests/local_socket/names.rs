@@ -0,0 +1,30 @@
+//! Tests retrieval of local and peer names of listeners and streams.
+
+use crate::{
+    local_socket::{prelude::*, ListenerOptions, Stream},
+    tests::util::*,
+};
+
+pub fn main(id: &str, path: bool) -> TestResult {
+    let (name, listener) = listen_and_pick_name(&mut namegen_local_socket(id, path), |nm| {
+        ListenerOptions::new().name(nm.borrow()).create_sync()
+    })?;
+    let local = listener.local_name().opname("listener local_name")?;
+    // Without an abstract namespace, namespaced names are resolved to filesystem paths.
+    if name.is_path() || cfg!(any(windows, target_os = "linux", target_os = "android")) {
+        ensure_eq!(local, name);
+    }
+
+    let client = Stream::connect(local.borrow()).opname("connect")?;
+    let server = listener.accept().opname("accept")?;
+    ensure_eq!(client.peer_name().opname("client peer_name")?, Some(local.clone()));
+    if cfg!(windows) {
+        ensure_eq!(client.local_name().opname("client local_name")?, Some(local.clone()));
+        ensure_eq!(server.peer_name().opname("server peer_name")?, Some(local.clone()));
+    } else {
+        ensure_eq!(client.local_name().opname("client local_name")?, None);
+        ensure_eq!(server.peer_name().opname("server peer_name")?, None);
+    }
+    ensure_eq!(server.local_name().opname("server local_name")?, Some(local));
+    Ok(())
+}
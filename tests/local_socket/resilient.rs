@@ -0,0 +1,127 @@
+//! Tests the resilient accept loop using a listener that fails on demand.
+
+use {
+    crate::{
+        local_socket::{
+            prelude::*, traits, AcceptErrorClass, AcceptStats, Listener, ListenerNonblockingMode,
+            ListenerOptions, Name, ResilientAccept, Stream,
+        },
+        tests::util::*,
+        Sealed,
+    },
+    std::{
+        collections::VecDeque,
+        io::{self, prelude::*},
+        iter::FusedIterator,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+};
+
+/// Listener that returns the queued errors before delegating to a real one.
+#[derive(Debug)]
+struct Flaky {
+    listener: Listener,
+    errors: Mutex<VecDeque<io::Error>>,
+}
+impl Sealed for Flaky {}
+impl traits::Listener for Flaky {
+    type Stream = Stream;
+    fn from_options(options: ListenerOptions<'_>) -> io::Result<Self> {
+        let listener = Listener::from_options(options)?;
+        Ok(Self { listener, errors: Mutex::default() })
+    }
+    fn accept(&self) -> io::Result<Stream> {
+        let e = self.errors.lock().unwrap().pop_front();
+        e.map_or_else(|| self.listener.accept(), Err)
+    }
+    fn set_nonblocking(&self, nonblocking: ListenerNonblockingMode) -> io::Result<()> {
+        self.listener.set_nonblocking(nonblocking)
+    }
+    fn do_not_reclaim_name_on_drop(&mut self) { self.listener.do_not_reclaim_name_on_drop() }
+    fn local_name(&self) -> io::Result<Name<'static>> { self.listener.local_name() }
+}
+impl Iterator for Flaky {
+    type Item = io::Result<Stream>;
+    fn next(&mut self) -> Option<Self::Item> { Some(traits::Listener::accept(self)) }
+}
+impl FusedIterator for Flaky {}
+
+fn out_of_fds() -> io::Error {
+    #[cfg(unix)]
+    {
+        io::Error::from_raw_os_error(libc::EMFILE)
+    }
+    #[cfg(not(unix))]
+    {
+        io::Error::from(io::ErrorKind::OutOfMemory)
+    }
+}
+
+#[test]
+fn classify() -> TestResult {
+    use {io::ErrorKind::*, AcceptErrorClass::*};
+    test_wrapper(|| {
+        for (kind, class) in [
+            (ConnectionAborted, Transient),
+            (Interrupted, Transient),
+            (OutOfMemory, ResourceExhaustion),
+            (WouldBlock, Fatal),
+            (TimedOut, Fatal),
+            (InvalidInput, Fatal),
+        ] {
+            ensure_eq!(AcceptErrorClass::of(&io::Error::from(kind)), class);
+        }
+        ensure_eq!(AcceptErrorClass::of(&out_of_fds()), ResourceExhaustion);
+        Ok(())
+    })
+}
+
+#[test]
+fn main() -> TestResult {
+    test_wrapper(|| {
+        let (name, listener) =
+            listen_and_pick_name(&mut namegen_local_socket(make_id!(), true), |nm| {
+                ListenerOptions::new().name(nm.borrow()).create_sync_as::<Flaky>()
+            })?;
+        let classes = Arc::new(Mutex::new(Vec::new()));
+        let mut resilient = ResilientAccept::new()
+            .backoff(Duration::from_millis(1), Duration::from_millis(4))
+            .on_error({
+                let classes = Arc::clone(&classes);
+                move |class, _| classes.lock().unwrap().push(class)
+            });
+
+        let mut first = Stream::connect(name.borrow()).opname("first client connect")?;
+        let _second = Stream::connect(name.borrow()).opname("second client connect")?;
+        listener.errors.lock().unwrap().extend([
+            io::Error::from(io::ErrorKind::ConnectionAborted),
+            io::Error::from(io::ErrorKind::Interrupted),
+            out_of_fds(),
+        ]);
+        let _ = resilient.accept(&listener).opname("resilient accept")?;
+        let dropped = u64::from(cfg!(unix));
+        ensure_eq!(resilient.stats(), AcceptStats {
+            transient: 2,
+            resource_exhaustion: 1,
+            fatal: 0,
+            dropped
+        });
+        if cfg!(unix) {
+            // The first client was disconnected to make room
+            ensure_eq!(first.read(&mut [0]).opname("first client receive")?, 0);
+        }
+
+        listener.errors.lock().unwrap().push_back(io::Error::from(io::ErrorKind::InvalidInput));
+        let err = resilient.accept(&listener).err();
+        ensure_eq!(err.map(|e| e.kind()), Some(io::ErrorKind::InvalidInput));
+        ensure_eq!(resilient.stats().fatal, 1);
+        ensure_eq!(*classes.lock().unwrap(), [
+            AcceptErrorClass::Transient,
+            AcceptErrorClass::Transient,
+            AcceptErrorClass::ResourceExhaustion,
+            AcceptErrorClass::Fatal,
+        ]);
+        Ok(())
+    })
+}
@@ -39,6 +39,11 @@ fn check_peer_creds(s: &Stream) -> TestResult {
             ensure_eq!(gid, unsafe { libc::getegid() });
         }
     }
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_value(creds).opname("serialize")?;
+        ensure_eq!(json["pid"], serde_json::to_value(creds.pid())?);
+    }
     Ok(())
 }
 
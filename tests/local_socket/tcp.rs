@@ -0,0 +1,197 @@
+//! Tests the loopback TCP implementation of local sockets.
+
+use {
+    crate::{
+        local_socket::{
+            prelude::*, Listener, ListenerNonblockingMode, ListenerOptions, Name, NameInner,
+            Stream,
+        },
+        tests::util::*,
+    },
+    color_eyre::eyre::bail,
+    std::{
+        io::{self, prelude::*},
+        net::TcpStream,
+        path::Path,
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+fn port_file<'a>(name: &'a Name<'_>) -> TestResult<&'a Path> {
+    match &name.0 {
+        NameInner::Tcp(path) => Ok(Path::new(path)),
+        _ => bail!("expected a loopback TCP name, got {name:?}"),
+    }
+}
+
+fn listen(id: &str, path: bool) -> TestResult<(Name<'static>, Listener)> {
+    listen_and_pick_name(&mut namegen_tcp(id, path), |nm| {
+        ListenerOptions::new().name(nm.borrow()).create_sync()
+    })
+}
+
+pub fn roundtrip(id: &str, path: bool) -> TestResult {
+    let (name, listener) = listen(id, path)?;
+    ensure_eq!(matches!(listener, Listener::Tcp(..)), true);
+    ensure_eq!(listener.local_name().opname("listener local_name")?, name);
+    ensure_eq!(port_file(&name)?.exists(), true);
+
+    let mut client = Stream::connect(name.borrow()).opname("client connect")?;
+    let mut server = listener.accept().opname("accept")?;
+    ensure_eq!(matches!(server, Stream::Tcp(..)), true);
+
+    client.write_all(b"ping").opname("client send")?;
+    let mut buf = [0; 4];
+    server.read_exact(&mut buf).opname("server receive")?;
+    ensure_eq!(&buf, b"ping");
+    server.write_all(b"pong").opname("server send")?;
+    client.read_exact(&mut buf).opname("client receive")?;
+    ensure_eq!(&buf, b"pong");
+
+    ensure_eq!(client.peer_name().opname("client peer_name")?, None);
+    ensure_eq!(server.peer_creds().err().map(|e| e.kind()), Some(io::ErrorKind::Unsupported));
+
+    drop(listener);
+    ensure_eq!(port_file(&name)?.exists(), false);
+    match Stream::connect(name.borrow()) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        els => bail!("expected NotFound when connecting to dropped listener, got {els:?}"),
+    }
+}
+
+fn impostor(listener: &Listener) -> TestResult<TcpStream> {
+    let Listener::Tcp(tcp_listener) = listener else {
+        bail!("expected a loopback TCP listener, got {listener:?}")
+    };
+    let port = tcp_listener.port().opname("port")?;
+    TcpStream::connect(("127.0.0.1", port)).opname("impostor connect")
+}
+
+pub fn bad_token(id: &str) -> TestResult {
+    let (name, listener) = listen(id, true)?;
+    let mut impostor = impostor(&listener)?;
+    impostor.write_all(&[0; 16]).opname("impostor send")?;
+    let mut client = Stream::connect(name.borrow()).opname("client connect")?;
+    let mut server = listener.accept().opname("accept")?;
+
+    server.write_all(b"hi").opname("server send")?;
+    let mut buf = [0; 2];
+    client.read_exact(&mut buf).opname("client receive")?;
+    ensure_eq!(&buf, b"hi");
+
+    // The impostor's connection is closed without any data being sent to it
+    impostor.set_read_timeout(Some(Duration::from_secs(5))).opname("set_read_timeout")?;
+    match impostor.read(&mut buf) {
+        Ok(0) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::ConnectionReset => Ok(()),
+        els => bail!("expected impostor to be disconnected, got {els:?}"),
+    }
+}
+
+pub fn overwrite(id: &str) -> TestResult {
+    let (name, old) = listen(id, true)?;
+    match ListenerOptions::new().name(name.borrow()).create_sync() {
+        Err(e) if e.kind() == io::ErrorKind::AddrInUse => {}
+        els => bail!("expected AddrInUse when binding to a taken name, got {els:?}"),
+    }
+    let new = ListenerOptions::new()
+        .name(name.borrow())
+        .try_overwrite(true)
+        .create_sync()
+        .opname("overwriting bind")?;
+
+    // The old listener must not reclaim the port file of the new one
+    drop(old);
+    ensure_eq!(port_file(&name)?.exists(), true);
+    let _client = Stream::connect(name.borrow()).opname("client connect")?;
+    let _server = new.accept().opname("accept")?;
+    Ok(())
+}
+
+pub fn silent_client(id: &str) -> TestResult {
+    let (name, listener) = listen(id, true)?;
+    let _impostor = impostor(&listener)?;
+    let start = Instant::now();
+    let _client = Stream::connect(name.borrow()).opname("client connect")?;
+    let _server = listener.accept().opname("accept")?;
+    // The handshake timeout is one second
+    ensure_eq!(start.elapsed() < Duration::from_millis(500), true);
+    Ok(())
+}
+
+pub fn nonblocking(id: &str) -> TestResult {
+    let (name, listener) = listen(id, true)?;
+    listener.set_nonblocking(ListenerNonblockingMode::Accept).opname("set_nonblocking")?;
+    let mut impostor = impostor(&listener)?;
+    // The impostor's connection has been accepted, but it hasn't sent the token yet
+    for _ in 0..2 {
+        match listener.accept() {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+            els => bail!("expected WouldBlock with a client in the handshake, got {els:?}"),
+        }
+    }
+    impostor.write_all(&[0; 16]).opname("impostor send")?;
+
+    let mut client = Stream::connect(name.borrow()).opname("client connect")?;
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut server = loop {
+        match listener.accept() {
+            Ok(server) => break server,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock && Instant::now() < deadline => {
+                thread::sleep(Duration::from_millis(10))
+            }
+            Err(e) => return Err(e).opname("accept"),
+        }
+    };
+    server.write_all(b"hi").opname("server send")?;
+    let mut buf = [0; 2];
+    client.read_exact(&mut buf).opname("client receive")?;
+    ensure_eq!(&buf, b"hi");
+    Ok(())
+}
+
+/// Registers the listener with a selector after putting its file descriptor in nonblocking mode,
+/// like mio does, and checks that it becomes readable when a client completes the handshake late.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn selector(id: &str) -> TestResult {
+    use {
+        crate::os::unix::selector::{Events, Interest, Selector, Token, Trigger},
+        std::os::unix::prelude::*,
+    };
+    let (name, listener) = listen(id, true)?;
+    let contents = std::fs::read_to_string(port_file(&name)?).opname("read port file")?;
+    let Some(hex) = contents.split_whitespace().nth(1) else {
+        bail!("no token in port file {contents:?}")
+    };
+    let token = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<Vec<_>, _>>()
+        .opname("parse token")?;
+
+    let fd = listener.as_fd().as_raw_fd();
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    ensure_eq!(unsafe { libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) }, 0);
+    let selector = Selector::new().opname("selector")?;
+    selector.register(&listener, Token(0), Interest::READABLE, Trigger::Level)?;
+    let mut events = Events::with_capacity(4);
+    let mut select = |timeout| -> TestResult<usize> {
+        selector.select(&mut events, Some(timeout)).opname("select")?;
+        Ok(events.len())
+    };
+
+    let mut late = impostor(&listener)?;
+    ensure_eq!(select(Duration::from_secs(5))?, 1);
+    match listener.accept() {
+        Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+        els => bail!("expected WouldBlock with a client in the handshake, got {els:?}"),
+    }
+    ensure_eq!(select(Duration::from_millis(50))?, 0);
+
+    late.write_all(&token).opname("late client send")?;
+    ensure_eq!(select(Duration::from_secs(5))?, 1);
+    let _server = listener.accept().opname("accept")?;
+    ensure_eq!(select(Duration::from_millis(50))?, 0);
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! Tests verification of the identity of the server by the client.
+
+use {
+    crate::{
+        local_socket::{prelude::*, ConnectOptions, ListenerOptions},
+        tests::util::*,
+    },
+    std::{
+        io,
+        sync::{
+            atomic::{AtomicUsize, Ordering::Relaxed},
+            Arc,
+        },
+    },
+};
+
+pub fn main(id: &str, path: bool) -> TestResult {
+    let (name, listener) = listen_and_pick_name(&mut namegen_local_socket(id, path), |nm| {
+        ListenerOptions::new().name(nm.borrow()).create_sync()
+    })?;
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_in_check = Arc::clone(&calls);
+    #[allow(clippy::cast_sign_loss)]
+    let own_pid = move |creds: &crate::local_socket::PeerCreds| {
+        calls_in_check.fetch_add(1, Relaxed);
+        creds.pid().map_or(true, |pid| pid as u32 == std::process::id())
+    };
+
+    let _ = ConnectOptions::new()
+        .name(name.borrow())
+        .expect_server(own_pid)
+        .connect_sync()
+        .opname("connect with passing check")?;
+    let _ = listener.accept().opname("accept")?;
+    ensure_eq!(calls.load(Relaxed), 1);
+
+    let err = ConnectOptions::new().name(name.borrow()).expect_server(|_| false).connect_sync();
+    ensure_eq!(err.err().map(|e| e.kind()), Some(io::ErrorKind::PermissionDenied));
+    let _ = listener.accept().opname("accept")?;
+
+    #[cfg(unix)]
+    {
+        use crate::os::unix::local_socket::ConnectOptionsExt;
+        // SAFETY: always safe
+        let (euid, egid) = unsafe { (libc::geteuid(), libc::getegid()) };
+        let _ = ConnectOptions::new()
+            .name(name.borrow())
+            .expect_server_euid(euid)
+            .expect_server_egid(egid)
+            .connect_sync()
+            .opname("connect with own euid and egid")?;
+        let _ = listener.accept().opname("accept")?;
+
+        let err = ConnectOptions::new()
+            .name(name.borrow())
+            .expect_server_euid(euid.wrapping_add(1))
+            .connect_sync();
+        ensure_eq!(err.err().map(|e| e.kind()), Some(io::ErrorKind::PermissionDenied));
+    }
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use {
+    crate::{
+        local_socket::{
+            async_io::{prelude::*, Listener, Stream},
+            ListenerOptions,
+        },
+        tests::util::*,
+        unnamed_pipe::async_io::pipe,
+    },
+    ::async_io::{block_on, Async, Timer},
+    color_eyre::eyre::bail,
+    futures_lite::{
+        future::{or, zip},
+        io::{AsyncReadExt as _, AsyncWriteExt as _},
+    },
+    std::{
+        io,
+        net::TcpStream,
+        time::{Duration, Instant},
+    },
+};
+
+fn stream_inner(id: &'static str, path: bool) -> TestResult {
+    block_on(async {
+        let (name, listener) = listen_and_pick_name(&mut namegen_local_socket(id, path), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_async_io()
+        })?;
+
+        let (server, client) = zip(listener.accept(), Stream::connect(name.borrow())).await;
+        let mut server = server.opname("accept")?;
+        let client = client.opname("client connect")?;
+
+        let (mut crecv, mut csend) = client.split();
+        csend.write_all(b"ping").await.opname("client send")?;
+        let mut buf = [0; 4];
+        server.read_exact(&mut buf).await.opname("server receive")?;
+        ensure_eq!(&buf, b"ping");
+
+        server.write_all(b"pong").await.opname("server send")?;
+        crecv.read_exact(&mut buf).await.opname("client receive")?;
+        ensure_eq!(&buf, b"pong");
+
+        let mut client = Stream::reunite(crecv, csend).opname("reunite")?;
+        drop(server);
+        ensure_eq!(client.read(&mut buf).await.opname("client receive EOF")?, 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn stream_file() -> TestResult { test_wrapper(|| stream_inner(make_id!(), true)) }
+#[test]
+fn stream_namespaced() -> TestResult { test_wrapper(|| stream_inner(make_id!(), false)) }
+
+#[test]
+fn unnamed_pipe() -> TestResult {
+    test_wrapper(|| {
+        block_on(async {
+            let (mut tx, mut rx) = pipe().opname("pipe")?;
+            let msg = b"Hello from the other side!";
+            let (sent, received) = zip(
+                async move {
+                    tx.write_all(msg).await?;
+                    drop(tx);
+                    std::io::Result::Ok(())
+                },
+                async {
+                    let mut buf = Vec::new();
+                    rx.read_to_end(&mut buf).await.map(|_| buf)
+                },
+            )
+            .await;
+            sent.opname("send")?;
+            ensure_eq!(received.opname("receive")?, msg);
+            Ok(())
+        })
+    })
+}
+
+/// Accepting must neither be held up by a client that never sends the token nor lose the clients
+/// it has accepted when cancelled.
+#[test]
+fn tcp_silent_client() -> TestResult {
+    test_wrapper(|| {
+        block_on(async {
+            let (name, listener) =
+                listen_and_pick_name(&mut namegen_tcp(make_id!(), true), |nm| {
+                    ListenerOptions::new().name(nm.borrow()).create_async_io()
+                })?;
+            let Listener::Tcp(tcp_listener) = &listener else {
+                bail!("expected a loopback TCP listener, got {listener:?}")
+            };
+            let port = tcp_listener.port().opname("port")?;
+            let _impostor = Async::<TcpStream>::connect(([127, 0, 0, 1], port))
+                .await
+                .opname("impostor connect")?;
+            let cancelled = or(listener.accept(), async {
+                Timer::after(Duration::from_millis(50)).await;
+                Err(io::ErrorKind::TimedOut.into())
+            })
+            .await;
+            ensure_eq!(cancelled.err().map(|e| e.kind()), Some(io::ErrorKind::TimedOut));
+
+            let start = Instant::now();
+            let (server, client) = zip(listener.accept(), Stream::connect(name.borrow())).await;
+            let mut server = server.opname("accept")?;
+            let mut client = client.opname("client connect")?;
+            // The handshake timeout is one second
+            ensure_eq!(start.elapsed() < Duration::from_millis(500), true);
+            server.write_all(b"hi").await.opname("server send")?;
+            let mut buf = [0; 2];
+            client.read_exact(&mut buf).await.opname("client receive")?;
+            ensure_eq!(&buf, b"hi");
+            Ok(())
+        })
+    })
+}
@@ -0,0 +1,93 @@
+use {
+    crate::{os::unix::fifo_file::FifoOptions, tests::util::*, OrErrno},
+    std::{
+        ffi::CString,
+        io::{self, prelude::*},
+        mem::zeroed,
+        os::unix::prelude::*,
+        path::{Path, PathBuf},
+        thread,
+        time::Duration,
+    },
+};
+
+static MSG: &str = "Message from sender to receiver\n";
+const TIMEOUT: Duration = Duration::from_secs(10);
+const SHORT_TIMEOUT: Duration = Duration::from_millis(50);
+
+fn pick_path(id: &str) -> TestResult<PathBuf> {
+    namegen_fifo(id)
+        .find(|path| !matches!(path, Ok(path) if path.exists()))
+        .unwrap()
+        .opname("path generation")
+}
+
+fn get_file_mode(path: &Path) -> TestResult<libc::mode_t> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let mut stat = unsafe { zeroed::<libc::stat>() };
+    unsafe { libc::stat(path.as_ptr(), &mut stat) != -1 }.true_val_or_errno(()).opname("stat")?;
+    Ok(stat.st_mode & 0o777)
+}
+
+fn basic() -> TestResult {
+    let path = pick_path(make_id!())?;
+    let opts = FifoOptions::new().create(true).unlink_on_drop(true).open_timeout(TIMEOUT);
+
+    let (rx_opts, rx_path) = (opts.clone(), path.clone());
+    let jh = thread::spawn(move || {
+        let mut rx = rx_opts.open_recver(&rx_path).opname("receiver open")?;
+        let mut buf = String::with_capacity(MSG.len());
+        rx.read_to_string(&mut buf).opname("receive")?;
+        ensure_eq!(buf, MSG);
+        TestResult::Ok(())
+    });
+
+    let mut tx = opts.open_sender(&path).opname("sender open")?;
+    tx.write_all(MSG.as_bytes()).opname("send")?;
+    drop(tx);
+    jh.join().unwrap()?;
+    ensure_eq!(path.exists(), false);
+    Ok(())
+}
+
+fn mode_and_dummy_writer() -> TestResult {
+    const MODE: libc::mode_t = 0o666;
+    let path = pick_path(make_id!())?;
+    let mut rx = FifoOptions::new()
+        .create(true)
+        .mode(MODE)
+        .dummy_writer(true)
+        .unlink_on_drop(true)
+        .open_recver(&path)
+        .opname("receiver open")?;
+    ensure_eq!(get_file_mode(&path)?, MODE);
+
+    // Senders coming and going must not cause end of file.
+    for _ in 0..2 {
+        let mut tx = FifoOptions::new().open_sender(&path).opname("sender open")?;
+        tx.write_all(MSG.as_bytes()).opname("send")?;
+    }
+    let mut buf = vec![0; MSG.len() * 2];
+    rx.read_exact(&mut buf).opname("receive")?;
+    ensure_eq!(buf, MSG.repeat(2).as_bytes());
+
+    drop(rx);
+    ensure_eq!(path.exists(), false);
+    Ok(())
+}
+
+fn timeouts() -> TestResult {
+    let path = pick_path(make_id!())?;
+    let opts = FifoOptions::new().create(true).unlink_on_drop(true).open_timeout(SHORT_TIMEOUT);
+    let kind = |rslt: io::Result<()>| rslt.err().map(|e| e.kind());
+    ensure_eq!(kind(opts.open_sender(&path).map(drop)), Some(io::ErrorKind::TimedOut));
+    ensure_eq!(kind(opts.open_recver(&path).map(drop)), Some(io::ErrorKind::TimedOut));
+    Ok(())
+}
+
+#[test]
+fn fifo_basic() -> TestResult { test_wrapper(basic) }
+#[test]
+fn fifo_mode_and_dummy_writer() -> TestResult { test_wrapper(mode_and_dummy_writer) }
+#[test]
+fn fifo_timeouts() -> TestResult { test_wrapper(timeouts) }
@@ -0,0 +1,124 @@
+use {
+    crate::{
+        local_socket::{prelude::*, ConnectOptions, ListenerNonblockingMode, ListenerOptions},
+        os::unix::{
+            uds_local_socket as uds,
+            uring_local_socket::{is_available, Listener, Stream},
+        },
+        tests::util::*,
+        TryClone as _,
+    },
+    std::{
+        io::{self, prelude::*},
+        thread,
+    },
+};
+
+/// Larger than the buffers of the rings, so that partial sends and leftover data are exercised.
+const LEN: usize = 100_000;
+
+fn payload() -> Vec<u8> { (0..=250).cycle().take(LEN).collect() }
+
+fn stream_inner(id: &'static str, path: bool) -> TestResult {
+    let (name, listener) = listen_and_pick_name(&mut namegen_local_socket(id, path), |nm| {
+        ListenerOptions::new().name(nm.borrow()).create_sync_as::<Listener>()
+    })?;
+    ensure_eq!(listener.uses_io_uring(), is_available());
+
+    let client = thread::spawn(move || -> TestResult {
+        let client =
+            ConnectOptions::new().name(name).connect_sync_as::<Stream>().opname("connect")?;
+        ensure_eq!(client.uses_io_uring(), is_available());
+        let (mut crecv, mut csend) = client.split();
+        csend.write_all(&payload()).opname("client send")?;
+        let mut buf = vec![0; LEN];
+        crecv.read_exact(&mut buf).opname("client receive")?;
+        ensure_eq!(buf, payload());
+        let mut client = Stream::reunite(crecv, csend).opname("reunite")?;
+        ensure_eq!(client.read(&mut [0; 4]).opname("client receive EOF")?, 0);
+        Ok(())
+    });
+
+    let mut server = listener.accept().opname("accept")?;
+    let mut buf = vec![0; LEN];
+    // Small reads to drain the provided buffers piecemeal
+    for chunk in buf.chunks_mut(1000) {
+        server.read_exact(chunk).opname("server receive")?;
+    }
+    ensure_eq!(buf, payload());
+    server.write_all(&buf).opname("server send")?;
+    drop(server);
+    client.join().unwrap()
+}
+
+#[test]
+fn stream_file() -> TestResult { test_wrapper(|| stream_inner(make_id!(), true)) }
+#[test]
+fn stream_namespaced() -> TestResult { test_wrapper(|| stream_inner(make_id!(), false)) }
+
+#[test]
+fn interop_and_nonblocking() -> TestResult {
+    test_wrapper(|| {
+        let (name, listener) =
+            listen_and_pick_name(&mut namegen_local_socket(make_id!(), false), |nm| {
+                ListenerOptions::new().name(nm.borrow()).create_sync_as::<Listener>()
+            })?;
+        listener.set_nonblocking(ListenerNonblockingMode::Accept).opname("set_nonblocking")?;
+        let e = listener.accept().expect_err("accepted a nonexistent client");
+        ensure_eq!(e.kind(), io::ErrorKind::WouldBlock);
+        listener.set_nonblocking(ListenerNonblockingMode::Neither).opname("set_nonblocking")?;
+
+        let mut client = ConnectOptions::new()
+            .name(name)
+            .connect_sync_as::<uds::Stream>()
+            .opname("connect")?;
+        let server = listener.accept().opname("accept")?;
+        let mut clone = server.try_clone().opname("try_clone")?;
+        client.write_all(b"ping").opname("client send")?;
+        let mut buf = [0; 4];
+        clone.read_exact(&mut buf).opname("server receive")?;
+        ensure_eq!(&buf, b"ping");
+
+        // Timeouts take the plain system call path
+        server.set_recv_timeout(Some(std::time::Duration::from_millis(50))).opname("timeout")?;
+        let e = (&server).read(&mut buf).expect_err("received nonexistent data");
+        ensure_eq!(e.kind(), io::ErrorKind::WouldBlock);
+        Ok(())
+    })
+}
+
+/// Connects more clients than the multishot accept takes ahead of time, making sure that none of
+/// them get lost when it's cancelled and rearmed.
+#[test]
+fn accept_ahead() -> TestResult {
+    test_wrapper(|| {
+        let (name, listener) =
+            listen_and_pick_name(&mut namegen_local_socket(make_id!(), false), |nm| {
+                ListenerOptions::new().name(nm.borrow()).create_sync_as::<Listener>()
+            })?;
+        let connect = |i: u8| -> TestResult<uds::Stream> {
+            let mut client = ConnectOptions::new()
+                .name(name.borrow())
+                .connect_sync_as::<uds::Stream>()
+                .opname("connect")?;
+            client.write_all(&[i]).opname("client send")?;
+            Ok(client)
+        };
+        let accept = |i: u8| -> TestResult {
+            let mut server = listener.accept().opname("accept")?;
+            let mut buf = [0];
+            server.read_exact(&mut buf).opname("server receive")?;
+            ensure_eq!(buf, [i]);
+            Ok(())
+        };
+        let clients = (0..30).map(connect).collect::<TestResult<Vec<_>>>()?;
+        for i in 0..30 {
+            accept(i)?;
+        }
+        // The multishot accept has to be rearmed for this one
+        let _client = connect(30)?;
+        accept(30)?;
+        drop(clients);
+        Ok(())
+    })
+}
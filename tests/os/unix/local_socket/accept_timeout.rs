@@ -0,0 +1,90 @@
+use {
+    crate::{
+        local_socket::{
+            prelude::*, traits, Listener as EnumListener, ListenerNonblockingMode,
+            ListenerOptions, Name, Stream,
+        },
+        os::unix::{
+            local_socket::ListenerExt,
+            uds_local_socket::{Listener, ListenerShutdown},
+        },
+        tests::util::*,
+    },
+    std::{
+        io,
+        time::{Duration, Instant},
+    },
+};
+
+const TIMEOUT: Duration = Duration::from_millis(50);
+
+fn timed_out(rslt: io::Result<impl Sized>) -> bool {
+    rslt.err().map(|e| e.kind()) == Some(io::ErrorKind::TimedOut)
+}
+
+fn exercise<L: ListenerExt>(name: &Name<'_>, listener: &L, nonblocking: bool) -> TestResult {
+    if nonblocking {
+        listener.set_nonblocking(ListenerNonblockingMode::Accept).opname("set_nonblocking")?;
+    }
+
+    let start = Instant::now();
+    ensure_eq!(timed_out(listener.accept_timeout(TIMEOUT)), true);
+    ensure_eq!(start.elapsed() >= TIMEOUT, true);
+    ensure_eq!(timed_out(listener.accept_deadline(Instant::now())), true);
+    ensure_eq!(timed_out(listener.incoming_timeout(TIMEOUT).next().unwrap()), true);
+
+    let _client = Stream::connect(name.borrow()).opname("client connect")?;
+    let _ = listener.accept_timeout(Duration::from_secs(5)).opname("accept")?;
+    let _client = Stream::connect(name.borrow()).opname("client connect")?;
+    let _ = listener.incoming_timeout(Duration::from_secs(5)).next().unwrap().opname("accept")?;
+    Ok(())
+}
+
+fn listen_as<L: traits::Listener>(tcp: bool) -> TestResult<(Name<'static>, L)> {
+    let id = make_id!();
+    let create = |nm: &Name<'static>| ListenerOptions::new().name(nm.borrow()).create_sync_as();
+    if tcp {
+        listen_and_pick_name(&mut namegen_tcp(id, true), create)
+    } else {
+        listen_and_pick_name(&mut namegen_local_socket(id, true), create)
+    }
+}
+
+fn test_inner(nonblocking: bool) -> TestResult {
+    let (name, listener) = listen_as::<Listener>(false)?;
+    exercise(&name, &listener, nonblocking)?;
+
+    listener.shutdown_handle().opname("shutdown handle creation")?.shutdown()?;
+    let err = listener.accept_timeout(Duration::from_secs(5)).err();
+    ensure_eq!(err.as_ref().map(ListenerShutdown::matches), Some(true));
+    Ok(())
+}
+
+fn enum_inner(nonblocking: bool, tcp: bool) -> TestResult {
+    let (name, listener) = listen_as::<EnumListener>(tcp)?;
+    ensure_eq!(matches!(listener, EnumListener::Tcp(..)), tcp);
+    exercise(&name, &listener, nonblocking)
+}
+
+#[test]
+fn main() -> TestResult { test_wrapper(|| test_inner(false)) }
+#[test]
+fn nonblocking() -> TestResult { test_wrapper(|| test_inner(true)) }
+#[test]
+fn enum_uds() -> TestResult { test_wrapper(|| enum_inner(false, false)) }
+#[test]
+fn enum_tcp() -> TestResult { test_wrapper(|| enum_inner(false, true)) }
+#[test]
+fn enum_tcp_nonblocking() -> TestResult { test_wrapper(|| enum_inner(true, true)) }
+
+#[cfg(all(target_os = "linux", feature = "io_uring"))]
+#[test]
+fn uring() -> TestResult {
+    use crate::os::unix::uring_local_socket::Listener as UringListener;
+    test_wrapper(|| {
+        let (name, listener) = listen_as::<UringListener>(false)?;
+        exercise(&name, &listener, false)?;
+        let (name, listener) = listen_as::<UringListener>(false)?;
+        exercise(&name, &listener, true)
+    })
+}
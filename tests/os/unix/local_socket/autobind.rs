@@ -0,0 +1,56 @@
+use {
+    crate::{
+        local_socket::{prelude::*, ListenerOptions, Name, Stream},
+        os::unix::{
+            local_socket::{AbstractNsUdSocket, ListenerOptionsExt},
+            uds_local_socket::Listener,
+        },
+        tests::util::*,
+    },
+    std::{
+        ffi::OsStr,
+        io::{prelude::*, BufReader},
+        os::unix::ffi::OsStrExt,
+    },
+};
+
+fn ping(listener: &Listener, name: Name<'_>) -> TestResult {
+    let mut client = Stream::connect(name).opname("client connect")?;
+    let server = listener.accept().opname("accept")?;
+    client.write_all(b"ping\n").opname("client send")?;
+    let mut buf = String::new();
+    BufReader::new(server).read_line(&mut buf).opname("server receive")?;
+    ensure_eq!(buf, "ping\n");
+    Ok(())
+}
+
+fn autobind() -> TestResult {
+    let listener = ListenerOptions::new()
+        .autobind(true)
+        .create_sync_as::<Listener>()
+        .opname("autobind listener creation")?;
+    let name = listener.local_name().opname("local_name")?;
+    ensure_eq!(name.is_namespaced(), true);
+    ping(&listener, name)
+}
+
+fn binary_name() -> TestResult {
+    let (name, listener) = listen_and_pick_name(
+        &mut NameGen::new(make_id!(), |rn| {
+            let mut bytes = b"\0interprocess\0test\0".to_vec();
+            bytes.extend_from_slice(&rn.to_le_bytes());
+            Ok(OsStr::from_bytes(&bytes).to_ns_name::<AbstractNsUdSocket>()?.into_owned())
+        }),
+        |name| ListenerOptions::new().name(name.borrow()).create_sync_as::<Listener>(),
+    )?;
+    ensure_eq!(listener.local_name().opname("local_name")?, name);
+    // The same name with the trailing bytes cut off at the first nul must not resolve to it.
+    let truncated = OsStr::from_bytes(b"\0interprocess").to_ns_name::<AbstractNsUdSocket>()?;
+    ensure_eq!(Stream::connect(truncated).is_err(), true);
+    ping(&listener, name)
+}
+
+#[test]
+fn main() -> TestResult { test_wrapper(autobind) }
+#[test]
+fn binary() -> TestResult { test_wrapper(binary_name) }
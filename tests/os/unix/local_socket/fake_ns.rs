@@ -6,7 +6,7 @@ use {
         os::unix::local_socket::SpecialDirUdSocket,
         tests::util::*,
     },
-    std::sync::Arc,
+    std::{io, sync::Arc},
 };
 
 fn test_inner(iter: u32) -> TestResult {
@@ -14,6 +14,7 @@ fn test_inner(iter: u32) -> TestResult {
         format!("interprocess test {:08x}/fake ns/test.sock", rnum)
             .to_ns_name::<SpecialDirUdSocket>()
             .map(Arc::new)
+            .map_err(io::Error::from)
     });
     let (name, _listener) = listen_and_pick_name(&mut namegen, |nm| {
         ListenerOptions::new().name(nm.borrow()).create_sync()
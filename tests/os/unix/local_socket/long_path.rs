@@ -0,0 +1,49 @@
+use {
+    crate::{
+        local_socket::{prelude::*, GenericFilePath, ListenerOptions, Stream},
+        tests::util::*,
+    },
+    std::{fs, io, path::PathBuf},
+};
+
+fn test_inner() -> TestResult {
+    let tmpdir = std::env::var_os("TMPDIR").map_or_else(|| PathBuf::from("/tmp"), PathBuf::from);
+    let mut namegen = NameGen::new(make_id!(), |rn| {
+        let mut dir = tmpdir.join(format!("interprocess-test-{rn:08x}"));
+        let base = dir.clone();
+        // Well past the 108 bytes of sun_path on Linux
+        for _ in 0..8 {
+            dir.push("a-rather-long-directory-name");
+        }
+        fs::create_dir_all(&dir)?;
+        Ok((base, dir.join("test.sock")))
+    });
+    let ((base, path), listener) = listen_and_pick_name(&mut namegen, |(_, path)| {
+        let name = path.as_path().to_fs_name::<GenericFilePath>()?;
+        ListenerOptions::new().name(name).create_sync().map(Some).or_else(|e| {
+            if cfg!(not(any(target_os = "linux", target_os = "android")))
+                && e.kind() == io::ErrorKind::InvalidInput
+            {
+                Ok(None)
+            } else {
+                Err(e)
+            }
+        })
+    })?;
+    let rslt = (|| {
+        // If listener is None, we're on a platform on which we expect this to not be supported
+        let Some(listener) = listener else { return Ok(()) };
+        ensure_eq!(path.exists(), true);
+        let name = path.as_path().to_fs_name::<GenericFilePath>()?;
+        ensure_eq!(listener.local_name().opname("local_name")?, name);
+        let _ = Stream::connect(name).opname("client connect")?;
+        drop(listener);
+        ensure_eq!(path.exists(), false);
+        Ok(())
+    })();
+    fs::remove_dir_all(base).opname("cleanup")?;
+    rslt
+}
+
+#[test]
+fn main() -> TestResult { test_wrapper(test_inner) }
@@ -1,6 +1,6 @@
 use {
     crate::{
-        local_socket::{traits::Stream as _, Listener, ListenerOptions, Name, NameInner, Stream},
+        local_socket::{traits::Stream as _, ListenerOptions, Name, NameInner, Stream},
         os::unix::local_socket::ListenerOptionsExt,
         tests::util::*,
         OrErrno,
@@ -57,10 +57,7 @@ fn test_inner(path: bool) -> TestResult {
     let actual_mode = if let Name(NameInner::UdSocketPath(path)) = name {
         get_file_mode(&path)
     } else {
-        let fd = match &listener {
-            Listener::UdSocket(l) => l.as_fd(),
-        };
-        get_fd_mode(fd)
+        get_fd_mode(listener.as_fd())
     }
     .opname("get mode")?;
     if actual_mode != 0 {
@@ -0,0 +1,76 @@
+use {
+    crate::{
+        local_socket::{prelude::*, ListenerOptions, NameInner, Stream},
+        os::unix::local_socket::{ListenerOptionsExt, NameExt},
+        tests::util::*,
+    },
+    color_eyre::eyre::bail,
+    std::{fs, os::unix::fs::MetadataExt as _, path::Path},
+};
+
+fn test_inner() -> TestResult {
+    const MODE: u32 = 0o660;
+    let (uid, gid) = unsafe { (libc::geteuid(), libc::getegid()) };
+    let (name, _listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), true), |nm| {
+            ListenerOptions::new()
+                .name(nm.borrow())
+                .mode(MODE as _)
+                .owner(uid)
+                .group(gid)
+                .create_sync()
+        })?;
+    let _ = Stream::connect(name.borrow()).opname("client connect")?;
+
+    let meta = fs::metadata(name.fs_path().unwrap()).opname("stat")?;
+    ensure_eq!(meta.uid(), uid);
+    ensure_eq!(meta.gid(), gid);
+    if meta.mode() & 0o777 != 0 {
+        // See the mode test
+        ensure_eq!(meta.mode() & 0o777, MODE);
+    }
+    Ok(())
+}
+
+/// Applied to the port file.
+fn tcp_inner() -> TestResult {
+    const MODE: u32 = 0o640;
+    let (uid, gid) = unsafe { (libc::geteuid(), libc::getegid()) };
+    let (name, _listener) = listen_and_pick_name(&mut namegen_tcp(make_id!(), true), |nm| {
+        ListenerOptions::new()
+            .name(nm.borrow())
+            .mode(MODE as _)
+            .owner(uid)
+            .group(gid)
+            .create_sync()
+    })?;
+    let _ = Stream::connect(name.borrow()).opname("client connect")?;
+
+    let NameInner::Tcp(path) = &name.0 else {
+        bail!("expected a loopback TCP name, got {name:?}")
+    };
+    let meta = fs::metadata(Path::new(path)).opname("stat")?;
+    ensure_eq!(meta.uid(), uid);
+    ensure_eq!(meta.gid(), gid);
+    ensure_eq!(meta.mode() & 0o777, MODE);
+    Ok(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn namespaced_inner() -> TestResult {
+    use {crate::local_socket::GenericNamespaced, std::io};
+    let gid = unsafe { libc::getegid() };
+    let name = format!("{}-owner", make_id!()).to_ns_name::<GenericNamespaced>()?;
+    let err = ListenerOptions::new().name(name).group(gid).create_sync().err();
+    ensure_eq!(err.map(|e| e.kind()), Some(io::ErrorKind::Unsupported));
+    Ok(())
+}
+
+#[test]
+fn main() -> TestResult { test_wrapper(test_inner) }
+#[test]
+fn tcp() -> TestResult { test_wrapper(tcp_inner) }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn namespaced() -> TestResult { test_wrapper(namespaced_inner) }
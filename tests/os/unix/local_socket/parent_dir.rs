@@ -0,0 +1,83 @@
+#![allow(deprecated)]
+
+use {
+    crate::{
+        local_socket::{prelude::*, GenericFilePath, ListenerOptions, LoopbackTcp, Name, Stream},
+        os::unix::local_socket::{ListenerOptionsExt, NameExt, SpecialDirUdSocket},
+        tests::util::*,
+    },
+    std::{
+        fs, io,
+        os::unix::fs::PermissionsExt as _,
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
+};
+
+fn dir_mode(path: &Path) -> TestResult<u32> {
+    Ok(fs::metadata(path).opname("stat")?.permissions().mode() & 0o777)
+}
+
+fn make_dir(suffix: &str) -> TestResult<PathBuf> {
+    let dir = std::env::temp_dir()
+        .join(format!("interprocess-test-parent-dir-{}{suffix}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir(&dir).opname("directory creation")?;
+    Ok(dir)
+}
+
+fn to_name(path: &Path, tcp: bool) -> io::Result<Name<'_>> {
+    if tcp {
+        Ok(path.to_fs_name::<LoopbackTcp>()?)
+    } else {
+        Ok(path.to_fs_name::<GenericFilePath>()?)
+    }
+}
+
+/// With `tcp`, the directory that is verified is that of the port file.
+fn verify_inner(tcp: bool) -> TestResult {
+    let dir = make_dir(if tcp { "-tcp" } else { "" })?;
+    let name = dir.join(if tcp { "test.port" } else { "test.sock" });
+    let create = || {
+        ListenerOptions::new().name(to_name(&name, tcp)?).verify_parent_dir(true).create_sync()
+    };
+
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o777)).opname("chmod")?;
+    let err = create().err();
+    ensure_eq!(err.map(|e| e.kind()), Some(io::ErrorKind::PermissionDenied));
+    ensure_eq!(name.exists(), false);
+
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o700)).opname("chmod")?;
+    let _listener = create().opname("listener creation")?;
+    let _ = Stream::connect(to_name(&name, tcp)?).opname("client connect")?;
+
+    fs::remove_dir_all(&dir).opname("cleanup")?;
+    Ok(())
+}
+
+fn dir_mode_inner() -> TestResult {
+    const MODE: u32 = 0o750;
+    let mut namegen = NameGen::new(make_id!(), |rnum| {
+        format!("interprocess test {rnum:08x}/dir mode/test.sock")
+            .to_ns_name::<SpecialDirUdSocket>()
+            .map(Arc::new)
+            .map_err(io::Error::from)
+    });
+    let (name, listener) = listen_and_pick_name(&mut namegen, |nm| {
+        ListenerOptions::new().name(nm.borrow()).dir_mode(MODE as _).create_sync()
+    })?;
+    let _ = Stream::connect(name.borrow()).opname("client connect")?;
+
+    let local_name = listener.local_name().opname("local_name")?;
+    let parent = local_name.fs_path().and_then(Path::parent).unwrap();
+    ensure_eq!(dir_mode(parent)?, MODE);
+    ensure_eq!(dir_mode(parent.parent().unwrap())?, MODE);
+    Ok(())
+}
+
+#[test]
+fn verify() -> TestResult { test_wrapper(|| verify_inner(false)) }
+#[test]
+fn verify_tcp() -> TestResult { test_wrapper(|| verify_inner(true)) }
+#[test]
+fn main() -> TestResult { test_wrapper(dir_mode_inner) }
@@ -0,0 +1,32 @@
+use {
+    crate::{
+        local_socket::{prelude::*, ListenerOptions, Stream},
+        os::unix::local_socket::{NameExt, RuntimeDirUdSocket},
+        tests::util::*,
+    },
+    std::{io, sync::Arc},
+};
+
+fn test_inner() -> TestResult {
+    let runtime_dir = RuntimeDirUdSocket::runtime_dir().opname("runtime directory resolution")?;
+    let mut namegen = NameGen::new(make_id!(), |rnum| {
+        format!("interprocess-test-{rnum:08x}.sock")
+            .to_ns_name::<RuntimeDirUdSocket>()
+            .map(Arc::new)
+            .map_err(io::Error::from)
+    });
+    let (name, _listener) = listen_and_pick_name(&mut namegen, |nm| {
+        ListenerOptions::new().name(nm.borrow()).create_sync()
+    })?;
+    ensure_eq!(name.fs_path().and_then(|path| path.parent()), Some(runtime_dir.path()));
+    let _ = Stream::connect(name.borrow()).opname("client connect")?;
+
+    for bad in ["", ".", "..", "sub/dir.sock", "nul\0.sock"] {
+        let err = bad.to_ns_name::<RuntimeDirUdSocket>().err();
+        ensure_eq!(err.map(|e| e.kind()), Some(io::ErrorKind::InvalidInput));
+    }
+    Ok(())
+}
+
+#[test]
+fn main() -> TestResult { test_wrapper(test_inner) }
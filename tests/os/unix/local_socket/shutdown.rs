@@ -0,0 +1,45 @@
+use {
+    crate::{
+        local_socket::{prelude::*, ListenerNonblockingMode, ListenerOptions, Stream},
+        os::unix::uds_local_socket::{Listener, ListenerShutdown},
+        tests::util::*,
+    },
+    std::{io, thread, time::Duration},
+};
+
+fn test_inner() -> TestResult {
+    let (name, listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), true), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_sync_as::<Listener>()
+        })?;
+    let handle = listener.shutdown_handle().opname("shutdown handle creation")?;
+
+    // Accepting still works with a handle around
+    let _client = Stream::connect(name.borrow()).opname("client connect")?;
+    let _ = listener.accept().opname("accept")?;
+
+    thread::scope(|scope| {
+        let acceptor = scope.spawn(|| listener.accept());
+        thread::sleep(Duration::from_millis(100));
+        ensure_eq!(handle.is_shutdown(), false);
+        handle.shutdown().opname("shutdown")?;
+        handle.shutdown().opname("second shutdown")?;
+        let err = acceptor.join().unwrap().err();
+        ensure_eq!(err.as_ref().map(ListenerShutdown::matches), Some(true));
+        TestResult::Ok(())
+    })?;
+
+    // Pending connections are not accepted after the shutdown, nonblocking or not
+    let _client = Stream::connect(name.borrow()).opname("client connect")?;
+    ensure_eq!(listener.accept().as_ref().err().map(ListenerShutdown::matches), Some(true));
+    listener.set_nonblocking(ListenerNonblockingMode::Both).opname("set_nonblocking")?;
+    ensure_eq!(listener.accept().as_ref().err().map(ListenerShutdown::matches), Some(true));
+    ensure_eq!(handle.is_shutdown(), true);
+
+    let other = io::Error::from(io::ErrorKind::Other);
+    ensure_eq!(ListenerShutdown::matches(&other), false);
+    Ok(())
+}
+
+#[test]
+fn main() -> TestResult { test_wrapper(test_inner) }
@@ -0,0 +1,56 @@
+use {
+    crate::{
+        local_socket::{prelude::*, ListenerOptions, Stream},
+        os::unix::unnamed_pipe::pipe,
+        tests::util::*,
+    },
+    ::mio::{Events, Interest, Poll, Token},
+    std::{
+        io::{self, prelude::*},
+        time::Duration,
+    },
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn wait_for(poll: &mut Poll, events: &mut Events, token: Token) -> TestResult {
+    poll.poll(events, Some(TIMEOUT)).opname("poll")?;
+    ensure_eq!(events.iter().any(|e| e.token() == token), true);
+    Ok(())
+}
+
+fn test_inner() -> TestResult {
+    let (name, mut listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), false), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_sync()
+        })?;
+    let mut poll = Poll::new().opname("Poll creation")?;
+    let mut events = Events::with_capacity(8);
+
+    poll.registry().register(&mut listener, Token(0), Interest::READABLE)?;
+    // Registration makes accepting nonblocking
+    ensure_eq!(listener.accept().err().map(|e| e.kind()), Some(io::ErrorKind::WouldBlock));
+
+    let mut client = Stream::connect(name.borrow()).opname("client connect")?;
+    wait_for(&mut poll, &mut events, Token(0))?;
+    let mut server = listener.accept().opname("accept")?;
+    poll.registry().register(&mut server, Token(1), Interest::READABLE)?;
+    let mut buf = [0; 1];
+    ensure_eq!(server.read(&mut buf).err().map(|e| e.kind()), Some(io::ErrorKind::WouldBlock));
+
+    client.write_all(b"x").opname("client send")?;
+    wait_for(&mut poll, &mut events, Token(1))?;
+    ensure_eq!(server.read(&mut buf).opname("server receive")?, 1);
+    poll.registry().deregister(&mut server)?;
+
+    let (mut tx, mut rx) = pipe(false).opname("pipe")?;
+    poll.registry().register(&mut rx, Token(2), Interest::READABLE)?;
+    tx.write_all(b"x").opname("pipe send")?;
+    wait_for(&mut poll, &mut events, Token(2))?;
+    ensure_eq!(rx.read(&mut buf).opname("pipe receive")?, 1);
+    ensure_eq!(rx.read(&mut buf).err().map(|e| e.kind()), Some(io::ErrorKind::WouldBlock));
+    Ok(())
+}
+
+#[test]
+fn main() -> TestResult { test_wrapper(test_inner) }
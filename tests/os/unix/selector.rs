@@ -0,0 +1,176 @@
+use {
+    crate::{
+        local_socket::{prelude::*, ListenerNonblockingMode, ListenerOptions, Stream},
+        os::unix::selector::{Events, Interest, Selector, Token, Trigger},
+        tests::util::*,
+        unnamed_pipe::pipe,
+    },
+    std::{io::prelude::*, time::Duration},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+const SHORT_TIMEOUT: Duration = Duration::from_millis(50);
+
+fn ready_tokens(
+    selector: &Selector,
+    events: &mut Events,
+    timeout: Duration,
+) -> TestResult<Vec<Token>> {
+    selector.select(events, Some(timeout)).opname("select")?;
+    let mut tokens =
+        events.iter().filter(|e| e.is_readable()).map(|e| e.token()).collect::<Vec<_>>();
+    tokens.sort();
+    Ok(tokens)
+}
+
+fn test_inner(selector: Selector, id: &'static str) -> TestResult {
+    let (name, listener) = listen_and_pick_name(&mut namegen_local_socket(id, false), |nm| {
+        ListenerOptions::new()
+            .name(nm.borrow())
+            .nonblocking(ListenerNonblockingMode::Accept)
+            .create_sync()
+    })?;
+    let (mut tx, rx) = pipe().opname("pipe")?;
+    let mut events = Events::with_capacity(8);
+
+    selector.register(&listener, Token(0), Interest::READABLE, Trigger::Level)?;
+    selector.register(&rx, Token(1), Interest::READABLE, Trigger::Level)?;
+    ensure_eq!(
+        selector.register(&rx, Token(1), Interest::READABLE, Trigger::Level).is_err(),
+        true
+    );
+    ensure_eq!(ready_tokens(&selector, &mut events, SHORT_TIMEOUT)?, []);
+
+    let mut client = Stream::connect(name.borrow()).opname("client connect")?;
+    ensure_eq!(ready_tokens(&selector, &mut events, TIMEOUT)?, [Token(0)]);
+    let server = listener.accept().opname("accept")?;
+    selector.register(&server, Token(2), Interest::READABLE, Trigger::Level)?;
+    ensure_eq!(ready_tokens(&selector, &mut events, SHORT_TIMEOUT)?, []);
+
+    client.write_all(b"x").opname("client send")?;
+    tx.write_all(b"x").opname("pipe send")?;
+    ensure_eq!(ready_tokens(&selector, &mut events, TIMEOUT)?, [Token(1), Token(2)]);
+    // Level-triggered readiness persists until the data is read
+    ensure_eq!(ready_tokens(&selector, &mut events, TIMEOUT)?, [Token(1), Token(2)]);
+
+    selector.reregister(&rx, Token(3), Interest::READABLE, Trigger::Level)?;
+    selector.deregister(&server)?;
+    ensure_eq!(ready_tokens(&selector, &mut events, TIMEOUT)?, [Token(3)]);
+    ensure_eq!(selector.deregister(&server).is_err(), true);
+
+    selector.register(&server, Token(4), Interest::WRITABLE, Trigger::Level)?;
+    selector.select(&mut events, Some(TIMEOUT)).opname("select")?;
+    ensure_eq!(events.iter().any(|e| e.token() == Token(4) && e.is_writable()), true);
+    selector.deregister(&server)?;
+
+    drop(client);
+    selector.register(&server, Token(5), Interest::READABLE, Trigger::Level)?;
+    selector.select(&mut events, Some(TIMEOUT)).opname("select")?;
+    ensure_eq!(events.iter().any(|e| e.token() == Token(5) && e.is_hangup()), true);
+    Ok(())
+}
+
+#[test]
+fn poll() -> TestResult { test_wrapper(|| test_inner(Selector::new_poll(), make_id!())) }
+#[test]
+fn poll_edge_unsupported() -> TestResult {
+    test_wrapper(|| {
+        let (_tx, rx) = pipe().opname("pipe")?;
+        let err = Selector::new_poll().register(&rx, Token(0), Interest::READABLE, Trigger::Edge);
+        ensure_eq!(err.err().map(|e| e.kind()), Some(std::io::ErrorKind::Unsupported));
+        Ok(())
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn epoll() -> TestResult { test_wrapper(|| test_inner(Selector::new()?, make_id!())) }
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn epoll_edge() -> TestResult {
+    test_wrapper(|| {
+        let selector = Selector::new()?;
+        let (mut tx, rx) = pipe().opname("pipe")?;
+        let mut events = Events::with_capacity(8);
+        selector.register(&rx, Token(7), Interest::READABLE, Trigger::Edge)?;
+        tx.write_all(b"x").opname("pipe send")?;
+        ensure_eq!(ready_tokens(&selector, &mut events, TIMEOUT)?, [Token(7)]);
+        // Not reported again until more data arrives
+        ensure_eq!(ready_tokens(&selector, &mut events, SHORT_TIMEOUT)?, []);
+        tx.write_all(b"x").opname("pipe send")?;
+        ensure_eq!(ready_tokens(&selector, &mut events, TIMEOUT)?, [Token(7)]);
+        Ok(())
+    })
+}
+
+fn clone_events_inner(selector: Selector) -> TestResult {
+    let mut pipes = (0..4).map(|_| pipe().opname("pipe")).collect::<Result<Vec<_>, _>>()?;
+    let mut events = Events::with_capacity(8);
+    for (i, (_, rx)) in pipes.iter().enumerate() {
+        selector.register(rx, Token(i), Interest::READABLE, Trigger::Level)?;
+    }
+    // The clones hold fewer events than they have room for
+    let mut empty = events.clone();
+    let (tx, _) = pipes.first_mut().unwrap();
+    tx.write_all(b"x").opname("pipe send")?;
+    ensure_eq!(ready_tokens(&selector, &mut events, TIMEOUT)?, [Token(0)]);
+    let mut clone = events.clone();
+    for (tx, _) in &mut pipes {
+        tx.write_all(b"x").opname("pipe send")?;
+    }
+    let all = (0..4).map(Token).collect::<Vec<_>>();
+    ensure_eq!(ready_tokens(&selector, &mut clone, TIMEOUT)?, all);
+    ensure_eq!(ready_tokens(&selector, &mut empty, TIMEOUT)?, all);
+    Ok(())
+}
+#[test]
+fn poll_clone_events() -> TestResult { test_wrapper(|| clone_events_inner(Selector::new_poll())) }
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn epoll_clone_events() -> TestResult { test_wrapper(|| clone_events_inner(Selector::new()?)) }
+
+#[test]
+fn poll_no_starvation() -> TestResult {
+    test_wrapper(|| {
+        let selector = Selector::new_poll();
+        let mut pipes = (0..3).map(|_| pipe().opname("pipe")).collect::<Result<Vec<_>, _>>()?;
+        for (i, (tx, rx)) in pipes.iter_mut().enumerate() {
+            selector.register(rx, Token(i), Interest::READABLE, Trigger::Level)?;
+            tx.write_all(b"x").opname("pipe send")?;
+        }
+        let mut events = Events::with_capacity(2);
+        let mut seen = Vec::new();
+        for _ in 0..2 {
+            seen.extend(ready_tokens(&selector, &mut events, TIMEOUT)?);
+        }
+        seen.sort();
+        seen.dedup();
+        ensure_eq!(seen, (0..3).map(Token).collect::<Vec<_>>());
+        Ok(())
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn epoll_writable_half_closed() -> TestResult {
+    use std::{io::ErrorKind, net::Shutdown, os::unix::net::UnixStream};
+    test_wrapper(|| {
+        let selector = Selector::new()?;
+        let (mut stream, peer) = UnixStream::pair().opname("socketpair")?;
+        stream.set_nonblocking(true).opname("set_nonblocking")?;
+        let buf = [0; 4096];
+        loop {
+            match stream.write(&buf) {
+                Ok(..) => {}
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e).opname("send"),
+            }
+        }
+        peer.shutdown(Shutdown::Write).opname("shutdown")?;
+        let mut events = Events::with_capacity(8);
+        selector.register(&stream, Token(0), Interest::WRITABLE, Trigger::Level)?;
+        selector.select(&mut events, Some(SHORT_TIMEOUT)).opname("select")?;
+        ensure_eq!(events.len(), 0);
+        Ok(())
+    })
+}
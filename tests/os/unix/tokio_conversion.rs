@@ -0,0 +1,75 @@
+use {
+    crate::{
+        local_socket::{self, prelude::*, tokio::Stream, ListenerOptions},
+        tests::util::{tokio::test_wrapper, *},
+        unnamed_pipe, TryClone,
+    },
+    ::tokio::io::{AsyncReadExt, AsyncWriteExt},
+    std::io::{self, Read, Write},
+};
+
+async fn stream_inner() -> TestResult {
+    let (name, listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), true), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_sync()
+        })?;
+    let client = local_socket::Stream::connect(name.borrow()).opname("client connect")?;
+    let mut server = listener.accept().opname("accept")?;
+
+    let mut client = Stream::try_from(client).map_err(io::Error::from).opname("sync to Tokio")?;
+    let mut clone = client.try_clone().opname("clone")?;
+    clone.write_all(b"ping").await.opname("send via clone")?;
+    let mut buf = [0; 4];
+    server.read_exact(&mut buf).opname("server receive")?;
+    ensure_eq!(&buf, b"ping");
+    drop(clone);
+
+    server.write_all(b"pong").opname("server send")?;
+    client.read_exact(&mut buf).await.opname("client receive")?;
+    ensure_eq!(&buf, b"pong");
+
+    let mut client = local_socket::Stream::try_from(client)
+        .map_err(io::Error::from)
+        .opname("Tokio to sync")?;
+    server.write_all(b"sync").opname("server send")?;
+    // Blocking mode is restored, so this waits for the data instead of failing
+    client.read_exact(&mut buf).opname("sync client receive")?;
+    ensure_eq!(&buf, b"sync");
+    Ok(())
+}
+
+async fn pipe_inner() -> TestResult {
+    let (tx, rx) = unnamed_pipe::pipe().opname("pipe")?;
+    let tx = unnamed_pipe::tokio::Sender::try_from(tx)
+        .map_err(io::Error::from)
+        .opname("sender to Tokio")?;
+    let mut rx = unnamed_pipe::tokio::Recver::try_from(rx)
+        .map_err(io::Error::from)
+        .opname("receiver to Tokio")?;
+
+    let mut tx2 = tx.try_clone().opname("sender clone")?;
+    let rx2 = rx.try_clone().opname("receiver clone")?;
+    drop(tx);
+    tx2.write_all(b"ping").await.opname("send via clone")?;
+    let mut buf = [0; 4];
+    rx.read_exact(&mut buf).await.opname("receive")?;
+    ensure_eq!(&buf, b"ping");
+    drop(rx);
+
+    let mut tx =
+        unnamed_pipe::Sender::try_from(tx2).map_err(io::Error::from).opname("sender to sync")?;
+    let mut rx = unnamed_pipe::Recver::try_from(rx2)
+        .map_err(io::Error::from)
+        .opname("receiver to sync")?;
+    tx.write_all(b"pong").opname("sync send")?;
+    drop(tx);
+    let mut msg = Vec::new();
+    rx.read_to_end(&mut msg).opname("sync receive")?;
+    ensure_eq!(msg, b"pong");
+    Ok(())
+}
+
+#[test]
+fn stream() -> TestResult { test_wrapper(stream_inner()) }
+#[test]
+fn pipe() -> TestResult { test_wrapper(pipe_inner()) }
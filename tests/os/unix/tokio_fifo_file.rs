@@ -0,0 +1,78 @@
+use {
+    crate::{
+        os::unix::fifo_file::{
+            create_fifo,
+            tokio::{Recver, Sender},
+        },
+        tests::util::{namegen_fifo, tokio::test_wrapper, TestResult, WrapErrExt},
+    },
+    std::{future::Future, io, path::PathBuf},
+    tokio::io::{AsyncReadExt, AsyncWriteExt},
+};
+
+static MSG: &str = "Message from sender to receiver\n";
+
+fn make_fifo(id: &str) -> TestResult<PathBuf> {
+    let path = namegen_fifo(id)
+        .map(|path| path.and_then(|path| create_fifo(&path, 0o600).map(|()| path)))
+        .find(|rslt| !matches!(rslt, Err(e) if e.kind() == io::ErrorKind::AlreadyExists))
+        .unwrap()
+        .opname("FIFO creation")?;
+    Ok(path)
+}
+
+async fn send(path: PathBuf) -> TestResult {
+    let mut tx = Sender::open(&path).await.opname("sender open")?;
+    tx.write_all(MSG.as_bytes()).await.opname("send")?;
+    Ok(())
+}
+
+async fn basic(path: PathBuf) -> TestResult {
+    let mut rx = Recver::open(&path).opname("receiver open")?;
+    send(path).await?;
+    let mut buf = String::with_capacity(MSG.len());
+    rx.read_to_string(&mut buf).await.opname("receive")?;
+    ensure_eq!(buf, MSG);
+    Ok(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+async fn recv_before_send(path: PathBuf) -> TestResult {
+    let mut rx = Recver::open(&path).opname("receiver open")?;
+    let jh = tokio::task::spawn(async move {
+        let mut buf = String::with_capacity(MSG.len());
+        rx.read_to_string(&mut buf).await.opname("receive")?;
+        ensure_eq!(buf, MSG);
+        TestResult::Ok(())
+    });
+    tokio::task::yield_now().await;
+    send(path).await?;
+    jh.await??;
+    Ok(())
+}
+
+async fn no_reader(path: PathBuf) -> TestResult {
+    let err = Sender::try_open(&path).map(drop).err();
+    ensure_eq!(err.map(|e| e.kind()), Some(io::ErrorKind::ConnectionRefused));
+    Ok(())
+}
+
+async fn with_fifo<F: Future<Output = TestResult>>(
+    id: &'static str,
+    f: impl FnOnce(PathBuf) -> F,
+) -> TestResult {
+    let path = make_fifo(id)?;
+    let rslt = f(path.clone()).await;
+    std::fs::remove_file(&path).opname("FIFO deletion")?;
+    rslt
+}
+
+#[test]
+fn tokio_basic() -> TestResult { test_wrapper(with_fifo(make_id!(), basic)) }
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn tokio_recv_before_send() -> TestResult {
+    test_wrapper(with_fifo(make_id!(), recv_before_send))
+}
+#[test]
+fn tokio_no_reader() -> TestResult { test_wrapper(with_fifo(make_id!(), no_reader)) }
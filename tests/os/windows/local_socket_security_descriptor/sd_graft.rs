@@ -122,6 +122,7 @@ pub(super) fn test_main() -> TestResult {
 
     let listener_handle = match listener {
         Listener::NamedPipe(l) => OwnedHandle::from(l),
+        Listener::Tcp(..) => unreachable!("named pipe name selected loopback TCP"),
     };
     let listener_sd =
         get_sd(listener_handle.as_handle(), SE_KERNEL_OBJECT).opname("get listener SD")?;
@@ -1,8 +1,14 @@
 // TODO test various error conditions
 
+mod incoming;
 mod no_server;
 mod stream;
 mod off_runtime_drop;
+mod readiness;
+mod timeout;
+mod resilient;
+mod tcp;
+mod verify_server;
 
 use crate::tests::util::{self, tokio::test_wrapper, TestResult};
 
@@ -32,3 +38,30 @@ matrix! {
 fn no_server_file() -> TestResult { test_wrapper(no_server::run_and_verify_error(true)) }
 #[test]
 fn no_server_namespaced() -> TestResult { test_wrapper(no_server::run_and_verify_error(false)) }
+
+#[test]
+fn verify_server_file() -> TestResult { test_wrapper(verify_server::main(true)) }
+#[test]
+fn verify_server_namespaced() -> TestResult { test_wrapper(verify_server::main(false)) }
+
+#[test]
+fn tcp_file() -> TestResult { tcp::main(true) }
+#[test]
+fn tcp_namespaced() -> TestResult { tcp::main(false) }
+#[test]
+fn tcp_silent_client() -> TestResult { tcp::silent_client() }
+#[test]
+fn tcp_handshake_limit() -> TestResult { tcp::handshake_limit() }
+
+#[test]
+fn incoming() -> TestResult { test_wrapper(incoming::unlimited()) }
+#[test]
+fn incoming_limited() -> TestResult { incoming::main_limited() }
+
+#[test]
+fn readiness() -> TestResult { test_wrapper(readiness::main()) }
+#[test]
+fn timeouts() -> TestResult { timeout::main() }
+
+#[test]
+fn resilient_accept() -> TestResult { resilient::main() }
@@ -0,0 +1,77 @@
+//! Tests the `Stream` of incoming connections and its concurrency limiting.
+
+use {
+    crate::{
+        local_socket::{
+            tokio::{prelude::*, Stream},
+            ListenerOptions,
+        },
+        tests::util::*,
+    },
+    ::tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        time::timeout,
+    },
+    futures_lite::StreamExt,
+    std::time::Duration,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+const SHORT_TIMEOUT: Duration = Duration::from_millis(50);
+
+pub async fn unlimited() -> TestResult {
+    let (name, listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), true), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_tokio()
+        })?;
+    let mut incoming = listener.incoming();
+    for i in 0..3u8 {
+        let mut client = Stream::connect(name.borrow()).await.opname("client connect")?;
+        client.write_all(&[i]).await.opname("client send")?;
+        let mut server =
+            incoming.next().await.expect("incoming stream ended").opname("accept")?;
+        let mut buf = [0];
+        server.read_exact(&mut buf).await.opname("server receive")?;
+        ensure_eq!(buf, [i]);
+    }
+    Ok(())
+}
+
+pub async fn limited() -> TestResult {
+    let (name, listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), true), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_tokio()
+        })?;
+    let mut incoming = listener.incoming_limited(1);
+    ensure_eq!(incoming.available(), 1);
+
+    let _first = Stream::connect(name.borrow()).await.opname("first client connect")?;
+    let (_conn, permit) = timeout(TIMEOUT, incoming.next())
+        .await
+        .opname("first accept")?
+        .expect("incoming stream ended")
+        .opname("first accept")?;
+    ensure_eq!(incoming.available(), 0);
+
+    let _second = Stream::connect(name.borrow()).await.opname("second client connect")?;
+    ensure_eq!(timeout(SHORT_TIMEOUT, incoming.next()).await.is_err(), true);
+
+    drop(permit);
+    let _ = timeout(TIMEOUT, incoming.next())
+        .await
+        .opname("second accept")?
+        .expect("incoming stream ended")
+        .opname("second accept")?;
+    Ok(())
+}
+
+/// Timeouts require the time driver, which the common test wrapper does not enable.
+pub fn main_limited() -> TestResult {
+    test_wrapper(|| {
+        let rt = ::tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .opname("Tokio runtime spawn")?;
+        rt.block_on(limited())
+    })
+}
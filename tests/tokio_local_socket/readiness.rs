@@ -0,0 +1,62 @@
+//! Tests readiness-based I/O on Tokio streams.
+
+use {
+    crate::{
+        local_socket::{
+            tokio::{prelude::*, Stream},
+            ListenerOptions,
+        },
+        tests::util::*,
+    },
+    ::tokio::io::Interest,
+    std::io::{self, IoSlice, IoSliceMut},
+};
+
+async fn read_when_ready(
+    stream: &Stream,
+    mut f: impl FnMut(&Stream) -> io::Result<usize>,
+) -> io::Result<usize> {
+    loop {
+        stream.readable().await?;
+        match f(stream) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+            els => return els,
+        }
+    }
+}
+
+pub async fn main() -> TestResult {
+    let (name, listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), true), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_tokio()
+        })?;
+    let client = Stream::connect(name.borrow()).await.opname("client connect")?;
+    let server = listener.accept().await.opname("accept")?;
+
+    let mut buf = [0; 8];
+    ensure_eq!(
+        client.try_read(&mut buf).err().map(|e| e.kind()),
+        Some(io::ErrorKind::WouldBlock)
+    );
+
+    let ready = server.ready(Interest::READABLE | Interest::WRITABLE).await.opname("ready")?;
+    ensure_eq!(ready.is_writable(), true);
+    ensure_eq!(server.try_write(b"ping").opname("server try_write")?, 4);
+    let n = read_when_ready(&client, |c| c.try_read(&mut buf)).await.opname("client try_read")?;
+    ensure_eq!(&buf[..n], b"ping");
+
+    client.writable().await.opname("client writable")?;
+    let sent = client
+        .try_write_vectored(&[IoSlice::new(b"po"), IoSlice::new(b"ng")])
+        .opname("client try_write_vectored")?;
+    ensure_eq!(sent, 4);
+    let (mut a, mut b) = ([0; 2], [0; 2]);
+    let n = read_when_ready(&server, |s| {
+        s.try_read_vectored(&mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)])
+    })
+    .await
+    .opname("server try_read_vectored")?;
+    ensure_eq!(n, 4);
+    ensure_eq!([a, b], [*b"po", *b"ng"]);
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Tests the resilient accept loop with Tokio using a listener that fails on demand.
+
+use {
+    crate::{
+        local_socket::{
+            tokio::{prelude::*, Listener, Stream},
+            traits::tokio as traits,
+            AcceptStats, ListenerOptions, Name, ResilientAccept,
+        },
+        tests::util::*,
+        Sealed,
+    },
+    ::tokio::io::AsyncReadExt,
+    std::{collections::VecDeque, future::Future, io, sync::Mutex, time::Duration},
+};
+
+#[derive(Debug)]
+struct Flaky {
+    listener: Listener,
+    errors: Mutex<VecDeque<io::Error>>,
+}
+impl Sealed for Flaky {}
+impl traits::Listener for Flaky {
+    type Stream = Stream;
+    fn from_options(options: ListenerOptions<'_>) -> io::Result<Self> {
+        let listener = Listener::from_options(options)?;
+        Ok(Self { listener, errors: Mutex::default() })
+    }
+    fn accept(&self) -> impl Future<Output = io::Result<Stream>> + Send + Sync {
+        let e = self.errors.lock().unwrap().pop_front();
+        async move {
+            match e {
+                Some(e) => Err(e),
+                None => self.listener.accept().await,
+            }
+        }
+    }
+    fn do_not_reclaim_name_on_drop(&mut self) { self.listener.do_not_reclaim_name_on_drop() }
+    fn local_name(&self) -> io::Result<Name<'static>> { self.listener.local_name() }
+}
+
+fn out_of_fds() -> io::Error {
+    #[cfg(unix)]
+    {
+        io::Error::from_raw_os_error(libc::EMFILE)
+    }
+    #[cfg(not(unix))]
+    {
+        io::Error::from(io::ErrorKind::OutOfMemory)
+    }
+}
+
+async fn test_inner() -> TestResult {
+    let (name, listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), true), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_tokio_as::<Flaky>()
+        })?;
+    let mut resilient =
+        ResilientAccept::new().backoff(Duration::from_millis(1), Duration::from_millis(4));
+
+    let mut first = Stream::connect(name.borrow()).await.opname("first client connect")?;
+    let _second = Stream::connect(name.borrow()).await.opname("second client connect")?;
+    listener
+        .errors
+        .lock()
+        .unwrap()
+        .extend([io::Error::from(io::ErrorKind::ConnectionAborted), out_of_fds()]);
+    let _ = resilient.accept_tokio(&listener).await.opname("resilient accept")?;
+    let dropped = u64::from(cfg!(unix));
+    ensure_eq!(resilient.stats(), AcceptStats {
+        transient: 1,
+        resource_exhaustion: 1,
+        fatal: 0,
+        dropped
+    });
+    if cfg!(unix) {
+        ensure_eq!(first.read(&mut [0]).await.opname("first client receive")?, 0);
+    }
+    Ok(())
+}
+
+/// Backing off requires the time driver, which the common test wrapper does not enable.
+pub fn main() -> TestResult {
+    test_wrapper(|| {
+        let rt = ::tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .opname("Tokio runtime spawn")?;
+        rt.block_on(test_inner())
+    })
+}
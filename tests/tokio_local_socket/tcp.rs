@@ -0,0 +1,114 @@
+//! Tests the loopback TCP implementation of Tokio local sockets.
+
+use {
+    crate::{
+        local_socket::{
+            tokio::{prelude::*, Listener, Stream},
+            ListenerOptions,
+        },
+        tests::util::*,
+    },
+    ::tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+        time::{timeout, Duration},
+    },
+    color_eyre::eyre::bail,
+    std::future::Future,
+};
+
+async fn test_inner(path: bool) -> TestResult {
+    let (name, listener) = listen_and_pick_name(&mut namegen_tcp(make_id!(), path), |nm| {
+        ListenerOptions::new().name(nm.borrow()).create_tokio()
+    })?;
+    ensure_eq!(matches!(listener, Listener::Tcp(..)), true);
+    ensure_eq!(listener.local_name().opname("listener local_name")?, name);
+
+    let (client, server) = ::tokio::try_join!(Stream::connect(name.borrow()), listener.accept())
+        .opname("connect and accept")?;
+    let (mut client_rh, mut client_sh) = client.split();
+    let (mut server_rh, mut server_sh) = server.split();
+
+    client_sh.write_all(b"ping").await.opname("client send")?;
+    let mut buf = [0; 4];
+    server_rh.read_exact(&mut buf).await.opname("server receive")?;
+    ensure_eq!(&buf, b"ping");
+    server_sh.write_all(b"pong").await.opname("server send")?;
+    client_rh.read_exact(&mut buf).await.opname("client receive")?;
+    ensure_eq!(&buf, b"pong");
+
+    let client = Stream::reunite(client_rh, client_sh).opname("reunite")?;
+    ensure_eq!(matches!(client, Stream::Tcp(..)), true);
+    Ok(())
+}
+
+/// Accepting must neither be held up by a client that never sends the token nor lose the clients
+/// it has accepted when cancelled.
+async fn silent_client_inner() -> TestResult {
+    let (name, listener) = listen_and_pick_name(&mut namegen_tcp(make_id!(), true), |nm| {
+        ListenerOptions::new().name(nm.borrow()).create_tokio()
+    })?;
+    let Listener::Tcp(tcp_listener) = &listener else {
+        bail!("expected a loopback TCP listener, got {listener:?}")
+    };
+    let port = tcp_listener.port().opname("port")?;
+    let _impostor = TcpStream::connect(("127.0.0.1", port)).await.opname("impostor connect")?;
+    let cancelled = timeout(Duration::from_millis(50), listener.accept()).await;
+    ensure_eq!(cancelled.is_err(), true);
+
+    let (mut client, mut server) = timeout(Duration::from_millis(500), async {
+        ::tokio::try_join!(Stream::connect(name.borrow()), listener.accept())
+    })
+    .await
+    .opname("connect and accept timeout")?
+    .opname("connect and accept")?;
+    server.write_all(b"hi").await.opname("server send")?;
+    let mut buf = [0; 2];
+    client.read_exact(&mut buf).await.opname("client receive")?;
+    ensure_eq!(&buf, b"hi");
+    Ok(())
+}
+
+/// Once 64 clients are in the middle of the handshake, no more are accepted until they time out.
+async fn handshake_limit_inner() -> TestResult {
+    let (name, listener) = listen_and_pick_name(&mut namegen_tcp(make_id!(), true), |nm| {
+        ListenerOptions::new().name(nm.borrow()).create_tokio()
+    })?;
+    let Listener::Tcp(tcp_listener) = &listener else {
+        bail!("expected a loopback TCP listener, got {listener:?}")
+    };
+    let port = tcp_listener.port().opname("port")?;
+    let mut impostors = Vec::with_capacity(64);
+    for _ in 0..64 {
+        impostors.push(TcpStream::connect(("127.0.0.1", port)).await.opname("impostor connect")?);
+    }
+    let cancelled = timeout(Duration::from_millis(100), listener.accept()).await;
+    ensure_eq!(cancelled.is_err(), true);
+
+    let mut client = Stream::connect(name.borrow()).await.opname("client connect")?;
+    let held_up = timeout(Duration::from_millis(300), listener.accept()).await;
+    ensure_eq!(held_up.is_err(), true);
+    let mut server = timeout(Duration::from_secs(5), listener.accept())
+        .await
+        .opname("accept timeout")?
+        .opname("accept")?;
+    server.write_all(b"hi").await.opname("server send")?;
+    let mut buf = [0; 2];
+    client.read_exact(&mut buf).await.opname("client receive")?;
+    ensure_eq!(&buf, b"hi");
+    Ok(())
+}
+
+/// The handshake timeout requires the time driver, which the common test wrapper does not enable.
+fn run(f: impl Future<Output = TestResult> + Send + 'static) -> TestResult {
+    test_wrapper(move || {
+        let rt = ::tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .opname("Tokio runtime spawn")?;
+        rt.block_on(f)
+    })
+}
+pub fn main(path: bool) -> TestResult { run(test_inner(path)) }
+pub fn silent_client() -> TestResult { run(silent_client_inner()) }
+pub fn handshake_limit() -> TestResult { run(handshake_limit_inner()) }
@@ -0,0 +1,74 @@
+//! Tests receive, send and idle timeouts on Tokio streams and their halves.
+
+use {
+    crate::{
+        local_socket::{
+            tokio::{prelude::*, Stream},
+            traits::tokio::{RecvHalf as _, SendHalf as _},
+            ListenerOptions,
+        },
+        tests::util::*,
+    },
+    ::tokio::io::{AsyncReadExt, AsyncWriteExt},
+    std::{io, time::Duration},
+};
+
+const TIMEOUT: Duration = Duration::from_millis(50);
+
+fn is_timed_out<T>(rslt: io::Result<T>) -> bool {
+    matches!(rslt, Err(e) if e.kind() == io::ErrorKind::TimedOut)
+}
+
+async fn test_inner() -> TestResult {
+    let (name, listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), true), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_tokio()
+        })?;
+    let mut client = Stream::connect(name.borrow()).await.opname("client connect")?;
+    let mut server = listener.accept().await.opname("accept")?;
+    let mut buf = [0; 4];
+
+    ensure_eq!(client.set_recv_timeout(Some(Duration::ZERO)).is_err(), true);
+    client.set_recv_timeout(Some(TIMEOUT)).opname("set receive timeout")?;
+    ensure_eq!(is_timed_out(client.read(&mut buf).await), true);
+    // The timeout only applies to waiting, not to data that is already available
+    server.write_all(b"ping").await.opname("server send")?;
+    client.read_exact(&mut buf).await.opname("client receive")?;
+    ensure_eq!(&buf, b"ping");
+    client.set_recv_timeout(None).opname("clear receive timeout")?;
+
+    client.set_send_timeout(Some(TIMEOUT)).opname("set send timeout")?;
+    // The server isn't reading, so the socket buffer eventually fills up
+    let big = vec![0; 1024 * 1024 * 16];
+    ensure_eq!(is_timed_out(client.write_all(&big).await), true);
+
+    server.set_idle_timeout(Some(TIMEOUT)).opname("set idle timeout")?;
+    let mut drain = vec![0; 1024 * 64];
+    let idle = loop {
+        match server.read(&mut drain).await {
+            Ok(0) => break Ok(()),
+            Ok(_) => continue,
+            Err(e) => break Err(e),
+        }
+    };
+    ensure_eq!(is_timed_out(idle), true);
+    server.set_idle_timeout(None).opname("clear idle timeout")?;
+
+    let (mut rh, mut sh) = server.split();
+    rh.set_timeout(Some(TIMEOUT)).opname("set receive half timeout")?;
+    sh.set_timeout(Some(TIMEOUT)).opname("set send half timeout")?;
+    ensure_eq!(is_timed_out(rh.read(&mut buf).await), true);
+    sh.write_all(b"pong").await.opname("send half send")?;
+    Ok(())
+}
+
+/// Timeouts require the time driver, which the common test wrapper does not enable.
+pub fn main() -> TestResult {
+    test_wrapper(|| {
+        let rt = ::tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .opname("Tokio runtime spawn")?;
+        rt.block_on(test_inner())
+    })
+}
@@ -0,0 +1,28 @@
+//! Tests verification of the identity of the server by the client with Tokio.
+
+use {
+    crate::{
+        local_socket::{tokio::prelude::*, ConnectOptions, ListenerOptions},
+        tests::util::*,
+    },
+    std::io,
+};
+
+pub async fn main(path: bool) -> TestResult {
+    let (name, listener) =
+        listen_and_pick_name(&mut namegen_local_socket(make_id!(), path), |nm| {
+            ListenerOptions::new().name(nm.borrow()).create_tokio()
+        })?;
+    let _ = ConnectOptions::new()
+        .name(name.borrow())
+        .expect_server(|_| true)
+        .connect_tokio()
+        .await
+        .opname("connect with passing check")?;
+    let _ = listener.accept().await.opname("accept")?;
+
+    let err =
+        ConnectOptions::new().name(name.borrow()).expect_server(|_| false).connect_tokio().await;
+    ensure_eq!(err.err().map(|e| e.kind()), Some(io::ErrorKind::PermissionDenied));
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use {
     super::Xorshift32,
-    crate::local_socket::{GenericFilePath, GenericNamespaced, Name, ToFsName, ToNsName},
-    std::io,
+    crate::local_socket::{
+        GenericFilePath, GenericNamespaced, LoopbackTcp, Name, ToFsName, ToNsName,
+    },
+    std::{io, path::PathBuf},
 };
 
 #[derive(Copy, Clone, Debug)]
@@ -33,19 +35,43 @@ fn next_fs(rn: u32) -> io::Result<Name<'static>> {
         unreachable!()
     }
     .to_fs_name::<GenericFilePath>()
+    .map_err(io::Error::from)
 }
 fn next_ns(rn: u32) -> io::Result<Name<'static>> {
-    format!("interprocess-test-{:08x}", rn).to_ns_name::<GenericNamespaced>()
+    format!("interprocess-test-{:08x}", rn)
+        .to_ns_name::<GenericNamespaced>()
+        .map_err(io::Error::from)
+}
+
+pub fn namegen_tcp(
+    id: &str,
+    path: bool,
+) -> NameGen<Name<'static>, impl FnMut(u32) -> io::Result<Name<'static>>> {
+    NameGen::new(id, move |rn| {
+        if path {
+            std::env::temp_dir()
+                .join(format!("interprocess-test-{rn:08x}.port"))
+                .to_fs_name::<LoopbackTcp>()
+        } else {
+            format!("interprocess-test-{rn:08x}").to_ns_name::<LoopbackTcp>()
+        }
+        .map_err(io::Error::from)
+    })
 }
 
 pub fn namegen_named_pipe(id: &str) -> NameGen<String, impl FnMut(u32) -> io::Result<String>> {
     NameGen::new(id, |rn| Ok(windows_path(rn)))
 }
 
+pub fn namegen_fifo(id: &str) -> NameGen<PathBuf, impl FnMut(u32) -> io::Result<PathBuf>> {
+    NameGen::new(id, |rn| Ok(tmpdir_path(rn, "fifo").into()))
+}
+
 fn windows_path(rn: u32) -> String { format!(r"\\.\pipe\interprocess-test-{rn:08x}") }
-fn unix_path(rn: u32) -> String {
+fn unix_path(rn: u32) -> String { tmpdir_path(rn, "sock") }
+fn tmpdir_path(rn: u32, ext: &str) -> String {
     let tmpdir = std::env::var("TMPDIR").ok();
-    format!("{}/interprocess-test-{rn:08x}.sock", tmpdir.as_deref().unwrap_or("/tmp"))
+    format!("{}/interprocess-test-{rn:08x}.{ext}", tmpdir.as_deref().unwrap_or("/tmp"))
 }
 
 macro_rules! make_id {